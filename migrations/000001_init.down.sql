@@ -1,5 +1,5 @@
 BEGIN;
 
-DROP TABLE IF EXISTS rust_simple_chat.messages;
+DROP TABLE IF EXISTS messages;
 
 COMMIT;
@@ -1,6 +1,6 @@
 BEGIN;
 
-CREATE TABLE IF NOT EXISTS rust_simple_chat.messages
+CREATE TABLE IF NOT EXISTS messages
 (
     message_id      bigserial,
     message_content varchar(300),
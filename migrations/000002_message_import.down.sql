@@ -0,0 +1,8 @@
+BEGIN;
+
+DROP INDEX IF EXISTS messages_external_id_uindex;
+
+ALTER TABLE messages
+    DROP COLUMN IF EXISTS external_id;
+
+COMMIT;
@@ -0,0 +1,9 @@
+BEGIN;
+
+ALTER TABLE messages
+    ADD COLUMN IF NOT EXISTS external_id varchar(255);
+
+CREATE UNIQUE INDEX IF NOT EXISTS messages_external_id_uindex
+    ON messages (external_id);
+
+COMMIT;
@@ -0,0 +1,35 @@
+BEGIN;
+
+ALTER TABLE messages
+    ADD COLUMN IF NOT EXISTS edited_at  timestamptz,
+    ADD COLUMN IF NOT EXISTS deleted_at timestamptz;
+
+CREATE TABLE IF NOT EXISTS webhooks
+(
+    webhook_id bigserial PRIMARY KEY,
+    url        varchar(2048) NOT NULL,
+    secret     varchar(128)  NOT NULL,
+    events     varchar(64)[] NOT NULL,
+    created_by integer       NOT NULL,
+    created_at timestamptz   NOT NULL DEFAULT now()
+);
+
+CREATE TABLE IF NOT EXISTS webhook_outbox
+(
+    delivery_id     bigserial PRIMARY KEY,
+    webhook_id      bigint      NOT NULL REFERENCES webhooks (webhook_id) ON DELETE CASCADE,
+    event_type      varchar(64) NOT NULL,
+    payload         jsonb       NOT NULL,
+    status          varchar(16) NOT NULL DEFAULT 'pending',
+    attempts        integer     NOT NULL DEFAULT 0,
+    next_attempt_at timestamptz NOT NULL DEFAULT now(),
+    last_error      text,
+    created_at      timestamptz NOT NULL DEFAULT now(),
+    delivered_at    timestamptz
+);
+
+CREATE INDEX IF NOT EXISTS webhook_outbox_pending_idx
+    ON webhook_outbox (next_attempt_at)
+    WHERE status = 'pending';
+
+COMMIT;
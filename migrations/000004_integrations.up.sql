@@ -0,0 +1,13 @@
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS integrations
+(
+    integration_id bigserial PRIMARY KEY,
+    name           varchar(100) NOT NULL,
+    token_hash     varchar(64)  NOT NULL UNIQUE,
+    created_by     integer      NOT NULL,
+    created_at     timestamptz  NOT NULL DEFAULT now(),
+    revoked_at     timestamptz
+);
+
+COMMIT;
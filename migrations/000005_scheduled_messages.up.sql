@@ -0,0 +1,18 @@
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS scheduled_messages
+(
+    scheduled_id    bigserial PRIMARY KEY,
+    message_content varchar(300) NOT NULL,
+    user_id         integer      NOT NULL,
+    send_at         timestamptz  NOT NULL,
+    created_at      timestamptz  NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS scheduled_messages_send_at_index
+    ON scheduled_messages (send_at);
+
+CREATE INDEX IF NOT EXISTS scheduled_messages_user_id_index
+    ON scheduled_messages (user_id);
+
+COMMIT;
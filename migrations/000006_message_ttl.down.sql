@@ -0,0 +1,11 @@
+BEGIN;
+
+DROP INDEX IF EXISTS messages_expires_at_index;
+
+ALTER TABLE scheduled_messages
+    DROP COLUMN IF EXISTS expires_in;
+
+ALTER TABLE messages
+    DROP COLUMN IF EXISTS expires_at;
+
+COMMIT;
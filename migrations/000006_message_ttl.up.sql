@@ -0,0 +1,13 @@
+BEGIN;
+
+ALTER TABLE messages
+    ADD COLUMN IF NOT EXISTS expires_at timestamptz;
+
+ALTER TABLE scheduled_messages
+    ADD COLUMN IF NOT EXISTS expires_in bigint;
+
+CREATE INDEX IF NOT EXISTS messages_expires_at_index
+    ON messages (expires_at)
+    WHERE expires_at IS NOT NULL;
+
+COMMIT;
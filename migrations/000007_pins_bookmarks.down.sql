@@ -0,0 +1,9 @@
+BEGIN;
+
+DROP TRIGGER IF EXISTS messages_forget_on_soft_delete ON messages;
+DROP TRIGGER IF EXISTS messages_forget_on_delete ON messages;
+DROP FUNCTION IF EXISTS forget_message_references();
+DROP TABLE IF EXISTS bookmarks;
+DROP TABLE IF EXISTS pins;
+
+COMMIT;
@@ -0,0 +1,45 @@
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS pins
+(
+    message_id bigint PRIMARY KEY,
+    pinned_by  integer     NOT NULL,
+    pinned_at  timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE TABLE IF NOT EXISTS bookmarks
+(
+    user_id       integer     NOT NULL,
+    message_id    bigint      NOT NULL,
+    bookmarked_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (user_id, message_id)
+);
+
+CREATE INDEX IF NOT EXISTS bookmarks_message_id_index
+    ON bookmarks (message_id);
+
+-- messages are removed softly by users and physically by retention and expiry jobs,
+-- pins and bookmarks must go away in both cases
+CREATE OR REPLACE FUNCTION forget_message_references() RETURNS trigger AS
+$$
+BEGIN
+    DELETE FROM pins WHERE message_id = OLD.message_id;
+    DELETE FROM bookmarks WHERE message_id = OLD.message_id;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER messages_forget_on_delete
+    AFTER DELETE
+    ON messages
+    FOR EACH ROW
+EXECUTE FUNCTION forget_message_references();
+
+CREATE TRIGGER messages_forget_on_soft_delete
+    AFTER UPDATE OF deleted_at
+    ON messages
+    FOR EACH ROW
+    WHEN (OLD.deleted_at IS NULL AND NEW.deleted_at IS NOT NULL)
+EXECUTE FUNCTION forget_message_references();
+
+COMMIT;
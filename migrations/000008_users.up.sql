@@ -0,0 +1,12 @@
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS users
+(
+    user_id      integer PRIMARY KEY,
+    display_name varchar(64),
+    avatar_url   varchar(2048),
+    bio          varchar(500),
+    updated_at   timestamptz NOT NULL DEFAULT now()
+);
+
+COMMIT;
@@ -0,0 +1,11 @@
+BEGIN;
+
+DROP INDEX IF EXISTS messages_posted_at_index;
+
+ALTER TABLE messages
+    DROP CONSTRAINT IF EXISTS messages_pkey,
+    ALTER COLUMN message_content DROP NOT NULL,
+    ALTER COLUMN user_id DROP NOT NULL,
+    ALTER COLUMN posted_at DROP NOT NULL;
+
+COMMIT;
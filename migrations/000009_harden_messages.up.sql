@@ -0,0 +1,22 @@
+BEGIN;
+
+-- rows written before the constraints existed get neutral values instead of being dropped
+UPDATE messages
+SET message_content = coalesce(message_content, ''),
+    user_id         = coalesce(user_id, 0),
+    posted_at       = coalesce(posted_at, 'epoch')
+WHERE message_content IS NULL
+   OR user_id IS NULL
+   OR posted_at IS NULL;
+
+ALTER TABLE messages
+    ALTER COLUMN message_content SET NOT NULL,
+    ALTER COLUMN user_id SET NOT NULL,
+    ALTER COLUMN posted_at SET NOT NULL,
+    ADD CONSTRAINT messages_pkey PRIMARY KEY (message_id);
+
+-- listings are ordered by (posted_at, message_id) in both directions
+CREATE INDEX IF NOT EXISTS messages_posted_at_index
+    ON messages (posted_at, message_id);
+
+COMMIT;
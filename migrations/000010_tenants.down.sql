@@ -0,0 +1,28 @@
+BEGIN;
+
+DROP POLICY IF EXISTS messages_tenant_isolation ON messages;
+
+ALTER TABLE messages
+    NO FORCE ROW LEVEL SECURITY;
+ALTER TABLE messages
+    DISABLE ROW LEVEL SECURITY;
+
+DROP INDEX IF EXISTS scheduled_messages_tenant_user_id_index;
+DROP INDEX IF EXISTS messages_tenant_posted_at_index;
+
+DROP INDEX IF EXISTS messages_external_id_uindex;
+CREATE UNIQUE INDEX IF NOT EXISTS messages_external_id_uindex
+    ON messages (external_id);
+
+ALTER TABLE webhooks
+    DROP COLUMN IF EXISTS tenant_id;
+ALTER TABLE integrations
+    DROP COLUMN IF EXISTS tenant_id;
+ALTER TABLE scheduled_messages
+    DROP COLUMN IF EXISTS tenant_id;
+ALTER TABLE messages
+    DROP COLUMN IF EXISTS tenant_id;
+
+DROP TABLE IF EXISTS tenants;
+
+COMMIT;
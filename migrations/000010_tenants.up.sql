@@ -0,0 +1,60 @@
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS tenants
+(
+    tenant_id    serial PRIMARY KEY,
+    name         varchar(100) NOT NULL,
+    created_at   timestamptz  NOT NULL DEFAULT now(),
+    suspended_at timestamptz
+);
+
+-- everything written before tenants existed belongs to the default tenant
+INSERT INTO tenants (tenant_id, name)
+VALUES (1, 'default')
+ON CONFLICT DO NOTHING;
+
+SELECT setval(pg_get_serial_sequence('tenants', 'tenant_id'), (SELECT max(tenant_id) FROM tenants));
+
+ALTER TABLE messages
+    ADD COLUMN IF NOT EXISTS tenant_id integer NOT NULL DEFAULT 1 REFERENCES tenants (tenant_id);
+ALTER TABLE scheduled_messages
+    ADD COLUMN IF NOT EXISTS tenant_id integer NOT NULL DEFAULT 1 REFERENCES tenants (tenant_id);
+ALTER TABLE integrations
+    ADD COLUMN IF NOT EXISTS tenant_id integer NOT NULL DEFAULT 1 REFERENCES tenants (tenant_id);
+ALTER TABLE webhooks
+    ADD COLUMN IF NOT EXISTS tenant_id integer NOT NULL DEFAULT 1 REFERENCES tenants (tenant_id);
+
+-- new rows must name their tenant explicitly
+ALTER TABLE messages
+    ALTER COLUMN tenant_id DROP DEFAULT;
+ALTER TABLE scheduled_messages
+    ALTER COLUMN tenant_id DROP DEFAULT;
+ALTER TABLE integrations
+    ALTER COLUMN tenant_id DROP DEFAULT;
+ALTER TABLE webhooks
+    ALTER COLUMN tenant_id DROP DEFAULT;
+
+-- imports are deduplicated per tenant
+DROP INDEX IF EXISTS messages_external_id_uindex;
+CREATE UNIQUE INDEX IF NOT EXISTS messages_external_id_uindex
+    ON messages (tenant_id, external_id);
+
+CREATE INDEX IF NOT EXISTS messages_tenant_posted_at_index
+    ON messages (tenant_id, posted_at, message_id);
+
+CREATE INDEX IF NOT EXISTS scheduled_messages_tenant_user_id_index
+    ON scheduled_messages (tenant_id, user_id);
+
+-- rows are visible only to transactions scoped to their tenant, or to maintenance jobs scoped
+-- to all tenants. FORCE applies the policy to the table owner as well, superusers and roles
+-- with BYPASSRLS still bypass it.
+ALTER TABLE messages
+    ENABLE ROW LEVEL SECURITY;
+ALTER TABLE messages
+    FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY messages_tenant_isolation ON messages
+    USING (tenant_id = nullif(current_setting('app.tenant_id', true), '')::integer
+        OR current_setting('app.all_tenants', true) = 'on');
+
+COMMIT;
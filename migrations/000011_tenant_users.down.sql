@@ -0,0 +1,23 @@
+BEGIN;
+
+DROP POLICY IF EXISTS users_tenant_isolation ON users;
+
+ALTER TABLE users
+    NO FORCE ROW LEVEL SECURITY;
+ALTER TABLE users
+    DISABLE ROW LEVEL SECURITY;
+
+-- only profiles of the default tenant fit into the shared key
+DELETE
+FROM users
+WHERE tenant_id <> 1;
+
+ALTER TABLE users
+    DROP CONSTRAINT IF EXISTS users_pkey;
+ALTER TABLE users
+    ADD PRIMARY KEY (user_id);
+
+ALTER TABLE users
+    DROP COLUMN IF EXISTS tenant_id;
+
+COMMIT;
@@ -0,0 +1,24 @@
+BEGIN;
+
+-- profiles are per tenant like the messages showing them, existing ones belong to the default
+-- tenant
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS tenant_id integer NOT NULL DEFAULT 1 REFERENCES tenants (tenant_id);
+ALTER TABLE users
+    ALTER COLUMN tenant_id DROP DEFAULT;
+
+ALTER TABLE users
+    DROP CONSTRAINT IF EXISTS users_pkey;
+ALTER TABLE users
+    ADD PRIMARY KEY (tenant_id, user_id);
+
+ALTER TABLE users
+    ENABLE ROW LEVEL SECURITY;
+ALTER TABLE users
+    FORCE ROW LEVEL SECURITY;
+
+CREATE POLICY users_tenant_isolation ON users
+    USING (tenant_id = nullif(current_setting('app.tenant_id', true), '')::integer
+        OR current_setting('app.all_tenants', true) = 'on');
+
+COMMIT;
@@ -0,0 +1,16 @@
+BEGIN;
+
+ALTER TABLE bookmarks
+    DROP CONSTRAINT IF EXISTS bookmarks_message_fkey;
+ALTER TABLE bookmarks
+    DROP COLUMN IF EXISTS tenant_id;
+
+ALTER TABLE pins
+    DROP CONSTRAINT IF EXISTS pins_message_fkey;
+ALTER TABLE pins
+    DROP COLUMN IF EXISTS tenant_id;
+
+ALTER TABLE messages
+    DROP CONSTRAINT IF EXISTS messages_tenant_message_key;
+
+COMMIT;
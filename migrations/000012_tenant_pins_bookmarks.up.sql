@@ -0,0 +1,41 @@
+BEGIN;
+
+-- messages are only visible under the tenant isolation policy, the backfill reads all of them
+SELECT set_config('app.all_tenants', 'on', true);
+
+-- pins and bookmarks belong to the tenant of their message, so that they can be isolated on
+-- their own instead of through a join with messages
+ALTER TABLE messages
+    ADD CONSTRAINT messages_tenant_message_key UNIQUE (tenant_id, message_id);
+
+ALTER TABLE pins
+    ADD COLUMN IF NOT EXISTS tenant_id integer REFERENCES tenants (tenant_id);
+UPDATE pins p
+SET tenant_id = m.tenant_id
+FROM messages m
+WHERE m.message_id = p.message_id;
+DELETE
+FROM pins
+WHERE tenant_id IS NULL;
+ALTER TABLE pins
+    ALTER COLUMN tenant_id SET NOT NULL;
+ALTER TABLE pins
+    ADD CONSTRAINT pins_message_fkey FOREIGN KEY (tenant_id, message_id)
+        REFERENCES messages (tenant_id, message_id) ON DELETE CASCADE;
+
+ALTER TABLE bookmarks
+    ADD COLUMN IF NOT EXISTS tenant_id integer REFERENCES tenants (tenant_id);
+UPDATE bookmarks b
+SET tenant_id = m.tenant_id
+FROM messages m
+WHERE m.message_id = b.message_id;
+DELETE
+FROM bookmarks
+WHERE tenant_id IS NULL;
+ALTER TABLE bookmarks
+    ALTER COLUMN tenant_id SET NOT NULL;
+ALTER TABLE bookmarks
+    ADD CONSTRAINT bookmarks_message_fkey FOREIGN KEY (tenant_id, message_id)
+        REFERENCES messages (tenant_id, message_id) ON DELETE CASCADE;
+
+COMMIT;
@@ -0,0 +1,42 @@
+BEGIN;
+
+DROP POLICY IF EXISTS webhook_outbox_tenant_isolation ON webhook_outbox;
+ALTER TABLE webhook_outbox
+    NO FORCE ROW LEVEL SECURITY;
+ALTER TABLE webhook_outbox
+    DISABLE ROW LEVEL SECURITY;
+
+DROP POLICY IF EXISTS webhooks_tenant_isolation ON webhooks;
+ALTER TABLE webhooks
+    NO FORCE ROW LEVEL SECURITY;
+ALTER TABLE webhooks
+    DISABLE ROW LEVEL SECURITY;
+
+DROP POLICY IF EXISTS integrations_tenant_isolation ON integrations;
+ALTER TABLE integrations
+    NO FORCE ROW LEVEL SECURITY;
+ALTER TABLE integrations
+    DISABLE ROW LEVEL SECURITY;
+
+DROP POLICY IF EXISTS scheduled_messages_tenant_isolation ON scheduled_messages;
+ALTER TABLE scheduled_messages
+    NO FORCE ROW LEVEL SECURITY;
+ALTER TABLE scheduled_messages
+    DISABLE ROW LEVEL SECURITY;
+
+DROP POLICY IF EXISTS bookmarks_tenant_isolation ON bookmarks;
+ALTER TABLE bookmarks
+    NO FORCE ROW LEVEL SECURITY;
+ALTER TABLE bookmarks
+    DISABLE ROW LEVEL SECURITY;
+
+DROP POLICY IF EXISTS pins_tenant_isolation ON pins;
+ALTER TABLE pins
+    NO FORCE ROW LEVEL SECURITY;
+ALTER TABLE pins
+    DISABLE ROW LEVEL SECURITY;
+
+ALTER TABLE webhook_outbox
+    DROP COLUMN IF EXISTS tenant_id;
+
+COMMIT;
@@ -0,0 +1,62 @@
+BEGIN;
+
+-- deliveries belong to the tenant of their webhook
+ALTER TABLE webhook_outbox
+    ADD COLUMN IF NOT EXISTS tenant_id integer REFERENCES tenants (tenant_id);
+UPDATE webhook_outbox o
+SET tenant_id = w.tenant_id
+FROM webhooks w
+WHERE w.webhook_id = o.webhook_id;
+ALTER TABLE webhook_outbox
+    ALTER COLUMN tenant_id SET NOT NULL;
+
+-- every tenant owned table is isolated like messages and users
+ALTER TABLE pins
+    ENABLE ROW LEVEL SECURITY;
+ALTER TABLE pins
+    FORCE ROW LEVEL SECURITY;
+CREATE POLICY pins_tenant_isolation ON pins
+    USING (tenant_id = nullif(current_setting('app.tenant_id', true), '')::integer
+        OR current_setting('app.all_tenants', true) = 'on');
+
+ALTER TABLE bookmarks
+    ENABLE ROW LEVEL SECURITY;
+ALTER TABLE bookmarks
+    FORCE ROW LEVEL SECURITY;
+CREATE POLICY bookmarks_tenant_isolation ON bookmarks
+    USING (tenant_id = nullif(current_setting('app.tenant_id', true), '')::integer
+        OR current_setting('app.all_tenants', true) = 'on');
+
+ALTER TABLE scheduled_messages
+    ENABLE ROW LEVEL SECURITY;
+ALTER TABLE scheduled_messages
+    FORCE ROW LEVEL SECURITY;
+CREATE POLICY scheduled_messages_tenant_isolation ON scheduled_messages
+    USING (tenant_id = nullif(current_setting('app.tenant_id', true), '')::integer
+        OR current_setting('app.all_tenants', true) = 'on');
+
+ALTER TABLE integrations
+    ENABLE ROW LEVEL SECURITY;
+ALTER TABLE integrations
+    FORCE ROW LEVEL SECURITY;
+CREATE POLICY integrations_tenant_isolation ON integrations
+    USING (tenant_id = nullif(current_setting('app.tenant_id', true), '')::integer
+        OR current_setting('app.all_tenants', true) = 'on');
+
+ALTER TABLE webhooks
+    ENABLE ROW LEVEL SECURITY;
+ALTER TABLE webhooks
+    FORCE ROW LEVEL SECURITY;
+CREATE POLICY webhooks_tenant_isolation ON webhooks
+    USING (tenant_id = nullif(current_setting('app.tenant_id', true), '')::integer
+        OR current_setting('app.all_tenants', true) = 'on');
+
+ALTER TABLE webhook_outbox
+    ENABLE ROW LEVEL SECURITY;
+ALTER TABLE webhook_outbox
+    FORCE ROW LEVEL SECURITY;
+CREATE POLICY webhook_outbox_tenant_isolation ON webhook_outbox
+    USING (tenant_id = nullif(current_setting('app.tenant_id', true), '')::integer
+        OR current_setting('app.all_tenants', true) = 'on');
+
+COMMIT;
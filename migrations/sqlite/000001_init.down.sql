@@ -0,0 +1,10 @@
+BEGIN;
+
+DROP TRIGGER IF EXISTS messages_forget_references_on_soft_delete;
+DROP TRIGGER IF EXISTS messages_forget_references_on_delete;
+DROP TABLE IF EXISTS bookmarks;
+DROP TABLE IF EXISTS pins;
+DROP TABLE IF EXISTS users;
+DROP TABLE IF EXISTS messages;
+
+COMMIT;
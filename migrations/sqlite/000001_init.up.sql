@@ -0,0 +1,61 @@
+BEGIN;
+
+-- timestamps are unix microseconds, so they sort and compare as integers
+
+CREATE TABLE IF NOT EXISTS messages
+(
+    message_id      integer PRIMARY KEY AUTOINCREMENT,
+    external_id     text UNIQUE,
+    message_content text    NOT NULL,
+    user_id         integer NOT NULL,
+    posted_at       integer NOT NULL,
+    expires_at      integer,
+    edited_at       integer,
+    deleted_at      integer
+);
+
+CREATE INDEX IF NOT EXISTS messages_posted_at_idx ON messages (posted_at, message_id);
+CREATE INDEX IF NOT EXISTS messages_expires_at_idx ON messages (expires_at) WHERE expires_at IS NOT NULL;
+
+CREATE TABLE IF NOT EXISTS users
+(
+    user_id      integer PRIMARY KEY,
+    display_name text,
+    avatar_url   text,
+    bio          text,
+    updated_at   integer NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS pins
+(
+    message_id integer PRIMARY KEY,
+    pinned_by  integer NOT NULL,
+    pinned_at  integer NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS bookmarks
+(
+    user_id       integer NOT NULL,
+    message_id    integer NOT NULL,
+    bookmarked_at integer NOT NULL,
+    PRIMARY KEY (user_id, message_id)
+);
+
+CREATE TRIGGER IF NOT EXISTS messages_forget_references_on_delete
+    AFTER DELETE
+    ON messages
+BEGIN
+    DELETE FROM pins WHERE message_id = old.message_id;
+    DELETE FROM bookmarks WHERE message_id = old.message_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS messages_forget_references_on_soft_delete
+    AFTER UPDATE OF deleted_at
+    ON messages
+    WHEN new.deleted_at IS NOT NULL
+BEGIN
+    DELETE FROM pins WHERE message_id = new.message_id;
+    DELETE FROM bookmarks WHERE message_id = new.message_id;
+END;
+
+COMMIT;
@@ -0,0 +1,53 @@
+BEGIN;
+
+CREATE TABLE messages_single
+(
+    message_id      integer PRIMARY KEY AUTOINCREMENT,
+    external_id     text UNIQUE,
+    message_content text    NOT NULL,
+    user_id         integer NOT NULL,
+    posted_at       integer NOT NULL,
+    expires_at      integer,
+    edited_at       integer,
+    deleted_at      integer
+);
+
+INSERT INTO messages_single (message_id, external_id, message_content, user_id, posted_at,
+                             expires_at, edited_at, deleted_at)
+SELECT message_id,
+       external_id,
+       message_content,
+       user_id,
+       posted_at,
+       expires_at,
+       edited_at,
+       deleted_at
+FROM messages
+WHERE tenant_id = 1;
+
+DROP TABLE messages;
+ALTER TABLE messages_single RENAME TO messages;
+
+CREATE INDEX IF NOT EXISTS messages_posted_at_idx ON messages (posted_at, message_id);
+CREATE INDEX IF NOT EXISTS messages_expires_at_idx ON messages (expires_at) WHERE expires_at IS NOT NULL;
+
+CREATE TRIGGER IF NOT EXISTS messages_forget_references_on_delete
+    AFTER DELETE
+    ON messages
+BEGIN
+    DELETE FROM pins WHERE message_id = old.message_id;
+    DELETE FROM bookmarks WHERE message_id = old.message_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS messages_forget_references_on_soft_delete
+    AFTER UPDATE OF deleted_at
+    ON messages
+    WHEN new.deleted_at IS NOT NULL
+BEGIN
+    DELETE FROM pins WHERE message_id = new.message_id;
+    DELETE FROM bookmarks WHERE message_id = new.message_id;
+END;
+
+DROP TABLE IF EXISTS tenants;
+
+COMMIT;
@@ -0,0 +1,67 @@
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS tenants
+(
+    tenant_id    integer PRIMARY KEY AUTOINCREMENT,
+    name         text    NOT NULL,
+    created_at   integer NOT NULL,
+    suspended_at integer
+);
+
+-- everything written before tenants existed belongs to the default tenant
+INSERT OR IGNORE INTO tenants (tenant_id, name, created_at)
+VALUES (1, 'default', CAST(unixepoch('subsec') * 1000000 AS integer));
+
+-- imports are deduplicated per tenant, which takes rebuilding the table since the inline
+-- unique constraint cannot be dropped
+CREATE TABLE messages_tenants
+(
+    message_id      integer PRIMARY KEY AUTOINCREMENT,
+    tenant_id       integer NOT NULL REFERENCES tenants (tenant_id),
+    external_id     text,
+    message_content text    NOT NULL,
+    user_id         integer NOT NULL,
+    posted_at       integer NOT NULL,
+    expires_at      integer,
+    edited_at       integer,
+    deleted_at      integer,
+    UNIQUE (tenant_id, external_id)
+);
+
+INSERT INTO messages_tenants (message_id, tenant_id, external_id, message_content, user_id,
+                              posted_at, expires_at, edited_at, deleted_at)
+SELECT message_id,
+       1,
+       external_id,
+       message_content,
+       user_id,
+       posted_at,
+       expires_at,
+       edited_at,
+       deleted_at
+FROM messages;
+
+DROP TABLE messages;
+ALTER TABLE messages_tenants RENAME TO messages;
+
+CREATE INDEX IF NOT EXISTS messages_tenant_posted_at_idx ON messages (tenant_id, posted_at, message_id);
+CREATE INDEX IF NOT EXISTS messages_expires_at_idx ON messages (expires_at) WHERE expires_at IS NOT NULL;
+
+CREATE TRIGGER IF NOT EXISTS messages_forget_references_on_delete
+    AFTER DELETE
+    ON messages
+BEGIN
+    DELETE FROM pins WHERE message_id = old.message_id;
+    DELETE FROM bookmarks WHERE message_id = old.message_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS messages_forget_references_on_soft_delete
+    AFTER UPDATE OF deleted_at
+    ON messages
+    WHEN new.deleted_at IS NOT NULL
+BEGIN
+    DELETE FROM pins WHERE message_id = new.message_id;
+    DELETE FROM bookmarks WHERE message_id = new.message_id;
+END;
+
+COMMIT;
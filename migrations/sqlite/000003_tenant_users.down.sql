@@ -0,0 +1,24 @@
+BEGIN;
+
+CREATE TABLE users_single
+(
+    user_id      integer PRIMARY KEY,
+    display_name text,
+    avatar_url   text,
+    bio          text,
+    updated_at   integer NOT NULL
+);
+
+INSERT INTO users_single (user_id, display_name, avatar_url, bio, updated_at)
+SELECT user_id,
+       display_name,
+       avatar_url,
+       bio,
+       updated_at
+FROM users
+WHERE tenant_id = 1;
+
+DROP TABLE users;
+ALTER TABLE users_single RENAME TO users;
+
+COMMIT;
@@ -0,0 +1,28 @@
+BEGIN;
+
+-- profiles are per tenant like the messages showing them, existing ones belong to the default
+-- tenant
+CREATE TABLE users_tenants
+(
+    tenant_id    integer NOT NULL REFERENCES tenants (tenant_id),
+    user_id      integer NOT NULL,
+    display_name text,
+    avatar_url   text,
+    bio          text,
+    updated_at   integer NOT NULL,
+    PRIMARY KEY (tenant_id, user_id)
+);
+
+INSERT INTO users_tenants (tenant_id, user_id, display_name, avatar_url, bio, updated_at)
+SELECT 1,
+       user_id,
+       display_name,
+       avatar_url,
+       bio,
+       updated_at
+FROM users;
+
+DROP TABLE users;
+ALTER TABLE users_tenants RENAME TO users;
+
+COMMIT;
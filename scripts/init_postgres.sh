@@ -3,4 +3,15 @@ set -e
 
 psql -v ON_ERROR_STOP=1 --username "$POSTGRES_USER" --dbname "$POSTGRES_DB" <<-EOSQL
     CREATE SCHEMA IF NOT EXISTS "$POSTGRES_SCHEMA";
-EOSQL
\ No newline at end of file
+
+    -- services connect as an ordinary role, superusers and roles with BYPASSRLS skip
+    -- row-level security
+    CREATE ROLE "$POSTGRES_SERVICE_USER" LOGIN NOSUPERUSER NOBYPASSRLS PASSWORD '$POSTGRES_SERVICE_PASSWORD';
+    GRANT USAGE ON SCHEMA "$POSTGRES_SCHEMA" TO "$POSTGRES_SERVICE_USER";
+
+    -- tables and sequences are created later by --migrate running as $POSTGRES_USER
+    ALTER DEFAULT PRIVILEGES FOR ROLE "$POSTGRES_USER" IN SCHEMA "$POSTGRES_SCHEMA"
+        GRANT SELECT, INSERT, UPDATE, DELETE ON TABLES TO "$POSTGRES_SERVICE_USER";
+    ALTER DEFAULT PRIVILEGES FOR ROLE "$POSTGRES_USER" IN SCHEMA "$POSTGRES_SCHEMA"
+        GRANT USAGE, SELECT ON SEQUENCES TO "$POSTGRES_SERVICE_USER";
+EOSQL
@@ -0,0 +1,192 @@
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use axum::{
+    Extension, RequestPartsExt,
+    extract::FromRequestParts,
+    http::{StatusCode, header, request::Parts},
+};
+use caslex::{errors::DefaultError, middlewares::auth};
+use caslex_extra::security::jwt;
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::{State, errors::ApiError},
+    domain::tenant::{DEFAULT_TENANT_ID, TenantUser},
+};
+
+/// Define access config.
+#[derive(Parser, Debug, Clone)]
+pub struct AccessConfig {
+    /// Comma separated `tenant_id:user_id` pairs of users allowed to call admin endpoints of
+    /// their tenant, a bare user id is a user of the default tenant. Admins of the default
+    /// tenant manage tenants too. Env variable name: `ADMIN_USER_IDS`.
+    #[arg(long, env = "ADMIN_USER_IDS", value_delimiter = ',')]
+    pub admin_user_ids: Vec<TenantUser>,
+
+    /// Comma separated `tenant_id:user_id` pairs of users allowed to moderate the room of their
+    /// tenant, admins are moderators too. Env variable name: `MODERATOR_USER_IDS`.
+    #[arg(long, env = "MODERATOR_USER_IDS", value_delimiter = ',')]
+    pub moderator_user_ids: Vec<TenantUser>,
+}
+
+impl AccessConfig {
+    pub fn parse() -> AccessConfig {
+        AccessConfig::try_parse().expect("Parsing configuration failed.")
+    }
+}
+
+/// Access token claims, the user acts within the tenant only.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: String,
+    pub exp: u64,
+    pub tenant_id: i32,
+}
+
+impl<S> FromRequestParts<S> for Claims
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let token = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split_once(' '))
+            .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("bearer"))
+            .map(|(_, token)| token.trim());
+        if let Some(Ok(data)) = token.map(jwt::decode_token::<Claims>) {
+            return Ok(data.claims);
+        }
+
+        // caslex tells why the token was rejected, a token it accepts lacks the tenant
+        auth::Claims::from_request_parts(parts, state).await?;
+        Err(DefaultError::AppError(&auth::AuthError::InvalidClaims).into())
+    }
+}
+
+/// Claims of any authenticated user of an active tenant, rejected with a problem+json error.
+pub struct User(pub Claims);
+
+impl<S> FromRequestParts<S> for User
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let (claims, _) = authenticate(parts, state).await?;
+        Ok(User(claims))
+    }
+}
+
+/// Claims of an authenticated user listed in [`AccessConfig::admin_user_ids`] for the tenant of
+/// the claims.
+pub struct Admin(pub Claims);
+
+impl<S> FromRequestParts<S> for Admin
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let (claims, app_state) = authenticate(parts, state).await?;
+
+        if has_role(&app_state.admin_user_ids, &claims) {
+            Ok(Admin(claims))
+        } else {
+            Err(ApiError::Forbidden)
+        }
+    }
+}
+
+/// Claims of an admin of the default tenant, the one operating the platform and its tenants.
+pub struct PlatformAdmin(pub Claims);
+
+impl<S> FromRequestParts<S> for PlatformAdmin
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Admin(claims) = Admin::from_request_parts(parts, state).await?;
+
+        if claims.tenant_id == DEFAULT_TENANT_ID {
+            Ok(PlatformAdmin(claims))
+        } else {
+            Err(ApiError::Forbidden)
+        }
+    }
+}
+
+/// Claims of an authenticated user listed in [`AccessConfig::moderator_user_ids`] or
+/// [`AccessConfig::admin_user_ids`] for the tenant of the claims.
+pub struct Moderator(pub Claims);
+
+impl<S> FromRequestParts<S> for Moderator
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let (claims, app_state) = authenticate(parts, state).await?;
+
+        if has_role(&app_state.moderator_user_ids, &claims)
+            || has_role(&app_state.admin_user_ids, &claims)
+        {
+            Ok(Moderator(claims))
+        } else {
+            Err(ApiError::Forbidden)
+        }
+    }
+}
+
+/// Whether the user of the claims is listed for the tenant of the claims, the same user id in
+/// another tenant is a different user.
+fn has_role(users: &[TenantUser], claims: &Claims) -> bool {
+    claims
+        .sub
+        .parse::<i32>()
+        .is_ok_and(|user_id| users.contains(&TenantUser::new(claims.tenant_id, user_id)))
+}
+
+/// Decodes claims and makes sure their tenant may use the chat.
+async fn authenticate<S>(parts: &mut Parts, state: &S) -> Result<(Claims, Arc<State>), ApiError>
+where
+    S: Send + Sync,
+{
+    let claims = Claims::from_request_parts(parts, state).await?;
+
+    let Extension(app_state) = parts
+        .extract::<Extension<Arc<State>>>()
+        .await
+        .map_err(|err| ApiError::Internal(anyhow!(err.body_text())))?;
+
+    ensure_active_tenant(&app_state, claims.tenant_id).await?;
+
+    Ok((claims, app_state))
+}
+
+/// Rejects requests on behalf of suspended or unknown tenants.
+pub(crate) async fn ensure_active_tenant(state: &State, tenant_id: i32) -> Result<(), ApiError> {
+    match state.tenants_repository.get_tenant(tenant_id).await? {
+        Some(tenant) if tenant.is_active() => Ok(()),
+        Some(_) => Err(ApiError::Rejected {
+            status: StatusCode::FORBIDDEN,
+            code: "tenant_suspended".to_owned(),
+            detail: "tenant is suspended".to_owned(),
+        }),
+        None => Err(ApiError::Rejected {
+            status: StatusCode::FORBIDDEN,
+            code: "tenant_not_found".to_owned(),
+            detail: "tenant does not exist".to_owned(),
+        }),
+    }
+}
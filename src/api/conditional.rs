@@ -0,0 +1,147 @@
+use axum::{
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+
+use crate::domain::{crypto, message};
+
+/// Cache validators of a listing response.
+pub struct Validators {
+    etag: HeaderValue,
+    last_modified: Option<HeaderValue>,
+}
+
+impl Validators {
+    /// Derives validators from the listing version. `media_type` is the one the body is sent in,
+    /// and `variant` tells apart responses built from the same messages, e.g. pages of different
+    /// size.
+    pub fn new(version: &message::ListingVersion, media_type: &str, variant: &str) -> Self {
+        let digest = Sha256::new()
+            .chain_update(media_type)
+            .chain_update("|")
+            .chain_update(variant)
+            .chain_update(format!(
+                "|{:?}|{:?}|{}",
+                version.max_message_id,
+                version.last_modified.map(|at| at.timestamp_micros()),
+                version.visible
+            ))
+            .finalize();
+
+        // the body is re-serialized on every request, so the tag is weak
+        let etag = format!("W/\"{}\"", crypto::to_hex(&digest[..16]));
+
+        Self {
+            etag: HeaderValue::from_str(&etag).expect("hex etag is a valid header value"),
+            last_modified: version.last_modified.map(|at| {
+                HeaderValue::from_str(&at.format("%a, %d %b %Y %H:%M:%S GMT").to_string())
+                    .expect("http date is a valid header value")
+            }),
+        }
+    }
+
+    /// Checks `If-None-Match` of the request, weak comparison as required for GET.
+    pub fn matches(&self, headers: &HeaderMap) -> bool {
+        let etag = weak(self.etag.to_str().unwrap_or_default());
+
+        headers
+            .get_all(header::IF_NONE_MATCH)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .any(|tag| tag == "*" || weak(tag) == etag)
+    }
+
+    /// Returns empty 304 response carrying the validators.
+    pub fn not_modified(&self) -> Response {
+        let mut response = StatusCode::NOT_MODIFIED.into_response();
+        self.apply(&mut response);
+        response
+    }
+
+    pub fn apply(&self, response: &mut Response) {
+        let headers = response.headers_mut();
+        headers.insert(header::ETAG, self.etag.clone());
+        if let Some(last_modified) = &self.last_modified {
+            headers.insert(header::LAST_MODIFIED, last_modified.clone());
+        }
+    }
+}
+
+fn weak(tag: &str) -> &str {
+    tag.strip_prefix("W/").unwrap_or(tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+
+    use super::*;
+
+    fn version(visible: i64) -> message::ListingVersion {
+        message::ListingVersion {
+            max_message_id: Some(10),
+            last_modified: Some(
+                DateTime::parse_from_rfc3339("2020-04-12T22:10:57+02:00")
+                    .unwrap()
+                    .with_timezone(&Utc),
+            ),
+            visible,
+        }
+    }
+
+    #[test]
+    fn test_validators_change_with_version_media_type_and_variant() {
+        let etag = |visible, media_type, variant| {
+            Validators::new(&version(visible), media_type, variant)
+                .etag
+                .to_str()
+                .unwrap()
+                .to_owned()
+        };
+
+        let json = "application/json";
+        assert_eq!(etag(5, json, "limit=1"), etag(5, json, "limit=1"));
+        assert_ne!(etag(5, json, "limit=1"), etag(4, json, "limit=1"));
+        assert_ne!(etag(5, json, "limit=1"), etag(5, json, "limit=2"));
+        assert_ne!(
+            etag(5, json, "limit=1"),
+            etag(5, "application/cbor", "limit=1")
+        );
+    }
+
+    #[test]
+    fn test_validators_match_if_none_match() {
+        let validators = Validators::new(&version(5), "application/json", "");
+        let etag = validators.etag.to_str().unwrap().to_owned();
+
+        let mut headers = HeaderMap::new();
+        assert!(!validators.matches(&headers));
+
+        headers.insert(
+            header::IF_NONE_MATCH,
+            HeaderValue::from_str(&format!("\"other\", {}", etag.trim_start_matches("W/")))
+                .unwrap(),
+        );
+        assert!(validators.matches(&headers));
+
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_static("*"));
+        assert!(validators.matches(&headers));
+    }
+
+    #[test]
+    fn test_validators_last_modified() {
+        let mut response = Validators::new(&version(5), "application/json", "").not_modified();
+
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(
+            response
+                .headers_mut()
+                .remove(header::LAST_MODIFIED)
+                .unwrap(),
+            "Sun, 12 Apr 2020 20:10:57 GMT"
+        );
+    }
+}
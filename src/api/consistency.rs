@@ -0,0 +1,21 @@
+use axum::{extract::Request, middleware::Next, response::Response};
+
+use crate::infra::postgres;
+
+/// Header asking for reads that see every committed write, e.g. right after posting from
+/// another instance.
+pub const READ_CONSISTENCY: &str = "x-read-consistency";
+
+/// Sends reads of requests with `X-Read-Consistency: primary` to the primary database.
+pub async fn read_consistency(request: Request, next: Next) -> Response {
+    let primary = request
+        .headers()
+        .get(READ_CONSISTENCY)
+        .is_some_and(|value| value.as_bytes().eq_ignore_ascii_case(b"primary"));
+
+    if primary {
+        postgres::with_primary_reads(next.run(request)).await
+    } else {
+        next.run(request).await
+    }
+}
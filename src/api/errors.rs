@@ -0,0 +1,167 @@
+use axum::{
+    extract::rejection::{JsonRejection, QueryRejection},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use caslex::errors::DefaultError;
+use opentelemetry::trace::TraceContextExt;
+use thiserror::Error;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use validator::ValidationErrors;
+
+use crate::{
+    domain::{crypto, errors::DomainError},
+    entities::problem::{PROBLEM_CONTENT_TYPE, ProblemResponse},
+};
+
+/// Error returned by handlers, rendered as `application/problem+json`.
+#[derive(Debug, Error)]
+pub enum ApiError {
+    #[error("{0} not found")]
+    NotFound(&'static str),
+    #[error("{0}")]
+    Conflict(String),
+    #[error("access denied")]
+    Forbidden,
+    /// Request rejected before reaching the handler, e.g. malformed JSON or bad token.
+    #[error("{detail}")]
+    Rejected {
+        status: StatusCode,
+        code: String,
+        detail: String,
+    },
+    #[error("request validation failed")]
+    Validation(#[from] ValidationErrors),
+    #[error("service temporarily unavailable")]
+    Unavailable(String),
+    #[error("internal error")]
+    Internal(#[from] anyhow::Error),
+}
+
+impl ApiError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::Conflict(_) => StatusCode::CONFLICT,
+            ApiError::Forbidden => StatusCode::FORBIDDEN,
+            ApiError::Rejected { status, .. } => *status,
+            ApiError::Validation(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::Unavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            ApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn code(&self) -> String {
+        match self {
+            ApiError::NotFound(entity) => format!("{entity}_not_found"),
+            ApiError::Conflict(_) => "conflict".to_owned(),
+            ApiError::Forbidden => "forbidden".to_owned(),
+            ApiError::Rejected { code, .. } => code.clone(),
+            ApiError::Validation(_) => "validation_error".to_owned(),
+            ApiError::Unavailable(_) => "unavailable".to_owned(),
+            ApiError::Internal(_) => "internal_error".to_owned(),
+        }
+    }
+}
+
+impl From<DomainError> for ApiError {
+    fn from(err: DomainError) -> Self {
+        match err {
+            DomainError::NotFound(entity) => ApiError::NotFound(entity),
+            DomainError::Conflict(detail) => ApiError::Conflict(detail),
+            DomainError::Forbidden => ApiError::Forbidden,
+            DomainError::Unavailable(reason) => ApiError::Unavailable(reason),
+            DomainError::Internal(err) => ApiError::Internal(err),
+        }
+    }
+}
+
+impl From<DefaultError> for ApiError {
+    fn from(err: DefaultError) -> Self {
+        match err {
+            DefaultError::JsonRejection(rejection) => rejection.into(),
+            DefaultError::ValidationError(errors) => ApiError::Validation(errors),
+            DefaultError::AppError(err) => ApiError::Rejected {
+                status: err.status(),
+                code: err.kind(),
+                detail: err.details(),
+            },
+            DefaultError::Other(err) => ApiError::Internal(err),
+        }
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        ApiError::Rejected {
+            status: rejection.status(),
+            code: "json_rejection".to_owned(),
+            detail: rejection.body_text(),
+        }
+    }
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(rejection: QueryRejection) -> Self {
+        ApiError::Rejected {
+            status: rejection.status(),
+            code: "query_rejection".to_owned(),
+            detail: rejection.body_text(),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        let trace_id = current_trace_id();
+
+        // internals stay in logs, clients get the trace id to report
+        let detail = match &self {
+            ApiError::Unavailable(reason) => {
+                tracing::error!(trace_id, "storage unavailable: {}", reason);
+                self.to_string()
+            }
+            ApiError::Internal(err) => {
+                tracing::error!(trace_id, "unhandled error: {:?}", err);
+                self.to_string()
+            }
+            ApiError::Validation(errors) => format!("[{errors}]").replace('\n', ", "),
+            _ => self.to_string(),
+        };
+
+        let errors = match &self {
+            ApiError::Validation(errors) => serde_json::to_value(errors).ok(),
+            _ => None,
+        };
+
+        let body = ProblemResponse {
+            type_: "about:blank".to_owned(),
+            title: status.canonical_reason().unwrap_or_default().to_owned(),
+            status: status.as_u16(),
+            detail,
+            code: self.code(),
+            trace_id,
+            errors,
+        };
+
+        (
+            status,
+            [(header::CONTENT_TYPE, PROBLEM_CONTENT_TYPE)],
+            serde_json::to_vec(&body).unwrap_or_default(),
+        )
+            .into_response()
+    }
+}
+
+/// Returns id of the current OpenTelemetry trace, or a random one when tracing is off.
+fn current_trace_id() -> String {
+    let context = tracing::Span::current().context();
+    let span_context = context.span().span_context().clone();
+
+    if span_context.is_valid() {
+        span_context.trace_id().to_string()
+    } else {
+        crypto::random_trace_id()
+    }
+}
@@ -0,0 +1,13 @@
+use axum::extract::{FromRequest, FromRequestParts};
+
+use crate::api::errors::ApiError;
+
+/// JSON body extractor that rejects malformed bodies with a problem+json error.
+#[derive(FromRequest)]
+#[from_request(via(axum::Json), rejection(ApiError))]
+pub struct AppJson<T>(pub T);
+
+/// Query string extractor that rejects malformed parameters with a problem+json error.
+#[derive(FromRequestParts)]
+#[from_request(via(axum::extract::Query), rejection(ApiError))]
+pub struct Query<T>(pub T);
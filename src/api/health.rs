@@ -0,0 +1,327 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use axum::{Extension, Json, http::StatusCode};
+use clap::Parser;
+use futures_util::future;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+use crate::{entities::health, infra::health::DependencyCheckTrait};
+
+const DOCS_HEALTH_TAG: &str = "HEALTH";
+
+/// Define health checks config.
+#[derive(Parser, Debug, Clone)]
+pub struct HealthConfig {
+    /// Time a dependency has to answer a readiness check. Env variable name:
+    /// `HEALTH_CHECK_TIMEOUT`.
+    #[arg(long, env = "HEALTH_CHECK_TIMEOUT", default_value = "2s")]
+    pub check_timeout: humantime::Duration,
+}
+
+impl HealthConfig {
+    pub fn parse() -> HealthConfig {
+        HealthConfig::try_parse().expect("Parsing configuration failed.")
+    }
+}
+
+struct Dependency {
+    name: &'static str,
+    check: Arc<dyn DependencyCheckTrait>,
+    critical: bool,
+}
+
+/// Dependencies checked by readiness, and whether graceful shutdown has started.
+pub struct Health {
+    dependencies: Vec<Dependency>,
+    check_timeout: Duration,
+    shutting_down: AtomicBool,
+}
+
+impl Health {
+    pub fn new(config: HealthConfig) -> Self {
+        Self {
+            dependencies: vec![],
+            check_timeout: config.check_timeout.into(),
+            shutting_down: AtomicBool::new(false),
+        }
+    }
+
+    /// Adds dependency the process can't serve without.
+    pub fn with_dependency(self, name: &'static str, check: Arc<dyn DependencyCheckTrait>) -> Self {
+        self.with(name, check, true)
+    }
+
+    /// Adds dependency reported by readiness without failing it, e.g. one with a fallback.
+    pub fn with_optional_dependency(
+        self,
+        name: &'static str,
+        check: Arc<dyn DependencyCheckTrait>,
+    ) -> Self {
+        self.with(name, check, false)
+    }
+
+    fn with(
+        mut self,
+        name: &'static str,
+        check: Arc<dyn DependencyCheckTrait>,
+        critical: bool,
+    ) -> Self {
+        self.dependencies.push(Dependency {
+            name,
+            check,
+            critical,
+        });
+        self
+    }
+
+    /// Fails readiness from now on.
+    pub fn shut_down(&self) {
+        self.shutting_down.store(true, Ordering::Release);
+    }
+
+    /// Fails readiness once a shutdown signal arrives, while the server drains connections.
+    pub fn shut_down_on_signal(self: &Arc<Self>) {
+        let health = self.clone();
+        tokio::spawn(async move {
+            shutdown_signal().await;
+            tracing::info!("Shutdown started, failing readiness");
+            health.shut_down();
+        });
+    }
+
+    fn is_shutting_down(&self) -> bool {
+        self.shutting_down.load(Ordering::Acquire)
+    }
+
+    async fn check(&self, dependency: &Dependency) -> health::DependencyResponse {
+        let started = Instant::now();
+        let result = tokio::time::timeout(self.check_timeout, dependency.check.check()).await;
+        let latency_ms = started.elapsed().as_millis() as u64;
+
+        let error = match result {
+            Ok(Ok(())) => None,
+            Ok(Err(err)) => Some(err.to_string()),
+            Err(_) => Some(format!("no answer within {:?}", self.check_timeout)),
+        };
+        if let Some(error) = &error {
+            tracing::warn!("dependency {} is failing: {}", dependency.name, error);
+        }
+
+        health::DependencyResponse {
+            status: match error {
+                None => health::HealthStatus::Ok,
+                Some(_) => health::HealthStatus::Failing,
+            },
+            critical: dependency.critical,
+            latency_ms,
+            error,
+        }
+    }
+}
+
+pub fn router(health: Arc<Health>) -> OpenApiRouter {
+    OpenApiRouter::new()
+        .routes(routes!(healthz_handler))
+        .routes(routes!(readyz_handler))
+        .layer(Extension(health))
+}
+
+/// Liveness, the process answers requests.
+#[utoipa::path(
+    get,
+    path = "/healthz",
+    tag = DOCS_HEALTH_TAG,
+    responses(
+        (status = 200, description = "Process is alive", body = health::HealthResponse)
+    )
+)]
+pub async fn healthz_handler() -> Json<health::HealthResponse> {
+    Json(health::HealthResponse {
+        status: health::HealthStatus::Ok,
+        dependencies: BTreeMap::new(),
+    })
+}
+
+/// Readiness, every critical dependency answers and the process is not shutting down.
+#[utoipa::path(
+    get,
+    path = "/readyz",
+    tag = DOCS_HEALTH_TAG,
+    responses(
+        (status = 200, description = "Ready to serve traffic", body = health::HealthResponse),
+        (status = 503, description = "Dependency is failing or shutdown started", body = health::HealthResponse)
+    )
+)]
+pub async fn readyz_handler(
+    Extension(health): Extension<Arc<Health>>,
+) -> (StatusCode, Json<health::HealthResponse>) {
+    if health.is_shutting_down() {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(health::HealthResponse {
+                status: health::HealthStatus::ShuttingDown,
+                dependencies: BTreeMap::new(),
+            }),
+        );
+    }
+
+    let checks = health
+        .dependencies
+        .iter()
+        .map(|dependency| health.check(dependency));
+    let dependencies: BTreeMap<_, _> = health
+        .dependencies
+        .iter()
+        .map(|dependency| dependency.name.to_string())
+        .zip(future::join_all(checks).await)
+        .collect();
+
+    let ready = dependencies
+        .values()
+        .all(|dependency| !dependency.critical || dependency.status == health::HealthStatus::Ok);
+    let (code, status) = if ready {
+        (StatusCode::OK, health::HealthStatus::Ok)
+    } else {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            health::HealthStatus::Failing,
+        )
+    };
+
+    (
+        code,
+        Json(health::HealthResponse {
+            status,
+            dependencies,
+        }),
+    )
+}
+
+async fn shutdown_signal() {
+    // same signals as the server's graceful shutdown
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        let (Ok(mut terminate), Ok(mut quit)) =
+            (signal(SignalKind::terminate()), signal(SignalKind::quit()))
+        else {
+            tracing::error!("Failed to install shutdown signal handlers");
+            return std::future::pending().await;
+        };
+
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {},
+            _ = terminate.recv() => {},
+            _ = quit.recv() => {},
+        }
+    }
+
+    #[cfg(not(unix))]
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Router, body::Body, extract::Request, http};
+    use http_body_util::BodyExt;
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::{domain::errors::DomainError, infra::health::MockDependencyCheckTrait};
+
+    fn check(result: fn() -> Result<(), DomainError>) -> Arc<dyn DependencyCheckTrait> {
+        let mut check = MockDependencyCheckTrait::default();
+        check
+            .expect_check()
+            .returning(move || Box::pin(async move { result() }));
+        Arc::new(check)
+    }
+
+    fn health() -> Health {
+        Health::new(HealthConfig {
+            check_timeout: Duration::from_millis(50).into(),
+        })
+    }
+
+    async fn get(health: Arc<Health>, uri: &str) -> (http::StatusCode, Value) {
+        let app = Router::from(router(health));
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::GET)
+                    .uri(uri)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_readyz_handler_ok() {
+        let health = health()
+            .with_dependency("postgres", check(|| Ok(())))
+            .with_optional_dependency(
+                "postgres_replica",
+                check(|| Err(DomainError::Unavailable("down".to_string()))),
+            );
+
+        let (status, body) = get(Arc::new(health), "/readyz").await;
+
+        assert_eq!(status, http::StatusCode::OK);
+        assert_eq!(body["status"], "ok");
+        assert_eq!(body["dependencies"]["postgres"]["status"], "ok");
+        assert_eq!(
+            body["dependencies"]["postgres_replica"]["status"],
+            "failing"
+        );
+        assert_eq!(body["dependencies"]["postgres_replica"]["critical"], false);
+    }
+
+    #[tokio::test]
+    async fn test_readyz_handler_failing() {
+        let mut slow = MockDependencyCheckTrait::default();
+        slow.expect_check().returning(|| {
+            Box::pin(async {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+                Ok(())
+            })
+        });
+        let health = health().with_dependency("postgres", Arc::new(slow));
+
+        let (status, body) = get(Arc::new(health), "/readyz").await;
+
+        assert_eq!(status, http::StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["status"], "failing");
+        assert_eq!(body["dependencies"]["postgres"]["status"], "failing");
+    }
+
+    #[tokio::test]
+    async fn test_readyz_handler_shutting_down() {
+        let health = Arc::new(health().with_dependency("postgres", check(|| Ok(()))));
+        health.shut_down();
+
+        let (status, body) = get(health.clone(), "/readyz").await;
+        assert_eq!(status, http::StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(
+            body,
+            json!({ "status": "shutting_down", "dependencies": {} })
+        );
+
+        let (status, _) = get(health, "/healthz").await;
+        assert_eq!(status, http::StatusCode::OK);
+    }
+}
@@ -0,0 +1,8 @@
+pub mod post_hook_message;
+
+const DOCS_HOOKS_TAG: &str = "HOOKS";
+
+/// Returns path of incoming webhook for the token.
+pub fn hook_path(token: &str) -> String {
+    format!("/hooks/{token}")
+}
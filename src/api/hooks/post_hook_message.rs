@@ -0,0 +1,134 @@
+use std::sync::Arc;
+
+use axum::{Extension, Json, extract::Path};
+
+use crate::{
+    api::{
+        State, access::ensure_active_tenant, errors::ApiError, extract::AppJson,
+        v1::post_message::publish_message,
+    },
+    domain, entities,
+};
+
+/// Post message via incoming webhook
+///
+/// Post message on behalf of integration bot. Token is part of the secret URL.
+#[utoipa::path(
+    post,
+    path = "/{token}",
+    tag = super::DOCS_HOOKS_TAG,
+    params(
+        ("token" = String, Path, description = "Integration token")
+    ),
+    request_body = entities::message::PostMessageRequest,
+    responses(
+        (status = 200, description = "", body = entities::message::PostMessageResponse),
+        (status = 404, description = "Integration not found", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 422, description = "Request body is invalid", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 503, description = "Storage is unavailable", body = entities::problem::ProblemResponse, content_type = "application/problem+json")
+    )
+)]
+pub async fn post_hook_message_handler(
+    Extension(state): Extension<Arc<State>>,
+    Path(token): Path<String>,
+    AppJson(payload): AppJson<entities::message::PostMessageRequest>,
+) -> Result<Json<entities::message::PostMessageResponse>, ApiError> {
+    let result = state
+        .integrations_repository
+        .find_active_integration(domain::crypto::hash_token(&token))
+        .await;
+
+    let integration = match result {
+        Ok(Some(integration)) => integration,
+        Ok(None) => return Err(ApiError::NotFound("integration")),
+        Err(err) => return Err(err.into()),
+    };
+
+    ensure_active_tenant(&state, integration.tenant_id).await?;
+
+    let response = publish_message(
+        &state,
+        integration.tenant_id,
+        integration.bot_user_id(),
+        payload,
+    )
+    .await?;
+
+    Ok(Json(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, body::Body, extract::Request, http};
+    use chrono::Utc;
+    use http_body_util::BodyExt;
+    use mockall::predicate::*;
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    use crate::{
+        api::{ApiRouterBuilder, State},
+        domain,
+        infra::repositories,
+    };
+
+    #[tokio::test]
+    async fn test_post_hook_message_handler_ok() {
+        let mut integrations_repository =
+            repositories::integrations::MockIntegrationsRepositoryTrait::default();
+
+        integrations_repository
+            .expect_find_active_integration()
+            .with(eq(domain::crypto::hash_token("secret-token")))
+            .once()
+            .returning(|_| {
+                Box::pin(async {
+                    Ok(Some(domain::integration::Integration {
+                        integration_id: 7,
+                        tenant_id: 1,
+                        name: "ci".to_string(),
+                        created_by: 123,
+                        created_at: Utc::now(),
+                        revoked_at: None,
+                    }))
+                })
+            });
+
+        let unit_of_work = repositories::unit_of_work::MockUnitOfWorkFactoryTrait::posting(
+            1,
+            |x| x.content == *"build passed" && x.user_id == -7,
+            1,
+        );
+
+        let state = State {
+            unit_of_work: Arc::new(unit_of_work),
+            integrations_repository: Arc::new(integrations_repository),
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/hooks/secret-token")
+                    .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                    .body(Body::from(
+                        serde_json::to_vec(&json!({ "text": "build passed" })).unwrap(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body_json: Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(body_json, json!({"message_id": 1}));
+    }
+}
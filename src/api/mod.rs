@@ -1,17 +1,31 @@
+pub mod access;
+mod conditional;
+mod consistency;
+pub mod errors;
+pub mod extract;
+pub mod health;
+pub mod hooks;
+mod pagination;
 mod query;
 pub mod router;
 pub mod state;
+mod transcode;
 pub mod v1;
+pub mod v2;
 
-pub use self::{router::ApiRouterBuilder, state::State};
+pub use self::{access::AccessConfig, router::ApiRouterBuilder, state::State};
 
 pub fn generate_test_token() -> String {
-    use caslex::middlewares::auth;
+    generate_tenant_test_token(crate::domain::tenant::DEFAULT_TENANT_ID)
+}
+
+pub fn generate_tenant_test_token(tenant_id: i32) -> String {
     use caslex_extra::security::jwt;
 
-    let token = jwt::encode_token(&auth::Claims {
+    let token = jwt::encode_token(&access::Claims {
         sub: 123.to_string(),
         exp: jwt::expiry(1_000),
+        tenant_id,
     })
     .unwrap();
 
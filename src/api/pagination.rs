@@ -0,0 +1,114 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::DateTime;
+
+use crate::domain::message;
+
+/// Encodes cursor as opaque URL safe token.
+pub fn encode_cursor(cursor: &message::Cursor) -> String {
+    let direction = match cursor.direction {
+        message::Direction::Next => 'n',
+        message::Direction::Prev => 'p',
+    };
+
+    URL_SAFE_NO_PAD.encode(format!(
+        "{direction}:{}:{}",
+        cursor.posted_at.timestamp_micros(),
+        cursor.message_id
+    ))
+}
+
+/// Decodes token produced by [`encode_cursor`], `None` for tampered tokens.
+pub fn decode_cursor(token: &str) -> Option<message::Cursor> {
+    let decoded = String::from_utf8(URL_SAFE_NO_PAD.decode(token).ok()?).ok()?;
+    let mut parts = decoded.splitn(3, ':');
+
+    let direction = match parts.next()? {
+        "n" => message::Direction::Next,
+        "p" => message::Direction::Prev,
+        _ => return None,
+    };
+    let posted_at = DateTime::from_timestamp_micros(parts.next()?.parse().ok()?)?;
+    let message_id = parts.next()?.parse().ok()?;
+
+    Some(message::Cursor {
+        posted_at,
+        message_id,
+        direction,
+    })
+}
+
+/// Builds RFC 8288 `Link` header value pointing to adjacent pages. Links keep the request
+/// query and replace only its cursor.
+pub fn link_header(
+    path: &str,
+    query: Option<&str>,
+    next_cursor: Option<&str>,
+    prev_cursor: Option<&str>,
+) -> Option<String> {
+    let params: Vec<(String, String)> = query
+        .and_then(|query| serde_urlencoded::from_str(query).ok())
+        .unwrap_or_default();
+
+    let link = |cursor: &str, rel: &str| {
+        let mut params: Vec<(&str, &str)> = params
+            .iter()
+            .filter(|(name, _)| name != "cursor")
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect();
+        params.push(("cursor", cursor));
+
+        let query = serde_urlencoded::to_string(params).unwrap_or_default();
+        format!("<{path}?{query}>; rel=\"{rel}\"")
+    };
+
+    let links: Vec<String> = [(next_cursor, "next"), (prev_cursor, "prev")]
+        .into_iter()
+        .filter_map(|(cursor, rel)| cursor.map(|cursor| link(cursor, rel)))
+        .collect();
+
+    if links.is_empty() {
+        None
+    } else {
+        Some(links.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+
+    use super::*;
+
+    #[test]
+    fn test_cursor_roundtrip() {
+        let cursor = message::Cursor {
+            posted_at: DateTime::parse_from_rfc3339("2020-04-12T22:10:57.123456+02:00")
+                .unwrap()
+                .with_timezone(&Utc),
+            message_id: 42,
+            direction: message::Direction::Prev,
+        };
+
+        assert_eq!(decode_cursor(&encode_cursor(&cursor)), Some(cursor));
+        assert_eq!(decode_cursor("not-a-cursor"), None);
+    }
+
+    #[test]
+    fn test_link_header() {
+        let header = link_header(
+            "/api/v2/messages",
+            Some("limit=10&cursor=old&contains=a%20b"),
+            Some("next"),
+            Some("prev"),
+        );
+
+        assert_eq!(
+            header.as_deref(),
+            Some(
+                "</api/v2/messages?limit=10&contains=a+b&cursor=next>; rel=\"next\", \
+                 </api/v2/messages?limit=10&contains=a+b&cursor=prev>; rel=\"prev\""
+            )
+        );
+        assert_eq!(link_header("/api/v2/messages", None, None, None), None);
+    }
+}
@@ -1,16 +1,24 @@
 use std::{fmt, str::FromStr};
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Deserializer, de};
-use utoipa::IntoParams;
+use utoipa::{IntoParams, ToSchema};
+use validator::{Validate, ValidationError};
+
+use crate::domain::message;
 
 const DEFAULT_PAGINATION_OFFSET: i64 = 0;
 const DEFAULT_PAGINATION_LIMIT: i64 = 100;
 
-#[derive(Debug, Deserialize, IntoParams)]
+#[derive(Debug, Deserialize, Validate, IntoParams)]
 #[allow(dead_code)]
 pub struct Pagination {
     #[serde(default, deserialize_with = "empty_string_as_none")]
+    #[validate(range(min = 1, max = 1000))]
+    #[param(minimum = 1, maximum = 1000)]
     limit: Option<i64>,
+    #[validate(range(min = 0))]
+    #[param(minimum = 0)]
     offset: Option<i64>,
 }
 
@@ -24,6 +32,125 @@ impl Pagination {
     }
 }
 
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+pub struct CursorPagination {
+    /// Cursor of the requested page, taken from `next_cursor` or `prev_cursor`.
+    pub cursor: Option<String>,
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    #[validate(range(min = 1, max = 1000))]
+    #[param(minimum = 1, maximum = 1000)]
+    limit: Option<i64>,
+    /// Count all matching items, costs an extra query.
+    #[serde(default)]
+    pub include_total: bool,
+}
+
+impl CursorPagination {
+    pub fn get_limit(&self) -> i64 {
+        self.limit.unwrap_or(DEFAULT_PAGINATION_LIMIT)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Order {
+    Asc,
+    #[default]
+    Desc,
+}
+
+#[derive(Debug, Default, Deserialize, Validate, IntoParams)]
+#[validate(schema(function = "validate_message_filters"))]
+pub struct MessageFilters {
+    /// Only messages of this author.
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub user_id: Option<i32>,
+    /// Only messages posted at or after this time.
+    pub since: Option<DateTime<Utc>>,
+    /// Only messages posted before this time.
+    pub until: Option<DateTime<Utc>>,
+    /// Only messages containing this text, case-insensitive.
+    #[validate(length(min = 1, max = 300))]
+    pub contains: Option<String>,
+    /// Only messages starting with this text, case-insensitive.
+    #[validate(length(min = 1, max = 300))]
+    pub prefix: Option<String>,
+    /// Order by posting time, latest first by default.
+    #[serde(default)]
+    #[param(inline)]
+    pub order: Order,
+}
+
+impl MessageFilters {
+    pub fn to_filter(&self) -> message::MessageFilter {
+        let text = match (&self.contains, &self.prefix) {
+            (Some(text), _) => Some(message::TextMatch::Contains(text.clone())),
+            (None, Some(text)) => Some(message::TextMatch::Prefix(text.clone())),
+            (None, None) => None,
+        };
+
+        message::MessageFilter {
+            user_id: self.user_id,
+            since: self.since,
+            until: self.until,
+            text,
+            order: match self.order {
+                Order::Asc => message::SortOrder::Asc,
+                Order::Desc => message::SortOrder::Desc,
+            },
+        }
+    }
+}
+
+fn validate_message_filters(filters: &MessageFilters) -> Result<(), ValidationError> {
+    validate_range(filters.since, filters.until)?;
+    if filters.contains.is_some() && filters.prefix.is_some() {
+        return Err(ValidationError::new("conflicting_filters")
+            .with_message("contains and prefix are mutually exclusive".into()));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Jsonl,
+    Md,
+    Html,
+}
+
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[validate(schema(function = "validate_export"))]
+pub struct Export {
+    /// Transcript format.
+    #[serde(default)]
+    #[param(inline)]
+    pub format: ExportFormat,
+    /// Export messages posted at or after this time.
+    pub since: Option<DateTime<Utc>>,
+    /// Export messages posted before this time.
+    pub until: Option<DateTime<Utc>>,
+}
+
+fn validate_export(export: &Export) -> Result<(), ValidationError> {
+    validate_range(export.since, export.until)
+}
+
+fn validate_range(
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+) -> Result<(), ValidationError> {
+    if let (Some(since), Some(until)) = (since, until)
+        && since >= until
+    {
+        return Err(
+            ValidationError::new("invalid_range").with_message("since must precede until".into())
+        );
+    }
+    Ok(())
+}
+
 fn empty_string_as_none<'de, D, T>(de: D) -> Result<Option<T>, D::Error>
 where
     D: Deserializer<'de>,
@@ -1,10 +1,13 @@
 use std::sync::Arc;
 
-use axum::Extension;
+use axum::{Extension, middleware};
 use tower::ServiceBuilder;
 use utoipa_axum::{router::OpenApiRouter, routes};
 
-use crate::{api, api::State};
+use crate::{
+    api,
+    api::{State, consistency, transcode},
+};
 
 #[derive(Default)]
 pub struct ApiRouterBuilder {
@@ -22,13 +25,74 @@ impl ApiRouterBuilder {
     }
 
     pub fn build(&self) -> OpenApiRouter {
-        let mut router = OpenApiRouter::new().nest(
-            "/api/v1",
-            OpenApiRouter::new()
-                .routes(routes!(api::v1::login::login_handler))
-                .routes(routes!(api::v1::list_messages::list_messages_handler))
-                .routes(routes!(api::v1::post_message::post_message_handler)),
-        );
+        let mut router = OpenApiRouter::new()
+            .nest(
+                "/api/v1",
+                OpenApiRouter::new()
+                    .routes(routes!(api::v1::login::login_handler))
+                    .routes(routes!(
+                        api::v1::get_me::get_me_handler,
+                        api::v1::update_me::update_me_handler
+                    ))
+                    .routes(routes!(api::v1::get_user::get_user_handler))
+                    .routes(routes!(api::v1::list_messages::list_messages_handler))
+                    .routes(routes!(api::v1::export_messages::export_messages_handler))
+                    .routes(routes!(api::v1::post_message::post_message_handler))
+                    .routes(routes!(
+                        api::v1::batch_get_messages::batch_get_messages_handler
+                    ))
+                    .routes(routes!(
+                        api::v1::list_scheduled_messages::list_scheduled_messages_handler
+                    ))
+                    .routes(routes!(
+                        api::v1::edit_scheduled_message::edit_scheduled_message_handler,
+                        api::v1::cancel_scheduled_message::cancel_scheduled_message_handler
+                    ))
+                    .routes(routes!(
+                        api::v1::get_message::get_message_handler,
+                        api::v1::edit_message::edit_message_handler,
+                        api::v1::delete_message::delete_message_handler
+                    ))
+                    .routes(routes!(api::v1::list_pins::list_pins_handler))
+                    .routes(routes!(
+                        api::v1::pin_message::pin_message_handler,
+                        api::v1::unpin_message::unpin_message_handler
+                    ))
+                    .routes(routes!(api::v1::list_bookmarks::list_bookmarks_handler))
+                    .routes(routes!(
+                        api::v1::add_bookmark::add_bookmark_handler,
+                        api::v1::remove_bookmark::remove_bookmark_handler
+                    ))
+                    .routes(routes!(api::v1::create_webhook::create_webhook_handler))
+                    .routes(routes!(api::v1::list_webhooks::list_webhooks_handler))
+                    .routes(routes!(api::v1::delete_webhook::delete_webhook_handler))
+                    .routes(routes!(
+                        api::v1::create_integration::create_integration_handler,
+                        api::v1::list_integrations::list_integrations_handler
+                    ))
+                    .routes(routes!(
+                        api::v1::rotate_integration_token::rotate_integration_token_handler
+                    ))
+                    .routes(routes!(
+                        api::v1::revoke_integration::revoke_integration_handler
+                    ))
+                    .routes(routes!(api::v1::create_tenant::create_tenant_handler))
+                    .routes(routes!(api::v1::suspend_tenant::suspend_tenant_handler))
+                    .layer(middleware::from_fn(transcode::transcode)),
+            )
+            .nest(
+                "/api/v2",
+                OpenApiRouter::new().routes(routes!(api::v2::list_messages::list_messages_handler)),
+            )
+            .nest(
+                "/hooks",
+                OpenApiRouter::new().routes(routes!(
+                    api::hooks::post_hook_message::post_hook_message_handler
+                )),
+            );
+
+        transcode::document_media_types(router.get_openapi_mut(), "/api/v1");
+        router = router.layer(middleware::from_fn(consistency::read_consistency));
 
         if let Some(state) = &self.state {
             router = router.layer(ServiceBuilder::new().layer(Extension(state.clone())));
@@ -1,8 +1,78 @@
 use std::sync::Arc;
 
-use crate::infra::repositories::messages::MessagesRepositoryTrait;
+use crate::{
+    commands::CommandRegistry,
+    domain::tenant::TenantUser,
+    infra::repositories::{
+        bookmarks::BookmarksRepositoryTrait, integrations::IntegrationsRepositoryTrait,
+        messages::MessagesRepositoryTrait, pins::PinsRepositoryTrait,
+        scheduled_messages::ScheduledMessagesRepositoryTrait, tenants::TenantsRepositoryTrait,
+        unit_of_work::UnitOfWorkFactoryTrait, users::UsersRepositoryTrait,
+        webhooks::WebhooksRepositoryTrait,
+    },
+};
 
 #[derive(Clone)]
 pub struct State {
     pub messages_repository: Arc<dyn MessagesRepositoryTrait>,
+    pub webhooks_repository: Arc<dyn WebhooksRepositoryTrait>,
+    pub integrations_repository: Arc<dyn IntegrationsRepositoryTrait>,
+    pub scheduled_messages_repository: Arc<dyn ScheduledMessagesRepositoryTrait>,
+    pub pins_repository: Arc<dyn PinsRepositoryTrait>,
+    pub bookmarks_repository: Arc<dyn BookmarksRepositoryTrait>,
+    pub users_repository: Arc<dyn UsersRepositoryTrait>,
+    pub tenants_repository: Arc<dyn TenantsRepositoryTrait>,
+    /// Opens transactions spanning several repositories.
+    pub unit_of_work: Arc<dyn UnitOfWorkFactoryTrait>,
+    pub admin_user_ids: Vec<TenantUser>,
+    pub moderator_user_ids: Vec<TenantUser>,
+    pub commands: CommandRegistry,
+}
+
+#[cfg(test)]
+impl State {
+    /// State with mocks without expectations, override required fields in tests. The tenant
+    /// of test tokens is found and active.
+    pub fn mocked() -> Self {
+        use crate::{
+            domain::tenant,
+            infra::repositories::{
+                bookmarks, integrations, messages, pins, scheduled_messages, tenants, unit_of_work,
+                users, webhooks,
+            },
+        };
+
+        let mut tenants_repository = tenants::MockTenantsRepositoryTrait::default();
+        tenants_repository
+            .expect_get_tenant()
+            .returning(|tenant_id| {
+                Box::pin(async move {
+                    Ok(Some(tenant::Tenant {
+                        tenant_id,
+                        name: "default".to_owned(),
+                        created_at: chrono::Utc::now(),
+                        suspended_at: None,
+                    }))
+                })
+            });
+
+        Self {
+            messages_repository: Arc::new(messages::MockMessagesRepositoryTrait::default()),
+            webhooks_repository: Arc::new(webhooks::MockWebhooksRepositoryTrait::default()),
+            integrations_repository: Arc::new(
+                integrations::MockIntegrationsRepositoryTrait::default(),
+            ),
+            scheduled_messages_repository: Arc::new(
+                scheduled_messages::MockScheduledMessagesRepositoryTrait::default(),
+            ),
+            pins_repository: Arc::new(pins::MockPinsRepositoryTrait::default()),
+            bookmarks_repository: Arc::new(bookmarks::MockBookmarksRepositoryTrait::default()),
+            users_repository: Arc::new(users::MockUsersRepositoryTrait::default()),
+            tenants_repository: Arc::new(tenants_repository),
+            unit_of_work: Arc::new(unit_of_work::MockUnitOfWorkFactoryTrait::default()),
+            admin_user_ids: vec![],
+            moderator_user_ids: vec![],
+            commands: CommandRegistry::with_builtins(),
+        }
+    }
 }
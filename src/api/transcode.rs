@@ -0,0 +1,367 @@
+use axum::{
+    body::{Body, to_bytes},
+    extract::Request,
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use utoipa::openapi::{OpenApi, RefOr, path::Operation};
+
+use crate::api::errors::ApiError;
+
+/// Largest request body accepted for transcoding, matches the default limit of axum extractors.
+const MAX_BODY_SIZE: usize = 2 * 1024 * 1024;
+
+pub(crate) const JSON: &str = "application/json";
+const MSGPACK: &str = "application/msgpack";
+const CBOR: &str = "application/cbor";
+
+/// Media types interchangeable with JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MediaType {
+    Json,
+    MsgPack,
+    Cbor,
+}
+
+impl MediaType {
+    fn parse(essence: &str) -> Option<Self> {
+        match essence {
+            JSON => Some(MediaType::Json),
+            MSGPACK | "application/x-msgpack" | "application/vnd.msgpack" => {
+                Some(MediaType::MsgPack)
+            }
+            CBOR => Some(MediaType::Cbor),
+            _ => None,
+        }
+    }
+
+    fn content_type(self) -> &'static str {
+        match self {
+            MediaType::Json => JSON,
+            MediaType::MsgPack => MSGPACK,
+            MediaType::Cbor => CBOR,
+        }
+    }
+
+    fn decode(self, bytes: &[u8]) -> anyhow::Result<serde_json::Value> {
+        Ok(match self {
+            MediaType::Json => serde_json::from_slice(bytes)?,
+            MediaType::MsgPack => rmp_serde::from_slice(bytes)?,
+            MediaType::Cbor => ciborium::from_reader(bytes)?,
+        })
+    }
+
+    fn encode(self, value: &serde_json::Value) -> anyhow::Result<Vec<u8>> {
+        Ok(match self {
+            MediaType::Json => serde_json::to_vec(value)?,
+            MediaType::MsgPack => rmp_serde::to_vec(value)?,
+            MediaType::Cbor => {
+                let mut bytes = Vec::new();
+                ciborium::into_writer(value, &mut bytes)?;
+                bytes
+            }
+        })
+    }
+}
+
+/// Lets JSON handlers speak MessagePack and CBOR. Binary request bodies are converted to JSON
+/// before extractors see them, so validation stays the same, and JSON responses are encoded
+/// in the media type preferred by `Accept`.
+pub async fn transcode(request: Request, next: Next) -> Response {
+    let (mut parts, body) = request.into_parts();
+
+    let body = match content_type(&parts.headers) {
+        Some(media) if media != MediaType::Json => {
+            let json = match to_json(media, body).await {
+                Ok(json) => json,
+                Err(err) => return err.into_response(),
+            };
+            parts
+                .headers
+                .insert(header::CONTENT_TYPE, HeaderValue::from_static(JSON));
+            parts.headers.remove(header::CONTENT_LENGTH);
+            Body::from(json)
+        }
+        _ => body,
+    };
+
+    let accept = negotiate(&parts.headers);
+    let mut response = next.run(Request::from_parts(parts, body)).await;
+
+    // the body left out of 304 would have been encoded as negotiated too
+    if response.status() == StatusCode::NOT_MODIFIED {
+        response
+            .headers_mut()
+            .append(header::VARY, HeaderValue::from_static("accept"));
+        return response;
+    }
+
+    if content_type(response.headers()) != Some(MediaType::Json) {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    parts
+        .headers
+        .append(header::VARY, HeaderValue::from_static("accept"));
+
+    if accept == MediaType::Json {
+        return Response::from_parts(parts, body);
+    }
+
+    let encoded = match from_json(accept, body).await {
+        Ok(encoded) => encoded,
+        Err(err) => return err.into_response(),
+    };
+    parts.headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static(accept.content_type()),
+    );
+    parts.headers.remove(header::CONTENT_LENGTH);
+
+    Response::from_parts(parts, Body::from(encoded))
+}
+
+async fn to_json(media: MediaType, body: Body) -> Result<Vec<u8>, ApiError> {
+    let bytes = to_bytes(body, MAX_BODY_SIZE)
+        .await
+        .map_err(|err| ApiError::Rejected {
+            status: StatusCode::PAYLOAD_TOO_LARGE,
+            code: "body_too_large".to_owned(),
+            detail: err.to_string(),
+        })?;
+
+    let value = media.decode(&bytes).map_err(|err| ApiError::Rejected {
+        status: StatusCode::BAD_REQUEST,
+        code: "malformed_body".to_owned(),
+        detail: format!("failed to decode {} body: {err}", media.content_type()),
+    })?;
+
+    Ok(serde_json::to_vec(&value).map_err(anyhow::Error::from)?)
+}
+
+async fn from_json(media: MediaType, body: Body) -> Result<Vec<u8>, ApiError> {
+    let bytes = to_bytes(body, usize::MAX)
+        .await
+        .map_err(|err| ApiError::Internal(err.into()))?;
+    let value = MediaType::Json.decode(&bytes)?;
+
+    Ok(media.encode(&value)?)
+}
+
+fn content_type(headers: &HeaderMap) -> Option<MediaType> {
+    let value = headers.get(header::CONTENT_TYPE)?.to_str().ok()?;
+    MediaType::parse(essence(value))
+}
+
+/// Media type [`transcode`] encodes JSON responses to for a request with `headers`. Validators of
+/// such responses must tell the encodings apart.
+pub(crate) fn negotiated_type(headers: &HeaderMap) -> &'static str {
+    negotiate(headers).content_type()
+}
+
+/// Picks the supported media type with the highest quality in `Accept`, earlier entries win
+/// ties. Wildcards and missing header mean JSON.
+fn negotiate(headers: &HeaderMap) -> MediaType {
+    let mut best: Option<(MediaType, f32)> = None;
+
+    for range in headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+    {
+        let mut params = range.split(';');
+        let media = match params.next().map(str::trim) {
+            Some("*/*" | "application/*") => MediaType::Json,
+            Some(essence) => match MediaType::parse(&essence.to_ascii_lowercase()) {
+                Some(media) => media,
+                None => continue,
+            },
+            None => continue,
+        };
+        let quality = params
+            .filter_map(|param| param.trim().strip_prefix("q="))
+            .find_map(|q| q.parse::<f32>().ok())
+            .unwrap_or(1.0);
+
+        if quality > 0.0 && best.is_none_or(|(_, best)| quality > best) {
+            best = Some((media, quality));
+        }
+    }
+
+    best.map_or(MediaType::Json, |(media, _)| media)
+}
+
+fn essence(content_type: &str) -> &str {
+    content_type.split(';').next().unwrap_or_default().trim()
+}
+
+/// Declares MessagePack and CBOR next to every JSON request and response body of operations
+/// under `prefix`.
+pub fn document_media_types(openapi: &mut OpenApi, prefix: &str) {
+    for (_, item) in openapi
+        .paths
+        .paths
+        .iter_mut()
+        .filter(|(path, _)| path.starts_with(prefix))
+    {
+        for operation in [
+            &mut item.get,
+            &mut item.put,
+            &mut item.post,
+            &mut item.delete,
+            &mut item.patch,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            document_operation(operation);
+        }
+    }
+}
+
+fn document_operation(operation: &mut Operation) {
+    if let Some(body) = operation.request_body.as_mut()
+        && let Some(json) = body.content.get(JSON).cloned()
+    {
+        body.content.insert(MSGPACK.to_owned(), json.clone());
+        body.content.insert(CBOR.to_owned(), json);
+    }
+
+    for response in operation.responses.responses.values_mut() {
+        if let RefOr::T(response) = response
+            && let Some(json) = response.content.get(JSON).cloned()
+        {
+            response.content.insert(MSGPACK.to_owned(), json.clone());
+            response.content.insert(CBOR.to_owned(), json);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, body::Body, extract::Request, http};
+    use http_body_util::BodyExt;
+    use mockall::predicate::*;
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::{
+        api,
+        api::{ApiRouterBuilder, State},
+        domain,
+        infra::repositories,
+    };
+
+    fn accept(value: &str) -> MediaType {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT, HeaderValue::from_str(value).unwrap());
+        negotiate(&headers)
+    }
+
+    #[test]
+    fn test_negotiate() {
+        assert_eq!(negotiate(&HeaderMap::new()), MediaType::Json);
+        assert_eq!(accept("*/*"), MediaType::Json);
+        assert_eq!(accept("application/msgpack"), MediaType::MsgPack);
+        assert_eq!(
+            accept("application/json;q=0.5, application/cbor"),
+            MediaType::Cbor
+        );
+        assert_eq!(
+            accept("application/json, application/msgpack"),
+            MediaType::Json
+        );
+        assert_eq!(accept("application/cbor;q=0, */*;q=0.1"), MediaType::Json);
+        assert_eq!(accept("text/html"), MediaType::Json);
+    }
+
+    #[tokio::test]
+    async fn test_transcode_msgpack_request_and_cbor_response() {
+        let unit_of_work = repositories::unit_of_work::MockUnitOfWorkFactoryTrait::posting(
+            1,
+            |msg: &domain::message::PostMessage| msg.content == "hello",
+            1,
+        );
+
+        let state = State {
+            unit_of_work: Arc::new(unit_of_work),
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/api/v1/messages")
+                    .header(http::header::CONTENT_TYPE, MSGPACK)
+                    .header(http::header::ACCEPT, CBOR)
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::from(
+                        rmp_serde::to_vec(&json!({ "text": "hello" })).unwrap(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+        assert_eq!(response.headers()[http::header::CONTENT_TYPE], CBOR);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: Value = ciborium::from_reader(&body[..]).unwrap();
+
+        assert_eq!(body, json!({ "message_id": 1 }));
+    }
+
+    #[tokio::test]
+    async fn test_transcode_validates_binary_body() {
+        let app = Router::from(
+            ApiRouterBuilder::new()
+                .with_state(Arc::new(State::mocked()))
+                .build(),
+        );
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/api/v1/messages")
+                    .header(http::header::CONTENT_TYPE, CBOR)
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::from(
+                        MediaType::Cbor.encode(&json!({ "text": "" })).unwrap(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[test]
+    fn test_document_media_types() {
+        let openapi = ApiRouterBuilder::new().build().into_openapi();
+        let operation = openapi.paths.paths["/api/v1/messages"]
+            .post
+            .as_ref()
+            .unwrap();
+
+        let request = operation.request_body.as_ref().unwrap();
+        assert!(request.content.contains_key(MSGPACK));
+        assert!(request.content.contains_key(CBOR));
+
+        let RefOr::T(response) = &operation.responses.responses["200"] else {
+            panic!("inline response expected");
+        };
+        assert!(response.content.contains_key(MSGPACK));
+        assert!(response.content.contains_key(CBOR));
+    }
+}
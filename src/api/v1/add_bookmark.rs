@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use axum::{Extension, extract::Path, http::StatusCode};
+
+use crate::{
+    api::{State, access::User, errors::ApiError},
+    entities,
+};
+
+/// Add bookmark
+///
+/// Save message to own private bookmarks.
+#[utoipa::path(
+    put,
+    path = "/bookmarks/{message_id}",
+    tag = super::DOCS_BOOKMARKS_TAG,
+    security(
+        ("api_key" = [])
+    ),
+    params(
+        ("message_id" = i64, Path, description = "Message id")
+    ),
+    responses(
+        (status = 204, description = "Bookmark added successfully"),
+        (status = 401, description = "Missing or invalid access token", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 404, description = "Message not found", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 503, description = "Storage is unavailable", body = entities::problem::ProblemResponse, content_type = "application/problem+json")
+    )
+)]
+pub async fn add_bookmark_handler(
+    User(claims): User,
+    Extension(state): Extension<Arc<State>>,
+    Path(message_id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    match state
+        .bookmarks_repository
+        .add_bookmark(
+            claims.tenant_id,
+            claims.sub.parse::<i32>().unwrap(),
+            message_id,
+        )
+        .await
+    {
+        Ok(true) => Ok(StatusCode::NO_CONTENT),
+        Ok(false) => Err(ApiError::NotFound("message")),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, body::Body, extract::Request, http};
+    use mockall::predicate::*;
+    use tower::ServiceExt;
+
+    use crate::{
+        api,
+        api::{ApiRouterBuilder, State},
+        infra::repositories,
+    };
+
+    #[tokio::test]
+    async fn test_add_bookmark_handler_ok() {
+        let mut bookmarks_repository =
+            repositories::bookmarks::MockBookmarksRepositoryTrait::default();
+
+        bookmarks_repository
+            .expect_add_bookmark()
+            .with(eq(1), eq(123), eq(1))
+            .once()
+            .returning(|_, _, _| Box::pin(async { Ok(true) }));
+
+        let state = State {
+            bookmarks_repository: Arc::new(bookmarks_repository),
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::PUT)
+                    .uri("/api/v1/bookmarks/1")
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::NO_CONTENT);
+    }
+}
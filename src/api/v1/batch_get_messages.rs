@@ -0,0 +1,182 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use axum::{Extension, Json};
+use validator::Validate;
+
+use crate::{
+    api::{State, access::User, errors::ApiError, extract::AppJson},
+    entities,
+};
+
+/// Batch get messages
+///
+/// Get up to 100 messages by ids. Missing ids are reported instead of failing the whole batch.
+#[utoipa::path(
+    post,
+    path = "/messages:batchGet",
+    tag = super::DOCS_MESSAGES_TAG,
+    security(
+        ("api_key" = [])
+    ),
+    request_body = entities::message::BatchGetMessagesRequest,
+    responses(
+        (status = 200, description = "Get messages successfully", body = entities::message::BatchGetMessagesResponse),
+        (status = 401, description = "Missing or invalid access token", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 422, description = "Request body is invalid", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 503, description = "Storage is unavailable", body = entities::problem::ProblemResponse, content_type = "application/problem+json")
+    )
+)]
+pub async fn batch_get_messages_handler(
+    User(claims): User,
+    Extension(state): Extension<Arc<State>>,
+    AppJson(payload): AppJson<entities::message::BatchGetMessagesRequest>,
+) -> Result<Json<entities::message::BatchGetMessagesResponse>, ApiError> {
+    match payload.validate() {
+        Ok(_) => {}
+        Err(err) => {
+            return Err(ApiError::Validation(err));
+        }
+    }
+
+    let result = state
+        .messages_repository
+        .get_messages(claims.tenant_id, payload.message_ids.clone())
+        .await;
+
+    let mut found = match result {
+        Ok(db_messages) => db_messages
+            .into_iter()
+            .map(|msg| (msg.message_id, msg))
+            .collect::<HashMap<_, _>>(),
+        Err(err) => return Err(err.into()),
+    };
+
+    let mut messages = Vec::with_capacity(found.len());
+    let mut not_found = Vec::new();
+    let mut seen = HashSet::new();
+    for message_id in payload.message_ids {
+        // duplicated ids are answered once
+        if !seen.insert(message_id) {
+            continue;
+        }
+
+        match found.remove(&message_id) {
+            Some(msg) => messages.push(entities::message::MessageResponse {
+                message_id: msg.message_id,
+                author: entities::user::AuthorResponse {
+                    user_id: msg.user_id,
+                    display_name: msg.display_name,
+                    avatar_url: msg.avatar_url,
+                },
+                content: msg.message_content,
+                posted_at: msg.posted_at,
+                expires_at: msg.expires_at,
+            }),
+            None => not_found.push(message_id),
+        }
+    }
+
+    Ok(Json(entities::message::BatchGetMessagesResponse {
+        messages,
+        not_found,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, body::Body, extract::Request, http};
+    use chrono::{DateTime, Utc};
+    use http_body_util::BodyExt;
+    use mockall::predicate::*;
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    use crate::{
+        api,
+        api::{ApiRouterBuilder, State},
+        domain,
+        infra::repositories,
+    };
+
+    async fn batch_get(state: State, payload: Value) -> (http::StatusCode, Value) {
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/api/v1/messages:batchGet")
+                    .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::from(serde_json::to_vec(&payload).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_batch_get_messages_handler_ok() {
+        let mut messages_repository =
+            repositories::messages::MockMessagesRepositoryTrait::default();
+
+        messages_repository
+            .expect_get_messages()
+            .with(eq(1), eq(vec![3, 1, 2, 3]))
+            .once()
+            .returning(|_, _| {
+                Box::pin(async {
+                    let posted_at = DateTime::parse_from_rfc3339("2020-04-12T22:10:57+02:00")
+                        .unwrap()
+                        .with_timezone(&Utc);
+
+                    Ok([1, 3]
+                        .into_iter()
+                        .map(|message_id| domain::message::AuthoredMessage {
+                            message_id,
+                            message_content: format!("message {message_id}"),
+                            user_id: 123,
+                            posted_at,
+                            expires_at: None,
+                            display_name: None,
+                            avatar_url: None,
+                        })
+                        .collect())
+                })
+            });
+
+        let state = State {
+            messages_repository: Arc::new(messages_repository),
+            ..State::mocked()
+        };
+
+        let (status, body) = batch_get(state, json!({ "message_ids": [3, 1, 2, 3] })).await;
+
+        assert_eq!(status, http::StatusCode::OK);
+        assert_eq!(body["messages"][0]["message_id"], 3);
+        assert_eq!(body["messages"][1]["message_id"], 1);
+        assert_eq!(body["messages"].as_array().unwrap().len(), 2);
+        assert_eq!(body["not_found"], json!([2]));
+    }
+
+    #[tokio::test]
+    async fn test_batch_get_messages_handler_too_many_ids() {
+        let ids: Vec<i64> = (1..=101).collect();
+
+        let (status, body) = batch_get(State::mocked(), json!({ "message_ids": ids })).await;
+
+        assert_eq!(status, http::StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["code"], "validation_error");
+    }
+}
@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use axum::{Extension, extract::Path, http::StatusCode};
+
+use crate::{
+    api::{State, access::User, errors::ApiError},
+    entities,
+};
+
+/// Cancel scheduled message
+///
+/// Remove own pending message before it is published.
+#[utoipa::path(
+    delete,
+    path = "/messages/scheduled/{scheduled_id}",
+    tag = super::DOCS_MESSAGES_TAG,
+    security(
+        ("api_key" = [])
+    ),
+    params(
+        ("scheduled_id" = i64, Path, description = "Scheduled message id")
+    ),
+    responses(
+        (status = 204, description = "Scheduled message cancelled successfully"),
+        (status = 401, description = "Missing or invalid access token", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 404, description = "Scheduled message not found", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 503, description = "Storage is unavailable", body = entities::problem::ProblemResponse, content_type = "application/problem+json")
+    )
+)]
+pub async fn cancel_scheduled_message_handler(
+    User(claims): User,
+    Extension(state): Extension<Arc<State>>,
+    Path(scheduled_id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    match state
+        .scheduled_messages_repository
+        .cancel_scheduled_message(
+            claims.tenant_id,
+            scheduled_id,
+            claims.sub.parse::<i32>().unwrap(),
+        )
+        .await
+    {
+        Ok(true) => Ok(StatusCode::NO_CONTENT),
+        Ok(false) => Err(ApiError::NotFound("scheduled_message")),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, body::Body, extract::Request, http};
+    use mockall::predicate::*;
+    use tower::ServiceExt;
+
+    use crate::{
+        api,
+        api::{ApiRouterBuilder, State},
+        infra::repositories,
+    };
+
+    #[tokio::test]
+    async fn test_cancel_scheduled_message_handler_ok() {
+        let mut scheduled_messages_repository =
+            repositories::scheduled_messages::MockScheduledMessagesRepositoryTrait::default();
+
+        scheduled_messages_repository
+            .expect_cancel_scheduled_message()
+            .with(eq(1), eq(1), eq(123))
+            .once()
+            .returning(|_, _, _| Box::pin(async { Ok(true) }));
+
+        let state = State {
+            scheduled_messages_repository: Arc::new(scheduled_messages_repository),
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::DELETE)
+                    .uri("/api/v1/messages/scheduled/1")
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::NO_CONTENT);
+    }
+}
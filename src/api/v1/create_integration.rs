@@ -0,0 +1,145 @@
+use std::sync::Arc;
+
+use axum::{Extension, Json};
+use validator::Validate;
+
+use crate::{
+    api::{State, access::Admin, errors::ApiError, extract::AppJson, hooks},
+    domain, entities,
+};
+
+/// Create integration
+///
+/// Create bot integration with secret incoming webhook URL.
+#[utoipa::path(
+    post,
+    path = "/integrations",
+    tag = super::DOCS_INTEGRATIONS_TAG,
+    security(
+        ("api_key" = [])
+    ),
+    request_body = entities::integration::CreateIntegrationRequest,
+    responses(
+        (status = 200, description = "Integration created successfully", body = entities::integration::IntegrationTokenResponse),
+        (status = 401, description = "Missing or invalid access token", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 403, description = "Access denied", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 422, description = "Request body is invalid", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 503, description = "Storage is unavailable", body = entities::problem::ProblemResponse, content_type = "application/problem+json")
+    )
+)]
+pub async fn create_integration_handler(
+    Admin(claims): Admin,
+    Extension(state): Extension<Arc<State>>,
+    AppJson(payload): AppJson<entities::integration::CreateIntegrationRequest>,
+) -> Result<Json<entities::integration::IntegrationTokenResponse>, ApiError> {
+    match payload.validate() {
+        Ok(_) => {}
+        Err(err) => {
+            return Err(ApiError::Validation(err));
+        }
+    }
+
+    let token = domain::crypto::random_token();
+
+    let result = state
+        .integrations_repository
+        .create_integration(domain::integration::NewIntegration {
+            tenant_id: claims.tenant_id,
+            name: payload.name,
+            token_hash: domain::crypto::hash_token(&token),
+            created_by: claims.sub.parse::<i32>().unwrap(),
+        })
+        .await;
+
+    let integration = match result {
+        Ok(integration) => integration,
+        Err(err) => return Err(err.into()),
+    };
+
+    Ok(Json(entities::integration::IntegrationTokenResponse {
+        integration_id: integration.integration_id,
+        hook_path: hooks::hook_path(&token),
+        token,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, body::Body, extract::Request, http};
+    use chrono::Utc;
+    use http_body_util::BodyExt;
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    use crate::{
+        api,
+        api::{ApiRouterBuilder, State},
+        domain, entities,
+        infra::repositories,
+    };
+
+    #[tokio::test]
+    async fn test_create_integration_handler_ok() {
+        let mut integrations_repository =
+            repositories::integrations::MockIntegrationsRepositoryTrait::default();
+
+        integrations_repository
+            .expect_create_integration()
+            .withf(|x| {
+                x.tenant_id == 1
+                    && x.name == "ci"
+                    && x.created_by == 123
+                    && x.token_hash.len() == 64
+            })
+            .once()
+            .returning(|x| {
+                Box::pin(async move {
+                    Ok(domain::integration::Integration {
+                        integration_id: 1,
+                        tenant_id: x.tenant_id,
+                        name: x.name,
+                        created_by: x.created_by,
+                        created_at: Utc::now(),
+                        revoked_at: None,
+                    })
+                })
+            });
+
+        let state = State {
+            integrations_repository: Arc::new(integrations_repository),
+            admin_user_ids: vec![domain::tenant::TenantUser::new(
+                domain::tenant::DEFAULT_TENANT_ID,
+                123,
+            )],
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/api/v1/integrations")
+                    .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::from(
+                        serde_json::to_vec(&json!({ "name": "ci" })).unwrap(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: entities::integration::IntegrationTokenResponse =
+            serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(body.integration_id, 1);
+        assert_eq!(body.hook_path, format!("/hooks/{}", body.token));
+    }
+}
@@ -0,0 +1,195 @@
+use std::sync::Arc;
+
+use axum::{Extension, Json};
+use validator::Validate;
+
+use crate::{
+    api::{State, access::PlatformAdmin, errors::ApiError, extract::AppJson},
+    entities,
+};
+
+/// Create tenant
+///
+/// Provision workspace of a customer organisation.
+#[utoipa::path(
+    post,
+    path = "/tenants",
+    tag = super::DOCS_TENANTS_TAG,
+    security(
+        ("api_key" = [])
+    ),
+    request_body = entities::tenant::CreateTenantRequest,
+    responses(
+        (status = 200, description = "Tenant created successfully", body = entities::tenant::TenantResponse),
+        (status = 401, description = "Missing or invalid access token", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 403, description = "Access denied", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 422, description = "Request body is invalid", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 503, description = "Storage is unavailable", body = entities::problem::ProblemResponse, content_type = "application/problem+json")
+    )
+)]
+pub async fn create_tenant_handler(
+    _: PlatformAdmin,
+    Extension(state): Extension<Arc<State>>,
+    AppJson(payload): AppJson<entities::tenant::CreateTenantRequest>,
+) -> Result<Json<entities::tenant::TenantResponse>, ApiError> {
+    match payload.validate() {
+        Ok(_) => {}
+        Err(err) => {
+            return Err(ApiError::Validation(err));
+        }
+    }
+
+    let tenant = match state.tenants_repository.create_tenant(payload.name).await {
+        Ok(tenant) => tenant,
+        Err(err) => return Err(err.into()),
+    };
+
+    Ok(Json(entities::tenant::TenantResponse {
+        tenant_id: tenant.tenant_id,
+        name: tenant.name,
+        created_at: tenant.created_at,
+        suspended_at: tenant.suspended_at,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, body::Body, extract::Request, http};
+    use chrono::Utc;
+    use http_body_util::BodyExt;
+    use mockall::predicate::*;
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    use crate::{
+        api,
+        api::{ApiRouterBuilder, State},
+        domain, entities,
+        infra::repositories,
+    };
+
+    #[tokio::test]
+    async fn test_create_tenant_handler_ok() {
+        let mut tenants_repository = repositories::tenants::MockTenantsRepositoryTrait::default();
+
+        tenants_repository
+            .expect_get_tenant()
+            .returning(|tenant_id| {
+                Box::pin(async move {
+                    Ok(Some(domain::tenant::Tenant {
+                        tenant_id,
+                        name: "default".to_owned(),
+                        created_at: Utc::now(),
+                        suspended_at: None,
+                    }))
+                })
+            });
+        tenants_repository
+            .expect_create_tenant()
+            .with(eq("acme".to_owned()))
+            .once()
+            .returning(|name| {
+                Box::pin(async move {
+                    Ok(domain::tenant::Tenant {
+                        tenant_id: 2,
+                        name,
+                        created_at: Utc::now(),
+                        suspended_at: None,
+                    })
+                })
+            });
+
+        let state = State {
+            tenants_repository: Arc::new(tenants_repository),
+            admin_user_ids: vec![domain::tenant::TenantUser::new(
+                domain::tenant::DEFAULT_TENANT_ID,
+                123,
+            )],
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/api/v1/tenants")
+                    .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::from(
+                        serde_json::to_vec(&json!({ "name": "acme" })).unwrap(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: entities::tenant::TenantResponse = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(body.tenant_id, 2);
+        assert_eq!(body.name, "acme");
+        assert_eq!(body.suspended_at, None);
+    }
+
+    #[tokio::test]
+    async fn test_create_tenant_handler_forbidden() {
+        let state = State {
+            admin_user_ids: vec![domain::tenant::TenantUser::new(1, 7)],
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/api/v1/tenants")
+                    .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::from(
+                        serde_json::to_vec(&json!({ "name": "acme" })).unwrap(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_create_tenant_handler_forbidden_for_tenant_admin() {
+        // admins of other tenants manage their own tenant only
+        let state = State {
+            admin_user_ids: vec![domain::tenant::TenantUser::new(2, 123)],
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/api/v1/tenants")
+                    .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                    .header(
+                        http::header::AUTHORIZATION,
+                        api::generate_tenant_test_token(2),
+                    )
+                    .body(Body::from(
+                        serde_json::to_vec(&json!({ "name": "acme" })).unwrap(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,185 @@
+use std::sync::Arc;
+
+use axum::{Extension, Json};
+use validator::Validate;
+
+use crate::{
+    api::{State, access::Admin, errors::ApiError, extract::AppJson},
+    domain, entities,
+};
+
+/// Create webhook
+///
+/// Register URL receiving signed message events.
+#[utoipa::path(
+    post,
+    path = "/webhooks",
+    tag = super::DOCS_WEBHOOKS_TAG,
+    security(
+        ("api_key" = [])
+    ),
+    request_body = entities::webhook::CreateWebhookRequest,
+    responses(
+        (status = 200, description = "Webhook created successfully", body = entities::webhook::CreateWebhookResponse),
+        (status = 401, description = "Missing or invalid access token", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 403, description = "Access denied", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 422, description = "Request body is invalid", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 503, description = "Storage is unavailable", body = entities::problem::ProblemResponse, content_type = "application/problem+json")
+    )
+)]
+pub async fn create_webhook_handler(
+    Admin(claims): Admin,
+    Extension(state): Extension<Arc<State>>,
+    AppJson(payload): AppJson<entities::webhook::CreateWebhookRequest>,
+) -> Result<Json<entities::webhook::CreateWebhookResponse>, ApiError> {
+    match payload.validate() {
+        Ok(_) => {}
+        Err(err) => {
+            return Err(ApiError::Validation(err));
+        }
+    }
+
+    let secret = domain::crypto::random_token();
+
+    let result = state
+        .webhooks_repository
+        .create_webhook(domain::webhook::NewWebhook {
+            tenant_id: claims.tenant_id,
+            url: payload.url,
+            secret: secret.clone(),
+            events: payload.events,
+            created_by: claims.sub.parse::<i32>().unwrap(),
+        })
+        .await;
+
+    let webhook = match result {
+        Ok(webhook) => webhook,
+        Err(err) => return Err(err.into()),
+    };
+
+    Ok(Json(entities::webhook::CreateWebhookResponse {
+        webhook_id: webhook.webhook_id,
+        secret,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, body::Body, extract::Request, http};
+    use chrono::Utc;
+    use http_body_util::BodyExt;
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    use crate::{
+        api,
+        api::{ApiRouterBuilder, State},
+        domain, entities,
+        infra::repositories,
+    };
+
+    fn request(token: String) -> Request<Body> {
+        Request::builder()
+            .method(http::Method::POST)
+            .uri("/api/v1/webhooks")
+            .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+            .header(http::header::AUTHORIZATION, token)
+            .body(Body::from(
+                serde_json::to_vec(&json!({
+                    "url": "https://example.com/hook",
+                    "events": ["message.created"]
+                }))
+                .unwrap(),
+            ))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_create_webhook_handler_ok() {
+        let mut webhooks_repository =
+            repositories::webhooks::MockWebhooksRepositoryTrait::default();
+
+        webhooks_repository
+            .expect_create_webhook()
+            .withf(|x| {
+                x.url == "https://example.com/hook"
+                    && x.events == vec![domain::webhook::EventType::MessageCreated]
+                    && x.created_by == 123
+                    && !x.secret.is_empty()
+            })
+            .once()
+            .returning(|x| {
+                Box::pin(async move {
+                    Ok(domain::webhook::Webhook {
+                        webhook_id: 1,
+                        url: x.url,
+                        events: x.events,
+                        created_by: x.created_by,
+                        created_at: Utc::now(),
+                    })
+                })
+            });
+
+        let state = State {
+            webhooks_repository: Arc::new(webhooks_repository),
+            admin_user_ids: vec![domain::tenant::TenantUser::new(
+                domain::tenant::DEFAULT_TENANT_ID,
+                123,
+            )],
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(request(api::generate_test_token()))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: entities::webhook::CreateWebhookResponse = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(body.webhook_id, 1);
+        assert_eq!(body.secret.len(), 64);
+    }
+
+    #[tokio::test]
+    async fn test_create_webhook_handler_forbidden() {
+        let app = Router::from(
+            ApiRouterBuilder::new()
+                .with_state(Arc::from(State::mocked()))
+                .build(),
+        );
+
+        let response = app
+            .oneshot(request(api::generate_test_token()))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_create_webhook_handler_forbidden_in_other_tenant() {
+        // user ids are unique within a tenant, user 123 of tenant 2 is not the admin
+        let state = State {
+            admin_user_ids: vec![domain::tenant::TenantUser::new(
+                domain::tenant::DEFAULT_TENANT_ID,
+                123,
+            )],
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(request(api::generate_tenant_test_token(2)))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,150 @@
+use std::sync::Arc;
+
+use axum::{Extension, extract::Path, http::StatusCode};
+use chrono::Utc;
+
+use crate::{
+    api::{State, access::User, errors::ApiError},
+    entities,
+};
+
+/// Delete message
+///
+/// Delete own message.
+#[utoipa::path(
+    delete,
+    path = "/messages/{message_id}",
+    tag = super::DOCS_MESSAGES_TAG,
+    security(
+        ("api_key" = [])
+    ),
+    params(
+        ("message_id" = i64, Path, description = "Message id")
+    ),
+    responses(
+        (status = 204, description = "Message deleted successfully"),
+        (status = 401, description = "Missing or invalid access token", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 403, description = "Access denied", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 404, description = "Message not found", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 503, description = "Storage is unavailable", body = entities::problem::ProblemResponse, content_type = "application/problem+json")
+    )
+)]
+pub async fn delete_message_handler(
+    User(claims): User,
+    Extension(state): Extension<Arc<State>>,
+    Path(message_id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    let result = state
+        .messages_repository
+        .delete_message(
+            claims.tenant_id,
+            message_id,
+            claims.sub.parse::<i32>().unwrap(),
+            Utc::now(),
+        )
+        .await;
+
+    match result {
+        Ok(_) => Ok(StatusCode::NO_CONTENT),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, body::Body, extract::Request, http};
+    use http_body_util::BodyExt;
+    use mockall::predicate::*;
+    use serde_json::Value;
+    use tower::ServiceExt;
+
+    use crate::{
+        api,
+        api::{ApiRouterBuilder, State},
+        domain,
+        infra::repositories,
+    };
+
+    #[tokio::test]
+    async fn test_delete_message_handler_not_found() {
+        let mut messages_repository =
+            repositories::messages::MockMessagesRepositoryTrait::default();
+
+        messages_repository
+            .expect_delete_message()
+            .with(eq(1), eq(1), eq(123), always())
+            .once()
+            .returning(|_, _, _, _| {
+                Box::pin(async { Err(domain::errors::DomainError::NotFound("message")) })
+            });
+
+        let state = State {
+            messages_repository: Arc::new(messages_repository),
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::DELETE)
+                    .uri("/api/v1/messages/1")
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+        assert_eq!(
+            response.headers()[http::header::CONTENT_TYPE],
+            "application/problem+json"
+        );
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(body["status"], 404);
+        assert_eq!(body["title"], "Not Found");
+        assert_eq!(body["code"], "message_not_found");
+        assert_eq!(body["trace_id"].as_str().unwrap().len(), 32);
+    }
+
+    #[tokio::test]
+    async fn test_delete_message_handler_forbidden() {
+        let mut messages_repository =
+            repositories::messages::MockMessagesRepositoryTrait::default();
+
+        messages_repository
+            .expect_delete_message()
+            .with(eq(1), eq(1), eq(123), always())
+            .once()
+            .returning(|_, _, _, _| {
+                Box::pin(async { Err(domain::errors::DomainError::Forbidden) })
+            });
+
+        let state = State {
+            messages_repository: Arc::new(messages_repository),
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::DELETE)
+                    .uri("/api/v1/messages/1")
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::FORBIDDEN);
+    }
+}
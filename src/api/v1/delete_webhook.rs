@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use axum::{Extension, extract::Path, http::StatusCode};
+
+use crate::{
+    api::{State, access::Admin, errors::ApiError},
+    entities,
+};
+
+/// Delete webhook
+///
+/// Delete webhook together with its pending deliveries.
+#[utoipa::path(
+    delete,
+    path = "/webhooks/{webhook_id}",
+    tag = super::DOCS_WEBHOOKS_TAG,
+    security(
+        ("api_key" = [])
+    ),
+    params(
+        ("webhook_id" = i64, Path, description = "Webhook id")
+    ),
+    responses(
+        (status = 204, description = "Webhook deleted successfully"),
+        (status = 401, description = "Missing or invalid access token", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 403, description = "Access denied", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 404, description = "Webhook not found", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 503, description = "Storage is unavailable", body = entities::problem::ProblemResponse, content_type = "application/problem+json")
+    )
+)]
+pub async fn delete_webhook_handler(
+    Admin(claims): Admin,
+    Extension(state): Extension<Arc<State>>,
+    Path(webhook_id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    match state
+        .webhooks_repository
+        .delete_webhook(claims.tenant_id, webhook_id)
+        .await
+    {
+        Ok(true) => Ok(StatusCode::NO_CONTENT),
+        Ok(false) => Err(ApiError::NotFound("webhook")),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, body::Body, extract::Request, http};
+    use mockall::predicate::*;
+    use tower::ServiceExt;
+
+    use crate::{
+        api,
+        api::{ApiRouterBuilder, State},
+        domain,
+        infra::repositories,
+    };
+
+    #[tokio::test]
+    async fn test_delete_webhook_handler_ok() {
+        let mut webhooks_repository =
+            repositories::webhooks::MockWebhooksRepositoryTrait::default();
+
+        webhooks_repository
+            .expect_delete_webhook()
+            .with(eq(1), eq(1))
+            .once()
+            .returning(|_, _| Box::pin(async { Ok(true) }));
+
+        let state = State {
+            webhooks_repository: Arc::new(webhooks_repository),
+            admin_user_ids: vec![domain::tenant::TenantUser::new(
+                domain::tenant::DEFAULT_TENANT_ID,
+                123,
+            )],
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::DELETE)
+                    .uri("/api/v1/webhooks/1")
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::NO_CONTENT);
+    }
+}
@@ -0,0 +1,179 @@
+use std::sync::Arc;
+
+use axum::{Extension, Json, extract::Path};
+use chrono::Utc;
+use validator::Validate;
+
+use crate::{
+    api::{State, access::User, errors::ApiError, extract::AppJson},
+    domain, entities,
+};
+
+/// Edit message
+///
+/// Replace content of own message.
+#[utoipa::path(
+    patch,
+    path = "/messages/{message_id}",
+    tag = super::DOCS_MESSAGES_TAG,
+    security(
+        ("api_key" = [])
+    ),
+    params(
+        ("message_id" = i64, Path, description = "Message id")
+    ),
+    request_body = entities::message::EditMessageRequest,
+    responses(
+        (status = 200, description = "Message edited successfully", body = entities::message::MessageResponse),
+        (status = 401, description = "Missing or invalid access token", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 403, description = "Access denied", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 404, description = "Message not found", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 422, description = "Request body is invalid", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 503, description = "Storage is unavailable", body = entities::problem::ProblemResponse, content_type = "application/problem+json")
+    )
+)]
+pub async fn edit_message_handler(
+    User(claims): User,
+    Extension(state): Extension<Arc<State>>,
+    Path(message_id): Path<i64>,
+    AppJson(payload): AppJson<entities::message::EditMessageRequest>,
+) -> Result<Json<entities::message::MessageResponse>, ApiError> {
+    match payload.validate() {
+        Ok(_) => {}
+        Err(err) => {
+            return Err(ApiError::Validation(err));
+        }
+    }
+
+    let result = state
+        .messages_repository
+        .update_message(
+            claims.tenant_id,
+            message_id,
+            claims.sub.parse::<i32>().unwrap(),
+            payload.text,
+            Utc::now(),
+        )
+        .await;
+
+    let msg = match result {
+        Ok(msg) => msg,
+        Err(err) => return Err(err.into()),
+    };
+
+    let profile = match state
+        .users_repository
+        .get_profile(claims.tenant_id, msg.user_id)
+        .await
+    {
+        Ok(profile) => profile.unwrap_or_else(|| domain::user::UserProfile::empty(msg.user_id)),
+        Err(err) => return Err(err.into()),
+    };
+
+    Ok(Json(entities::message::MessageResponse {
+        message_id: msg.message_id,
+        author: entities::user::AuthorResponse {
+            user_id: profile.user_id,
+            display_name: profile.display_name,
+            avatar_url: profile.avatar_url,
+        },
+        content: msg.message_content,
+        posted_at: msg.posted_at,
+        expires_at: msg.expires_at,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, body::Body, extract::Request, http};
+    use chrono::{DateTime, Utc};
+    use http_body_util::BodyExt;
+    use mockall::predicate::*;
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    use crate::{
+        api,
+        api::{ApiRouterBuilder, State},
+        domain,
+        infra::repositories,
+    };
+
+    #[tokio::test]
+    async fn test_edit_message_handler_ok() {
+        let mut messages_repository =
+            repositories::messages::MockMessagesRepositoryTrait::default();
+
+        messages_repository
+            .expect_update_message()
+            .with(eq(1), eq(1), eq(123), eq("edited".to_string()), always())
+            .once()
+            .returning(|_, message_id, user_id, content, _| {
+                Box::pin(async move {
+                    let posted_at =
+                        DateTime::parse_from_rfc3339("2020-04-12T22:10:57+02:00").unwrap();
+
+                    Ok(domain::message::Message {
+                        message_id,
+                        message_content: content,
+                        user_id,
+                        posted_at: posted_at.with_timezone(&Utc),
+                        expires_at: None,
+                    })
+                })
+            });
+
+        let mut users_repository = repositories::users::MockUsersRepositoryTrait::default();
+
+        users_repository
+            .expect_get_profile()
+            .with(eq(1), eq(123))
+            .once()
+            .returning(|_, _| Box::pin(async { Ok(None) }));
+
+        let state = State {
+            messages_repository: Arc::new(messages_repository),
+            users_repository: Arc::new(users_repository),
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::PATCH)
+                    .uri("/api/v1/messages/1")
+                    .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::from(
+                        serde_json::to_vec(&json!({ "text": "edited" })).unwrap(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body_json: Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(
+            body_json,
+            json!({
+                "content": "edited",
+                "message_id": 1,
+                "author": {
+                    "user_id": 123,
+                    "display_name": null,
+                    "avatar_url": null
+                },
+                "posted_at": "2020-04-12T20:10:57Z",
+                "expires_at": null
+            })
+        );
+    }
+}
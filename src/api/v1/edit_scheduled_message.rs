@@ -0,0 +1,163 @@
+use std::sync::Arc;
+
+use axum::{Extension, Json, extract::Path};
+use validator::Validate;
+
+use crate::{
+    api::{State, access::User, errors::ApiError, extract::AppJson},
+    entities,
+};
+
+/// Edit scheduled message
+///
+/// Change text or send time of own pending message.
+#[utoipa::path(
+    patch,
+    path = "/messages/scheduled/{scheduled_id}",
+    tag = super::DOCS_MESSAGES_TAG,
+    security(
+        ("api_key" = [])
+    ),
+    params(
+        ("scheduled_id" = i64, Path, description = "Scheduled message id")
+    ),
+    request_body = entities::scheduled::EditScheduledMessageRequest,
+    responses(
+        (status = 200, description = "Scheduled message edited successfully", body = entities::scheduled::ScheduledMessageResponse),
+        (status = 401, description = "Missing or invalid access token", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 404, description = "Scheduled message not found", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 422, description = "Request body is invalid", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 503, description = "Storage is unavailable", body = entities::problem::ProblemResponse, content_type = "application/problem+json")
+    )
+)]
+pub async fn edit_scheduled_message_handler(
+    User(claims): User,
+    Extension(state): Extension<Arc<State>>,
+    Path(scheduled_id): Path<i64>,
+    AppJson(payload): AppJson<entities::scheduled::EditScheduledMessageRequest>,
+) -> Result<Json<entities::scheduled::ScheduledMessageResponse>, ApiError> {
+    match payload.validate() {
+        Ok(_) => {}
+        Err(err) => {
+            return Err(ApiError::Validation(err));
+        }
+    }
+
+    let result = state
+        .scheduled_messages_repository
+        .update_scheduled_message(
+            claims.tenant_id,
+            scheduled_id,
+            claims.sub.parse::<i32>().unwrap(),
+            payload.text,
+            payload.send_at,
+        )
+        .await;
+
+    let msg = match result {
+        Ok(Some(msg)) => msg,
+        Ok(None) => return Err(ApiError::NotFound("scheduled_message")),
+        Err(err) => return Err(err.into()),
+    };
+
+    Ok(Json(entities::scheduled::ScheduledMessageResponse {
+        scheduled_id: msg.scheduled_id,
+        content: msg.message_content,
+        send_at: msg.send_at,
+        expires_in: msg.expires_in,
+        created_at: msg.created_at,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, body::Body, extract::Request, http};
+    use chrono::{Duration, Utc};
+    use mockall::predicate::*;
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    use crate::{
+        api,
+        api::{ApiRouterBuilder, State},
+        infra::repositories,
+    };
+
+    #[tokio::test]
+    async fn test_edit_scheduled_message_handler_already_published() {
+        let mut scheduled_messages_repository =
+            repositories::scheduled_messages::MockScheduledMessagesRepositoryTrait::default();
+
+        scheduled_messages_repository
+            .expect_update_scheduled_message()
+            .with(
+                eq(1),
+                eq(1),
+                eq(123),
+                eq(Some("edited".to_string())),
+                eq(None),
+            )
+            .once()
+            .returning(|_, _, _, _, _| Box::pin(async { Ok(None) }));
+
+        let state = State {
+            scheduled_messages_repository: Arc::new(scheduled_messages_repository),
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::PATCH)
+                    .uri("/api/v1/messages/scheduled/1")
+                    .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::from(
+                        serde_json::to_vec(&json!({ "text": "edited" })).unwrap(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_edit_scheduled_message_handler_past_send_at() {
+        let mut scheduled_messages_repository =
+            repositories::scheduled_messages::MockScheduledMessagesRepositoryTrait::default();
+
+        scheduled_messages_repository
+            .expect_update_scheduled_message()
+            .never();
+
+        let state = State {
+            scheduled_messages_repository: Arc::new(scheduled_messages_repository),
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let send_at = Utc::now() - Duration::minutes(1);
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::PATCH)
+                    .uri("/api/v1/messages/scheduled/1")
+                    .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::from(
+                        serde_json::to_vec(&json!({ "send_at": send_at })).unwrap(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
@@ -0,0 +1,340 @@
+use std::{future::ready, sync::Arc};
+
+use axum::{
+    Extension,
+    body::Body,
+    http::{StatusCode, header},
+    response::Response,
+};
+use futures_util::{StreamExt, stream};
+use validator::Validate;
+
+use crate::{
+    api::{State, access::User, errors::ApiError, extract::Query, query, query::ExportFormat},
+    domain,
+    domain::errors::DomainError,
+    entities,
+};
+
+/// Export messages
+///
+/// Stream conversation history as a transcript.
+#[utoipa::path(
+    get,
+    path = "/messages/export",
+    tag = super::DOCS_MESSAGES_TAG,
+    security(
+        ("api_key" = [])
+    ),
+    params(
+        query::Export
+    ),
+    responses(
+        (status = 200, description = "Transcript streamed successfully", content(
+            (String = "application/x-ndjson"),
+            (String = "text/markdown"),
+            (String = "text/html")
+        )),
+        (status = 401, description = "Missing or invalid access token", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 422, description = "Query parameters are invalid", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 503, description = "Storage is unavailable", body = entities::problem::ProblemResponse, content_type = "application/problem+json")
+    )
+)]
+pub async fn export_messages_handler(
+    User(claims): User,
+    Extension(state): Extension<Arc<State>>,
+    Query(params): Query<query::Export>,
+) -> Result<Response, ApiError> {
+    params.validate()?;
+
+    let mut messages =
+        state
+            .messages_repository
+            .stream_messages(claims.tenant_id, params.since, params.until);
+
+    // surface storage failures before the response status is sent
+    let first = match messages.next().await {
+        Some(Ok(msg)) => Some(Ok(msg)),
+        Some(Err(err)) => return Err(err.into()),
+        None => None,
+    };
+
+    let format = params.format;
+    let body = stream::once(ready(Ok(transcript_header(format))))
+        .chain(
+            stream::iter(first)
+                .chain(messages)
+                .map(move |msg| msg.and_then(|msg| render_message(format, msg))),
+        )
+        .chain(stream::once(ready(Ok(transcript_footer(format)))));
+
+    let response = Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, content_type(format))
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"messages.{}\"", extension(format)),
+        )
+        .body(Body::from_stream(body))
+        .map_err(|err| ApiError::Internal(err.into()))?;
+
+    Ok(response)
+}
+
+fn content_type(format: ExportFormat) -> &'static str {
+    match format {
+        ExportFormat::Jsonl => "application/x-ndjson",
+        ExportFormat::Md => "text/markdown; charset=utf-8",
+        ExportFormat::Html => "text/html; charset=utf-8",
+    }
+}
+
+fn extension(format: ExportFormat) -> &'static str {
+    match format {
+        ExportFormat::Jsonl => "jsonl",
+        ExportFormat::Md => "md",
+        ExportFormat::Html => "html",
+    }
+}
+
+fn transcript_header(format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Jsonl => String::new(),
+        ExportFormat::Md => "# Conversation history\n\n".to_owned(),
+        ExportFormat::Html => concat!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n",
+            "<title>Conversation history</title>\n</head>\n<body>\n<ul>\n"
+        )
+        .to_owned(),
+    }
+}
+
+fn transcript_footer(format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Jsonl | ExportFormat::Md => String::new(),
+        ExportFormat::Html => "</ul>\n</body>\n</html>\n".to_owned(),
+    }
+}
+
+fn render_message(
+    format: ExportFormat,
+    msg: domain::message::Message,
+) -> Result<String, DomainError> {
+    let posted_at = msg.posted_at.to_rfc3339();
+
+    let rendered = match format {
+        ExportFormat::Jsonl => {
+            let mut line = serde_json::to_string(&entities::message::ExportedMessageResponse {
+                message_id: msg.message_id,
+                user_id: msg.user_id,
+                content: msg.message_content,
+                posted_at: msg.posted_at,
+            })
+            .map_err(|err| DomainError::Internal(err.into()))?;
+            line.push('\n');
+            line
+        }
+        ExportFormat::Md => format!(
+            "**user {}** _{}_\n\n{}\n\n",
+            msg.user_id,
+            posted_at,
+            escape_markdown(&msg.message_content)
+        ),
+        ExportFormat::Html => format!(
+            "<li><time datetime=\"{posted_at}\">{posted_at}</time> <b>user {}</b>: {}</li>\n",
+            msg.user_id,
+            escape_html(&msg.message_content)
+        ),
+    };
+
+    Ok(rendered)
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// Backslash escapes every ASCII punctuation, which CommonMark allows for all of them, so that
+/// messages can't add headings, tables, code or links to the transcript.
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        if ch.is_ascii_punctuation() {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, body::Body, extract::Request, http};
+    use chrono::{DateTime, Utc};
+    use futures_util::{StreamExt, stream};
+    use http_body_util::BodyExt;
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    use crate::{
+        api,
+        api::{ApiRouterBuilder, State},
+        domain,
+        infra::repositories,
+    };
+
+    fn test_app(content: &'static str) -> Router {
+        let mut messages_repository =
+            repositories::messages::MockMessagesRepositoryTrait::default();
+
+        messages_repository
+            .expect_stream_messages()
+            .once()
+            .returning(move |_, _, _| {
+                let posted_at = DateTime::parse_from_rfc3339("2020-04-12T22:10:57+02:00")
+                    .unwrap()
+                    .with_timezone(&Utc);
+
+                stream::iter(vec![Ok(domain::message::Message {
+                    message_id: 1,
+                    message_content: content.to_string(),
+                    user_id: 123,
+                    posted_at,
+                    expires_at: None,
+                })])
+                .boxed()
+            });
+
+        let state = State {
+            messages_repository: Arc::new(messages_repository),
+            ..State::mocked()
+        };
+        Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build())
+    }
+
+    #[tokio::test]
+    async fn test_export_messages_handler_jsonl() {
+        let response = test_app("test")
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::GET)
+                    .uri("/api/v1/messages/export?format=jsonl")
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+        assert_eq!(
+            response.headers()[http::header::CONTENT_TYPE],
+            "application/x-ndjson"
+        );
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let lines: Vec<Value> = body
+            .split(|b| *b == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_slice(line).unwrap())
+            .collect();
+
+        assert_eq!(
+            lines,
+            vec![json!({
+                "message_id": 1,
+                "user_id": 123,
+                "content": "test",
+                "posted_at": "2020-04-12T20:10:57Z"
+            })]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_messages_handler_invalid_range() {
+        let app = Router::from(
+            ApiRouterBuilder::new()
+                .with_state(Arc::from(State::mocked()))
+                .build(),
+        );
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::GET)
+                    .uri("/api/v1/messages/export?since=2020-04-13T00:00:00Z&until=2020-04-13T00:00:00Z")
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::UNPROCESSABLE_ENTITY);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], "validation_error");
+    }
+
+    #[tokio::test]
+    async fn test_export_messages_handler_html_escaped() {
+        let response = test_app("<script>")
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::GET)
+                    .uri("/api/v1/messages/export?format=html")
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let html = String::from_utf8(body.to_vec()).unwrap();
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<b>user 123</b>: &lt;script&gt;</li>"));
+        assert!(html.ends_with("</html>\n"));
+    }
+
+    #[tokio::test]
+    async fn test_export_messages_handler_markdown_escaped() {
+        let response = test_app("# a | `b`")
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::GET)
+                    .uri("/api/v1/messages/export?format=md")
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let markdown = String::from_utf8(body.to_vec()).unwrap();
+
+        assert!(markdown.starts_with("# Conversation history\n\n"));
+        assert!(markdown.contains("\n\n\\# a \\| \\`b\\`\n\n"));
+    }
+}
@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use axum::{Extension, Json};
+
+use crate::{
+    api::{State, access::User, errors::ApiError},
+    domain, entities,
+};
+
+/// Get own profile
+///
+/// Get profile of the current user, empty until filled in.
+#[utoipa::path(
+    get,
+    path = "/users/me",
+    tag = super::DOCS_USERS_TAG,
+    security(
+        ("api_key" = [])
+    ),
+    responses(
+        (status = 200, description = "Get profile successfully", body = entities::user::UserProfileResponse),
+        (status = 401, description = "Missing or invalid access token", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 503, description = "Storage is unavailable", body = entities::problem::ProblemResponse, content_type = "application/problem+json")
+    )
+)]
+pub async fn get_me_handler(
+    User(claims): User,
+    Extension(state): Extension<Arc<State>>,
+) -> Result<Json<entities::user::UserProfileResponse>, ApiError> {
+    let user_id = claims.sub.parse::<i32>().unwrap();
+
+    let profile = match state
+        .users_repository
+        .get_profile(claims.tenant_id, user_id)
+        .await
+    {
+        Ok(profile) => profile.unwrap_or_else(|| domain::user::UserProfile::empty(user_id)),
+        Err(err) => return Err(err.into()),
+    };
+
+    Ok(Json(entities::user::UserProfileResponse {
+        user_id: profile.user_id,
+        display_name: profile.display_name,
+        avatar_url: profile.avatar_url,
+        bio: profile.bio,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, body::Body, extract::Request, http};
+    use http_body_util::BodyExt;
+    use mockall::predicate::*;
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    use crate::{
+        api,
+        api::{ApiRouterBuilder, State},
+        infra::repositories,
+    };
+
+    #[tokio::test]
+    async fn test_get_me_handler_empty_profile() {
+        let mut users_repository = repositories::users::MockUsersRepositoryTrait::default();
+
+        users_repository
+            .expect_get_profile()
+            .with(eq(1), eq(123))
+            .once()
+            .returning(|_, _| Box::pin(async { Ok(None) }));
+
+        let state = State {
+            users_repository: Arc::new(users_repository),
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::GET)
+                    .uri("/api/v1/users/me")
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body_json: Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(
+            body_json,
+            json!({
+                "user_id": 123,
+                "display_name": null,
+                "avatar_url": null,
+                "bio": null
+            })
+        );
+    }
+}
@@ -0,0 +1,219 @@
+use std::sync::Arc;
+
+use axum::{Extension, Json, extract::Path};
+
+use crate::{
+    api::{State, access::User, errors::ApiError},
+    entities,
+};
+
+/// Get message
+///
+/// Get single message by id, used to resolve deep links and replies.
+#[utoipa::path(
+    get,
+    path = "/messages/{message_id}",
+    tag = super::DOCS_MESSAGES_TAG,
+    security(
+        ("api_key" = [])
+    ),
+    params(
+        ("message_id" = i64, Path, description = "Message id")
+    ),
+    responses(
+        (status = 200, description = "Get message successfully", body = entities::message::MessageResponse),
+        (status = 401, description = "Missing or invalid access token", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 404, description = "Message not found", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 503, description = "Storage is unavailable", body = entities::problem::ProblemResponse, content_type = "application/problem+json")
+    )
+)]
+pub async fn get_message_handler(
+    User(claims): User,
+    Extension(state): Extension<Arc<State>>,
+    Path(message_id): Path<i64>,
+) -> Result<Json<entities::message::MessageResponse>, ApiError> {
+    let msg = match state
+        .messages_repository
+        .get_message(claims.tenant_id, message_id)
+        .await
+    {
+        Ok(msg) => msg,
+        Err(err) => return Err(err.into()),
+    };
+
+    Ok(Json(entities::message::MessageResponse {
+        message_id: msg.message_id,
+        author: entities::user::AuthorResponse {
+            user_id: msg.user_id,
+            display_name: msg.display_name,
+            avatar_url: msg.avatar_url,
+        },
+        content: msg.message_content,
+        posted_at: msg.posted_at,
+        expires_at: msg.expires_at,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, body::Body, extract::Request, http};
+    use chrono::{DateTime, Utc};
+    use http_body_util::BodyExt;
+    use mockall::predicate::*;
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    use crate::{
+        api,
+        api::{ApiRouterBuilder, State},
+        domain,
+        infra::repositories,
+    };
+
+    #[tokio::test]
+    async fn test_get_message_handler_ok() {
+        let mut messages_repository =
+            repositories::messages::MockMessagesRepositoryTrait::default();
+
+        messages_repository
+            .expect_get_message()
+            .with(eq(1), eq(1))
+            .once()
+            .returning(|_, message_id| {
+                Box::pin(async move {
+                    let posted_at =
+                        DateTime::parse_from_rfc3339("2020-04-12T22:10:57+02:00").unwrap();
+
+                    Ok(domain::message::AuthoredMessage {
+                        message_id,
+                        message_content: "test".to_string(),
+                        user_id: 123,
+                        posted_at: posted_at.with_timezone(&Utc),
+                        expires_at: None,
+                        display_name: Some("Alice".to_string()),
+                        avatar_url: None,
+                    })
+                })
+            });
+
+        let state = State {
+            messages_repository: Arc::new(messages_repository),
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::GET)
+                    .uri("/api/v1/messages/1")
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body_json: Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(
+            body_json,
+            json!({
+                "content": "test",
+                "message_id": 1,
+                "author": {
+                    "user_id": 123,
+                    "display_name": "Alice",
+                    "avatar_url": null
+                },
+                "posted_at": "2020-04-12T20:10:57Z",
+                "expires_at": null
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_message_handler_not_found() {
+        let mut messages_repository =
+            repositories::messages::MockMessagesRepositoryTrait::default();
+
+        messages_repository
+            .expect_get_message()
+            .with(eq(1), eq(2))
+            .once()
+            .returning(|_, _| {
+                Box::pin(async { Err(domain::errors::DomainError::NotFound("message")) })
+            });
+
+        let state = State {
+            messages_repository: Arc::new(messages_repository),
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::GET)
+                    .uri("/api/v1/messages/2")
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_get_message_handler_tenant_suspended() {
+        let mut tenants_repository = repositories::tenants::MockTenantsRepositoryTrait::default();
+
+        tenants_repository
+            .expect_get_tenant()
+            .with(eq(1))
+            .once()
+            .returning(|tenant_id| {
+                Box::pin(async move {
+                    Ok(Some(domain::tenant::Tenant {
+                        tenant_id,
+                        name: "default".to_string(),
+                        created_at: Utc::now(),
+                        suspended_at: Some(Utc::now()),
+                    }))
+                })
+            });
+
+        let state = State {
+            tenants_repository: Arc::new(tenants_repository),
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::GET)
+                    .uri("/api/v1/messages/1")
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::FORBIDDEN);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body_json: Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(body_json["code"], "tenant_suspended");
+    }
+}
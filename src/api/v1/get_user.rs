@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use axum::{Extension, Json, extract::Path};
+
+use crate::{
+    api::{State, access::User, errors::ApiError},
+    entities,
+};
+
+/// Get user profile
+///
+/// Get public profile of another user.
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}",
+    tag = super::DOCS_USERS_TAG,
+    security(
+        ("api_key" = [])
+    ),
+    params(
+        ("user_id" = i32, Path, description = "User id")
+    ),
+    responses(
+        (status = 200, description = "Get profile successfully", body = entities::user::UserProfileResponse),
+        (status = 401, description = "Missing or invalid access token", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 404, description = "User not found", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 503, description = "Storage is unavailable", body = entities::problem::ProblemResponse, content_type = "application/problem+json")
+    )
+)]
+pub async fn get_user_handler(
+    User(claims): User,
+    Extension(state): Extension<Arc<State>>,
+    Path(user_id): Path<i32>,
+) -> Result<Json<entities::user::UserProfileResponse>, ApiError> {
+    let profile = match state
+        .users_repository
+        .get_profile(claims.tenant_id, user_id)
+        .await
+    {
+        Ok(Some(profile)) => profile,
+        Ok(None) => return Err(ApiError::NotFound("user")),
+        Err(err) => return Err(err.into()),
+    };
+
+    Ok(Json(entities::user::UserProfileResponse {
+        user_id: profile.user_id,
+        display_name: profile.display_name,
+        avatar_url: profile.avatar_url,
+        bio: profile.bio,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, body::Body, extract::Request, http};
+    use mockall::predicate::*;
+    use tower::ServiceExt;
+
+    use crate::{
+        api,
+        api::{ApiRouterBuilder, State},
+        infra::repositories,
+    };
+
+    #[tokio::test]
+    async fn test_get_user_handler_not_found() {
+        let mut users_repository = repositories::users::MockUsersRepositoryTrait::default();
+
+        users_repository
+            .expect_get_profile()
+            .with(eq(1), eq(7))
+            .once()
+            .returning(|_, _| Box::pin(async { Ok(None) }));
+
+        let state = State {
+            users_repository: Arc::new(users_repository),
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::GET)
+                    .uri("/api/v1/users/7")
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,134 @@
+use std::sync::Arc;
+
+use axum::{Extension, Json};
+
+use crate::{
+    api::{State, access::User, errors::ApiError},
+    entities,
+};
+
+/// List bookmarks
+///
+/// List own bookmarked messages, latest bookmark first.
+#[utoipa::path(
+    get,
+    path = "/bookmarks",
+    tag = super::DOCS_BOOKMARKS_TAG,
+    security(
+        ("api_key" = [])
+    ),
+    responses(
+        (status = 200, description = "List bookmarks successfully", body = [entities::bookmark::BookmarkResponse]),
+        (status = 401, description = "Missing or invalid access token", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 503, description = "Storage is unavailable", body = entities::problem::ProblemResponse, content_type = "application/problem+json")
+    )
+)]
+pub async fn list_bookmarks_handler(
+    User(claims): User,
+    Extension(state): Extension<Arc<State>>,
+) -> Result<Json<Vec<entities::bookmark::BookmarkResponse>>, ApiError> {
+    let result = state
+        .bookmarks_repository
+        .list_bookmarks(claims.tenant_id, claims.sub.parse::<i32>().unwrap())
+        .await;
+
+    let bookmarks = match result {
+        Ok(bookmarks) => bookmarks,
+        Err(err) => return Err(err.into()),
+    };
+
+    Ok(Json(
+        bookmarks
+            .into_iter()
+            .map(|bookmark| entities::bookmark::BookmarkResponse {
+                message_id: bookmark.message_id,
+                content: bookmark.message_content,
+                user_id: bookmark.user_id,
+                posted_at: bookmark.posted_at,
+                bookmarked_at: bookmark.bookmarked_at,
+            })
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, body::Body, extract::Request, http};
+    use chrono::{DateTime, Utc};
+    use http_body_util::BodyExt;
+    use mockall::predicate::*;
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    use crate::{
+        api,
+        api::{ApiRouterBuilder, State},
+        domain,
+        infra::repositories,
+    };
+
+    #[tokio::test]
+    async fn test_list_bookmarks_handler_ok() {
+        let mut bookmarks_repository =
+            repositories::bookmarks::MockBookmarksRepositoryTrait::default();
+
+        bookmarks_repository
+            .expect_list_bookmarks()
+            .with(eq(1), eq(123))
+            .once()
+            .returning(|_, _| {
+                Box::pin(async {
+                    let posted_at = DateTime::parse_from_rfc3339("2020-04-12T22:10:57+02:00")
+                        .unwrap()
+                        .with_timezone(&Utc);
+
+                    Ok(vec![domain::bookmark::Bookmark {
+                        message_id: 1,
+                        message_content: "edited later".to_string(),
+                        user_id: 7,
+                        posted_at,
+                        bookmarked_at: posted_at,
+                    }])
+                })
+            });
+
+        let state = State {
+            bookmarks_repository: Arc::new(bookmarks_repository),
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::GET)
+                    .uri("/api/v1/bookmarks")
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body_json: Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(
+            body_json,
+            json!([
+                {
+                    "message_id": 1,
+                    "content": "edited later",
+                    "user_id": 7,
+                    "posted_at": "2020-04-12T20:10:57Z",
+                    "bookmarked_at": "2020-04-12T20:10:57Z"
+                }
+            ])
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use std::sync::Arc;
+
+use axum::{Extension, Json};
+
+use crate::{
+    api::{State, access::Admin, errors::ApiError},
+    entities,
+};
+
+/// List integrations
+///
+/// List all bot integrations including revoked ones.
+#[utoipa::path(
+    get,
+    path = "/integrations",
+    tag = super::DOCS_INTEGRATIONS_TAG,
+    security(
+        ("api_key" = [])
+    ),
+    responses(
+        (status = 200, description = "List all integrations successfully", body = [entities::integration::IntegrationResponse]),
+        (status = 401, description = "Missing or invalid access token", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 403, description = "Access denied", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 503, description = "Storage is unavailable", body = entities::problem::ProblemResponse, content_type = "application/problem+json")
+    )
+)]
+pub async fn list_integrations_handler(
+    Admin(claims): Admin,
+    Extension(state): Extension<Arc<State>>,
+) -> Result<Json<Vec<entities::integration::IntegrationResponse>>, ApiError> {
+    let integrations = match state
+        .integrations_repository
+        .list_integrations(claims.tenant_id)
+        .await
+    {
+        Ok(integrations) => integrations,
+        Err(err) => return Err(err.into()),
+    };
+
+    Ok(Json(
+        integrations
+            .into_iter()
+            .map(|integration| entities::integration::IntegrationResponse {
+                integration_id: integration.integration_id,
+                bot_user_id: integration.bot_user_id(),
+                name: integration.name,
+                created_by: integration.created_by,
+                created_at: integration.created_at,
+                revoked_at: integration.revoked_at,
+            })
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, body::Body, extract::Request, http};
+    use chrono::{DateTime, Utc};
+    use http_body_util::BodyExt;
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    use crate::{
+        api,
+        api::{ApiRouterBuilder, State},
+        domain,
+        infra::repositories,
+    };
+
+    #[tokio::test]
+    async fn test_list_integrations_handler_ok() {
+        let mut integrations_repository =
+            repositories::integrations::MockIntegrationsRepositoryTrait::default();
+
+        integrations_repository
+            .expect_list_integrations()
+            .once()
+            .returning(|_| {
+                Box::pin(async {
+                    let created_at =
+                        DateTime::parse_from_rfc3339("2020-04-12T22:10:57+02:00").unwrap();
+
+                    Ok(vec![domain::integration::Integration {
+                        integration_id: 7,
+                        tenant_id: 1,
+                        name: "ci".to_string(),
+                        created_by: 123,
+                        created_at: created_at.with_timezone(&Utc),
+                        revoked_at: None,
+                    }])
+                })
+            });
+
+        let state = State {
+            integrations_repository: Arc::new(integrations_repository),
+            admin_user_ids: vec![domain::tenant::TenantUser::new(
+                domain::tenant::DEFAULT_TENANT_ID,
+                123,
+            )],
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::GET)
+                    .uri("/api/v1/integrations")
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body_json: Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(
+            body_json,
+            json!([
+                {
+                    "integration_id": 7,
+                    "name": "ci",
+                    "bot_user_id": -7,
+                    "created_by": 123,
+                    "created_at": "2020-04-12T20:10:57Z",
+                    "revoked_at": null
+                }
+            ])
+        );
+    }
+}
@@ -1,16 +1,26 @@
 use std::sync::Arc;
 
-use axum::{Extension, Json, extract::Query};
-use caslex::{errors::DefaultError, middlewares::auth};
+use axum::{
+    Extension, Json,
+    extract::OriginalUri,
+    http::HeaderMap,
+    response::{IntoResponse, Response},
+};
+use validator::Validate;
 
 use crate::{
-    api::{State, query},
+    api::{
+        State, access::User, conditional::Validators, errors::ApiError, extract::Query, query,
+        transcode,
+    },
     entities,
+    infra::cache,
 };
 
 /// List all messages
 ///
-/// List all messages from storage.
+/// List messages from storage, optionally filtered by author, time range and text. Responses
+/// carry `ETag`, unchanged listings are answered with 304 to `If-None-Match`.
 #[utoipa::path(
     get,
     path = "/messages",
@@ -19,37 +29,90 @@ use crate::{
         ("api_key" = [])
     ),
     params(
-        query::Pagination
+        query::Pagination,
+        query::MessageFilters
     ),
     responses(
-        (status = 200, description = "List all messages successfully", body = [entities::message::MessageResponse])
+        (status = 200, description = "List all messages successfully", body = [entities::message::MessageResponse],
+            headers(
+                ("ETag" = String, description = "Validator of the listing"),
+                ("Last-Modified" = String, description = "Time of the latest change in the listing")
+            )
+        ),
+        (status = 304, description = "Listing has not changed since `If-None-Match` validator"),
+        (status = 400, description = "Malformed query parameters", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 401, description = "Missing or invalid access token", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 422, description = "Query parameters are invalid", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 503, description = "Storage is unavailable", body = entities::problem::ProblemResponse, content_type = "application/problem+json")
     )
 )]
 pub async fn list_messages_handler(
-    _: auth::Claims,
+    User(claims): User,
     Extension(state): Extension<Arc<State>>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
     Query(params): Query<query::Pagination>,
-) -> Result<Json<Vec<entities::message::MessageResponse>>, DefaultError> {
-    let result = state
+    Query(filters): Query<query::MessageFilters>,
+) -> Result<Response, ApiError> {
+    params.validate()?;
+    filters.validate()?;
+
+    let version = match state
         .messages_repository
-        .list_messages(params.get_offset(), params.get_limit())
-        .await;
+        .messages_version(claims.tenant_id, filters.to_filter())
+        .await
+    {
+        Ok(version) => version,
+        Err(err) => return Err(err.into()),
+    };
+
+    let validators = Validators::new(
+        &version,
+        transcode::negotiated_type(&headers),
+        uri.query().unwrap_or_default(),
+    );
+    if validators.matches(&headers) {
+        return Ok(validators.not_modified());
+    }
+
+    // cached pages are served only when read at the version the validators describe
+    let result = cache::at_version(
+        version,
+        state.messages_repository.list_messages(
+            claims.tenant_id,
+            filters.to_filter(),
+            params.get_offset(),
+            params.get_limit(),
+        ),
+    )
+    .await;
 
     let db_messages = match result {
         Ok(db_messages) => db_messages,
-        Err(err) => return Err(DefaultError::Other(err)),
+        Err(err) => return Err(err.into()),
     };
 
-    Ok(Json(
+    let mut response = Json(
         db_messages
             .into_iter()
             .map(|msg| entities::message::MessageResponse {
                 message_id: msg.message_id,
+                author: entities::user::AuthorResponse {
+                    user_id: msg.user_id,
+                    display_name: msg.display_name,
+                    avatar_url: msg.avatar_url,
+                },
                 content: msg.message_content,
                 posted_at: msg.posted_at,
+                expires_at: msg.expires_at,
             })
-            .collect(),
-    ))
+            .collect::<Vec<_>>(),
+    )
+    .into_response();
+    validators.apply(&mut response);
+
+    Ok(response)
 }
 
 #[cfg(test)]
@@ -57,7 +120,7 @@ mod tests {
     use std::sync::Arc;
 
     use axum::{Router, body::Body, extract::Request, http};
-    use chrono::{DateTime, Utc};
+    use chrono::{DateTime, Duration, Utc};
     use http_body_util::BodyExt;
     use mockall::predicate::*;
     use serde_json::{Value, json};
@@ -67,35 +130,59 @@ mod tests {
         api,
         api::{ApiRouterBuilder, State},
         domain,
-        infra::repositories,
+        infra::{
+            cache, memory,
+            repositories::{self, messages::MessagesRepositoryTrait},
+        },
     };
 
+    fn version() -> domain::message::ListingVersion {
+        domain::message::ListingVersion {
+            max_message_id: Some(1),
+            last_modified: None,
+            visible: 1,
+        }
+    }
+
     #[tokio::test]
     async fn test_list_messages_handler_ok() {
         let mut messages_repository =
             repositories::messages::MockMessagesRepositoryTrait::default();
 
+        messages_repository
+            .expect_messages_version()
+            .once()
+            .returning(|_, _| Box::pin(async { Ok(version()) }));
         messages_repository
             .expect_list_messages()
-            .with(eq(0), eq(100))
+            .with(
+                eq(1),
+                eq(domain::message::MessageFilter::default()),
+                eq(0),
+                eq(100),
+            )
             .once()
-            .returning(|_, _| {
+            .returning(|_, _, _, _| {
                 Box::pin(async {
                     let posted_at =
                         DateTime::parse_from_rfc3339("2020-04-12T22:10:57+02:00").unwrap();
                     let posted_at_utc = posted_at.with_timezone(&Utc);
 
-                    Ok(vec![domain::message::Message {
+                    Ok(vec![domain::message::AuthoredMessage {
                         message_id: 1,
                         message_content: "test".to_string(),
                         user_id: 123,
                         posted_at: posted_at_utc,
+                        expires_at: Some(posted_at_utc + Duration::days(1)),
+                        display_name: Some("Alice".to_string()),
+                        avatar_url: None,
                     }])
                 })
             });
 
         let state = State {
             messages_repository: Arc::new(messages_repository),
+            ..State::mocked()
         };
         let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
 
@@ -123,9 +210,230 @@ mod tests {
                {
                   "content":"test",
                   "message_id":1,
-                  "posted_at":"2020-04-12T20:10:57Z"
+                  "author":{
+                     "user_id":123,
+                     "display_name":"Alice",
+                     "avatar_url":null
+                  },
+                  "posted_at":"2020-04-12T20:10:57Z",
+                  "expires_at":"2020-04-13T20:10:57Z"
                }
             ])
         );
     }
+
+    #[tokio::test]
+    async fn test_list_messages_handler_filters() {
+        let mut messages_repository =
+            repositories::messages::MockMessagesRepositoryTrait::default();
+
+        let since = DateTime::parse_from_rfc3339("2020-04-12T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        messages_repository
+            .expect_messages_version()
+            .once()
+            .returning(|_, _| Box::pin(async { Ok(version()) }));
+        messages_repository
+            .expect_list_messages()
+            .with(
+                eq(1),
+                eq(domain::message::MessageFilter {
+                    user_id: Some(7),
+                    since: Some(since),
+                    until: None,
+                    text: Some(domain::message::TextMatch::Prefix("50%".to_string())),
+                    order: domain::message::SortOrder::Asc,
+                }),
+                eq(10),
+                eq(5),
+            )
+            .once()
+            .returning(|_, _, _, _| Box::pin(async { Ok(vec![]) }));
+
+        let state = State {
+            messages_repository: Arc::new(messages_repository),
+            ..State::mocked()
+        };
+
+        let (status, _) = list(
+            state,
+            "/api/v1/messages?user_id=7&since=2020-04-12T00:00:00Z&prefix=50%25&order=asc&offset=10&limit=5",
+        )
+        .await;
+
+        assert_eq!(status, http::StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_list_messages_handler_invalid_filters() {
+        for uri in [
+            "/api/v1/messages?since=2020-04-13T00:00:00Z&until=2020-04-12T00:00:00Z",
+            "/api/v1/messages?contains=a&prefix=b",
+            "/api/v1/messages?limit=0",
+        ] {
+            let (status, body) = list(State::mocked(), uri).await;
+
+            assert_eq!(status, http::StatusCode::UNPROCESSABLE_ENTITY, "{uri}");
+            assert_eq!(body["code"], "validation_error", "{uri}");
+        }
+
+        let (status, body) = list(State::mocked(), "/api/v1/messages?order=random").await;
+
+        assert_eq!(status, http::StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "query_rejection");
+    }
+
+    async fn list(state: State, uri: &str) -> (http::StatusCode, Value) {
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::GET)
+                    .uri(uri)
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_list_messages_handler_not_modified() {
+        let mut messages_repository =
+            repositories::messages::MockMessagesRepositoryTrait::default();
+
+        messages_repository
+            .expect_messages_version()
+            .times(3)
+            .returning(|_, _| Box::pin(async { Ok(version()) }));
+        messages_repository
+            .expect_list_messages()
+            .times(2)
+            .returning(|_, _, _, _| Box::pin(async { Ok(vec![]) }));
+
+        let state = Arc::new(State {
+            messages_repository: Arc::new(messages_repository),
+            ..State::mocked()
+        });
+        let app = Router::from(ApiRouterBuilder::new().with_state(state).build());
+
+        let request = |etag: Option<&str>, accept: &str| {
+            let mut request = Request::builder()
+                .method(http::Method::GET)
+                .uri("/api/v1/messages?limit=10")
+                .header(http::header::ACCEPT, accept)
+                .header(http::header::AUTHORIZATION, api::generate_test_token());
+            if let Some(etag) = etag {
+                request = request.header(http::header::IF_NONE_MATCH, etag);
+            }
+            request.body(Body::empty()).unwrap()
+        };
+        let json = "application/json";
+
+        let response = app.clone().oneshot(request(None, json)).await.unwrap();
+        assert_eq!(response.status(), http::StatusCode::OK);
+        assert_eq!(response.headers()[http::header::VARY], "accept");
+        let etag = response.headers()[http::header::ETAG]
+            .to_str()
+            .unwrap()
+            .to_owned();
+
+        // the tag of the JSON body doesn't stand for the MessagePack one
+        let response = app
+            .clone()
+            .oneshot(request(Some(&etag), "application/msgpack"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::OK);
+        assert_ne!(response.headers()[http::header::ETAG], etag.as_str());
+
+        let response = app.oneshot(request(Some(&etag), json)).await.unwrap();
+        assert_eq!(response.status(), http::StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[http::header::ETAG], etag.as_str());
+        assert_eq!(response.headers()[http::header::VARY], "accept");
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert!(body.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_list_messages_handler_cached_by_version() {
+        let store = memory::MemoryStore::new();
+        // writes of another process, e.g. the worker, bypass the cache of this one
+        let worker = memory::MemoryMessagesRepository::new(store.clone());
+        let cached = cache::CachedMessagesRepository::new(
+            Arc::new(memory::MemoryMessagesRepository::new(store)),
+            std::time::Duration::from_secs(60),
+            10,
+        );
+        let state = Arc::new(State {
+            messages_repository: Arc::new(cached),
+            ..State::mocked()
+        });
+        let app = Router::from(ApiRouterBuilder::new().with_state(state).build());
+
+        let post = |content: &str| {
+            worker.create_message(
+                1,
+                domain::message::PostMessage {
+                    content: content.to_string(),
+                    user_id: 123,
+                    posted_at: Utc::now(),
+                    expires_at: None,
+                },
+            )
+        };
+        let request = |etag: Option<&str>| {
+            let mut request = Request::builder()
+                .method(http::Method::GET)
+                .uri("/api/v1/messages")
+                .header(http::header::AUTHORIZATION, api::generate_test_token());
+            if let Some(etag) = etag {
+                request = request.header(http::header::IF_NONE_MATCH, etag);
+            }
+            request.body(Body::empty()).unwrap()
+        };
+        let list = |etag: Option<String>| {
+            let app = app.clone();
+            async move {
+                let response = app.oneshot(request(etag.as_deref())).await.unwrap();
+                let status = response.status();
+                let etag = response.headers()[http::header::ETAG]
+                    .to_str()
+                    .unwrap()
+                    .to_owned();
+                let body = response.into_body().collect().await.unwrap().to_bytes();
+                let body: Option<Value> = serde_json::from_slice(&body).ok();
+                (
+                    status,
+                    etag,
+                    body.map(|body| body.as_array().unwrap().len()),
+                )
+            }
+        };
+
+        post("a").await.unwrap();
+        let (status, first, listed) = list(None).await;
+        assert_eq!(status, http::StatusCode::OK);
+        assert_eq!(listed, Some(1));
+
+        // the new tag comes with the new message, not with the cached page
+        post("b").await.unwrap();
+        let (status, second, listed) = list(Some(first.clone())).await;
+        assert_eq!(status, http::StatusCode::OK);
+        assert_ne!(second, first);
+        assert_eq!(listed, Some(2));
+
+        let (status, _, _) = list(Some(second)).await;
+        assert_eq!(status, http::StatusCode::NOT_MODIFIED);
+    }
 }
@@ -0,0 +1,125 @@
+use std::sync::Arc;
+
+use axum::{Extension, Json};
+
+use crate::{
+    api::{State, access::User, errors::ApiError},
+    entities,
+};
+
+/// List pins
+///
+/// List messages pinned in the room, latest pin first.
+#[utoipa::path(
+    get,
+    path = "/pins",
+    tag = super::DOCS_PINS_TAG,
+    security(
+        ("api_key" = [])
+    ),
+    responses(
+        (status = 200, description = "List pins successfully", body = [entities::bookmark::PinResponse]),
+        (status = 401, description = "Missing or invalid access token", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 503, description = "Storage is unavailable", body = entities::problem::ProblemResponse, content_type = "application/problem+json")
+    )
+)]
+pub async fn list_pins_handler(
+    User(claims): User,
+    Extension(state): Extension<Arc<State>>,
+) -> Result<Json<Vec<entities::bookmark::PinResponse>>, ApiError> {
+    let pins = match state.pins_repository.list_pins(claims.tenant_id).await {
+        Ok(pins) => pins,
+        Err(err) => return Err(err.into()),
+    };
+
+    Ok(Json(
+        pins.into_iter()
+            .map(|pin| entities::bookmark::PinResponse {
+                message_id: pin.message_id,
+                content: pin.message_content,
+                user_id: pin.user_id,
+                posted_at: pin.posted_at,
+                pinned_by: pin.pinned_by,
+                pinned_at: pin.pinned_at,
+            })
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, body::Body, extract::Request, http};
+    use chrono::{DateTime, Utc};
+    use http_body_util::BodyExt;
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    use crate::{
+        api,
+        api::{ApiRouterBuilder, State},
+        domain,
+        infra::repositories,
+    };
+
+    #[tokio::test]
+    async fn test_list_pins_handler_ok() {
+        let mut pins_repository = repositories::pins::MockPinsRepositoryTrait::default();
+
+        pins_repository.expect_list_pins().once().returning(|_| {
+            Box::pin(async {
+                let posted_at = DateTime::parse_from_rfc3339("2020-04-12T22:10:57+02:00")
+                    .unwrap()
+                    .with_timezone(&Utc);
+
+                Ok(vec![domain::bookmark::Pin {
+                    message_id: 1,
+                    message_content: "rules".to_string(),
+                    user_id: 7,
+                    posted_at,
+                    pinned_by: 123,
+                    pinned_at: posted_at,
+                }])
+            })
+        });
+
+        let state = State {
+            pins_repository: Arc::new(pins_repository),
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::GET)
+                    .uri("/api/v1/pins")
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body_json: Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(
+            body_json,
+            json!([
+                {
+                    "message_id": 1,
+                    "content": "rules",
+                    "user_id": 7,
+                    "posted_at": "2020-04-12T20:10:57Z",
+                    "pinned_by": 123,
+                    "pinned_at": "2020-04-12T20:10:57Z"
+                }
+            ])
+        );
+    }
+}
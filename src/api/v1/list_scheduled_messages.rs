@@ -0,0 +1,137 @@
+use std::sync::Arc;
+
+use axum::{Extension, Json};
+
+use crate::{
+    api::{State, access::User, errors::ApiError},
+    entities,
+};
+
+/// List scheduled messages
+///
+/// List own messages waiting to be published.
+#[utoipa::path(
+    get,
+    path = "/messages/scheduled",
+    tag = super::DOCS_MESSAGES_TAG,
+    security(
+        ("api_key" = [])
+    ),
+    responses(
+        (status = 200, description = "List scheduled messages successfully", body = [entities::scheduled::ScheduledMessageResponse]),
+        (status = 401, description = "Missing or invalid access token", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 503, description = "Storage is unavailable", body = entities::problem::ProblemResponse, content_type = "application/problem+json")
+    )
+)]
+pub async fn list_scheduled_messages_handler(
+    User(claims): User,
+    Extension(state): Extension<Arc<State>>,
+) -> Result<Json<Vec<entities::scheduled::ScheduledMessageResponse>>, ApiError> {
+    let result = state
+        .scheduled_messages_repository
+        .list_scheduled_messages(claims.tenant_id, claims.sub.parse::<i32>().unwrap())
+        .await;
+
+    let msgs = match result {
+        Ok(msgs) => msgs,
+        Err(err) => return Err(err.into()),
+    };
+
+    Ok(Json(
+        msgs.into_iter()
+            .map(|msg| entities::scheduled::ScheduledMessageResponse {
+                scheduled_id: msg.scheduled_id,
+                content: msg.message_content,
+                send_at: msg.send_at,
+                expires_in: msg.expires_in,
+                created_at: msg.created_at,
+            })
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, body::Body, extract::Request, http};
+    use chrono::{DateTime, Utc};
+    use http_body_util::BodyExt;
+    use mockall::predicate::*;
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    use crate::{
+        api,
+        api::{ApiRouterBuilder, State},
+        domain,
+        infra::repositories,
+    };
+
+    #[tokio::test]
+    async fn test_list_scheduled_messages_handler_ok() {
+        let mut scheduled_messages_repository =
+            repositories::scheduled_messages::MockScheduledMessagesRepositoryTrait::default();
+
+        scheduled_messages_repository
+            .expect_list_scheduled_messages()
+            .with(eq(1), eq(123))
+            .once()
+            .returning(|_, _| {
+                Box::pin(async {
+                    let send_at = DateTime::parse_from_rfc3339("2030-04-12T22:10:57+02:00")
+                        .unwrap()
+                        .with_timezone(&Utc);
+                    let created_at = DateTime::parse_from_rfc3339("2020-04-12T22:10:57+02:00")
+                        .unwrap()
+                        .with_timezone(&Utc);
+
+                    Ok(vec![domain::scheduled::ScheduledMessage {
+                        scheduled_id: 1,
+                        message_content: "later".to_string(),
+                        user_id: 123,
+                        send_at,
+                        expires_in: Some(60),
+                        created_at,
+                    }])
+                })
+            });
+
+        let state = State {
+            scheduled_messages_repository: Arc::new(scheduled_messages_repository),
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::GET)
+                    .uri("/api/v1/messages/scheduled")
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body_json: Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(
+            body_json,
+            json!([
+                {
+                    "scheduled_id": 1,
+                    "content": "later",
+                    "send_at": "2030-04-12T20:10:57Z",
+                    "expires_in": 60,
+                    "created_at": "2020-04-12T20:10:57Z"
+                }
+            ])
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use std::sync::Arc;
+
+use axum::{Extension, Json};
+
+use crate::{
+    api::{State, access::Admin, errors::ApiError},
+    entities,
+};
+
+/// List webhooks
+///
+/// List all registered webhooks.
+#[utoipa::path(
+    get,
+    path = "/webhooks",
+    tag = super::DOCS_WEBHOOKS_TAG,
+    security(
+        ("api_key" = [])
+    ),
+    responses(
+        (status = 200, description = "List all webhooks successfully", body = [entities::webhook::WebhookResponse]),
+        (status = 401, description = "Missing or invalid access token", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 403, description = "Access denied", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 503, description = "Storage is unavailable", body = entities::problem::ProblemResponse, content_type = "application/problem+json")
+    )
+)]
+pub async fn list_webhooks_handler(
+    Admin(claims): Admin,
+    Extension(state): Extension<Arc<State>>,
+) -> Result<Json<Vec<entities::webhook::WebhookResponse>>, ApiError> {
+    let webhooks = match state
+        .webhooks_repository
+        .list_webhooks(claims.tenant_id)
+        .await
+    {
+        Ok(webhooks) => webhooks,
+        Err(err) => return Err(err.into()),
+    };
+
+    Ok(Json(
+        webhooks
+            .into_iter()
+            .map(|webhook| entities::webhook::WebhookResponse {
+                webhook_id: webhook.webhook_id,
+                url: webhook.url,
+                events: webhook.events,
+                created_by: webhook.created_by,
+                created_at: webhook.created_at,
+            })
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, body::Body, extract::Request, http};
+    use chrono::{DateTime, Utc};
+    use http_body_util::BodyExt;
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    use crate::{
+        api,
+        api::{ApiRouterBuilder, State},
+        domain,
+        infra::repositories,
+    };
+
+    #[tokio::test]
+    async fn test_list_webhooks_handler_ok() {
+        let mut webhooks_repository =
+            repositories::webhooks::MockWebhooksRepositoryTrait::default();
+
+        webhooks_repository
+            .expect_list_webhooks()
+            .once()
+            .returning(|_| {
+                Box::pin(async {
+                    let created_at =
+                        DateTime::parse_from_rfc3339("2020-04-12T22:10:57+02:00").unwrap();
+
+                    Ok(vec![domain::webhook::Webhook {
+                        webhook_id: 1,
+                        url: "https://example.com/hook".to_string(),
+                        events: vec![domain::webhook::EventType::MessageDeleted],
+                        created_by: 123,
+                        created_at: created_at.with_timezone(&Utc),
+                    }])
+                })
+            });
+
+        let state = State {
+            webhooks_repository: Arc::new(webhooks_repository),
+            admin_user_ids: vec![domain::tenant::TenantUser::new(
+                domain::tenant::DEFAULT_TENANT_ID,
+                123,
+            )],
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::GET)
+                    .uri("/api/v1/webhooks")
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body_json: Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(
+            body_json,
+            json!([
+                {
+                    "webhook_id": 1,
+                    "url": "https://example.com/hook",
+                    "events": ["message.deleted"],
+                    "created_by": 123,
+                    "created_at": "2020-04-12T20:10:57Z"
+                }
+            ])
+        );
+    }
+}
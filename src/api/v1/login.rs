@@ -2,10 +2,12 @@ use std::sync::Arc;
 
 use anyhow::anyhow;
 use axum::{Extension, Json};
-use caslex::{errors::DefaultError, middlewares::auth::Claims};
 use caslex_extra::security::jwt;
 
-use crate::{api::State, entities};
+use crate::{
+    api::{State, access::Claims, errors::ApiError},
+    domain, entities,
+};
 
 /// Login
 ///
@@ -15,23 +17,25 @@ use crate::{api::State, entities};
     path = "/login",
     tag = super::DOCS_AUTH_TAG,
     responses(
-        (status = 200, description = "List all todos successfully", body = entities::auth::LoginResponse)
+        (status = 200, description = "List all todos successfully", body = entities::auth::LoginResponse),
+        (status = 500, description = "Internal error", body = entities::problem::ProblemResponse, content_type = "application/problem+json")
     )
 )]
 pub async fn login_handler(
     Extension(_state): Extension<Arc<State>>,
-) -> Result<Json<entities::auth::LoginResponse>, DefaultError> {
+) -> Result<Json<entities::auth::LoginResponse>, ApiError> {
     const USER_ID: i32 = 123;
     const TOKEN_LIFETIME_SECS: u64 = 300;
 
     let claims = Claims {
         sub: USER_ID.to_string(),
         exp: jwt::expiry(TOKEN_LIFETIME_SECS),
+        tenant_id: domain::tenant::DEFAULT_TENANT_ID,
     };
 
     let token = match jwt::encode_token(&claims) {
         Ok(token) => token,
-        Err(error) => return Err(DefaultError::Other(anyhow!(error))),
+        Err(error) => return Err(ApiError::Internal(anyhow!(error))),
     };
 
     Ok(Json::from(entities::auth::LoginResponse { token }))
@@ -56,6 +60,7 @@ mod tests {
         let messages_repository = repositories::messages::MockMessagesRepositoryTrait::default();
         let state = State {
             messages_repository: Arc::new(messages_repository),
+            ..State::mocked()
         };
         let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
 
@@ -1,6 +1,38 @@
+pub mod add_bookmark;
+pub mod batch_get_messages;
+pub mod cancel_scheduled_message;
+pub mod create_integration;
+pub mod create_tenant;
+pub mod create_webhook;
+pub mod delete_message;
+pub mod delete_webhook;
+pub mod edit_message;
+pub mod edit_scheduled_message;
+pub mod export_messages;
+pub mod get_me;
+pub mod get_message;
+pub mod get_user;
+pub mod list_bookmarks;
+pub mod list_integrations;
 pub mod list_messages;
+pub mod list_pins;
+pub mod list_scheduled_messages;
+pub mod list_webhooks;
 pub mod login;
+pub mod pin_message;
 pub mod post_message;
+pub mod remove_bookmark;
+pub mod revoke_integration;
+pub mod rotate_integration_token;
+pub mod suspend_tenant;
+pub mod unpin_message;
+pub mod update_me;
 
 const DOCS_AUTH_TAG: &str = "AUTH";
+const DOCS_BOOKMARKS_TAG: &str = "BOOKMARKS";
+const DOCS_INTEGRATIONS_TAG: &str = "INTEGRATIONS";
 const DOCS_MESSAGES_TAG: &str = "MESSAGES";
+const DOCS_PINS_TAG: &str = "PINS";
+const DOCS_TENANTS_TAG: &str = "TENANTS";
+const DOCS_USERS_TAG: &str = "USERS";
+const DOCS_WEBHOOKS_TAG: &str = "WEBHOOKS";
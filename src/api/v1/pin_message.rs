@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use axum::{Extension, extract::Path, http::StatusCode};
+
+use crate::{
+    api::{State, access::Moderator, errors::ApiError},
+    entities,
+};
+
+/// Pin message
+///
+/// Pin message for everyone in the room.
+#[utoipa::path(
+    put,
+    path = "/pins/{message_id}",
+    tag = super::DOCS_PINS_TAG,
+    security(
+        ("api_key" = [])
+    ),
+    params(
+        ("message_id" = i64, Path, description = "Message id")
+    ),
+    responses(
+        (status = 204, description = "Message pinned successfully"),
+        (status = 401, description = "Missing or invalid access token", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 403, description = "Access denied", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 404, description = "Message not found", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 503, description = "Storage is unavailable", body = entities::problem::ProblemResponse, content_type = "application/problem+json")
+    )
+)]
+pub async fn pin_message_handler(
+    Moderator(claims): Moderator,
+    Extension(state): Extension<Arc<State>>,
+    Path(message_id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    match state
+        .pins_repository
+        .pin_message(
+            claims.tenant_id,
+            message_id,
+            claims.sub.parse::<i32>().unwrap(),
+        )
+        .await
+    {
+        Ok(true) => Ok(StatusCode::NO_CONTENT),
+        Ok(false) => Err(ApiError::NotFound("message")),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, body::Body, extract::Request, http};
+    use mockall::predicate::*;
+    use tower::ServiceExt;
+
+    use crate::{
+        api,
+        api::{ApiRouterBuilder, State},
+        domain,
+        infra::repositories,
+    };
+
+    fn pin_request() -> Request<Body> {
+        Request::builder()
+            .method(http::Method::PUT)
+            .uri("/api/v1/pins/1")
+            .header(http::header::AUTHORIZATION, api::generate_test_token())
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_pin_message_handler_ok() {
+        let mut pins_repository = repositories::pins::MockPinsRepositoryTrait::default();
+
+        pins_repository
+            .expect_pin_message()
+            .with(eq(1), eq(1), eq(123))
+            .once()
+            .returning(|_, _, _| Box::pin(async { Ok(true) }));
+
+        let state = State {
+            pins_repository: Arc::new(pins_repository),
+            moderator_user_ids: vec![domain::tenant::TenantUser::new(
+                domain::tenant::DEFAULT_TENANT_ID,
+                123,
+            )],
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app.oneshot(pin_request()).await.unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn test_pin_message_handler_not_moderator() {
+        let mut pins_repository = repositories::pins::MockPinsRepositoryTrait::default();
+        pins_repository.expect_pin_message().never();
+
+        let state = State {
+            pins_repository: Arc::new(pins_repository),
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app.oneshot(pin_request()).await.unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::FORBIDDEN);
+    }
+}
@@ -1,14 +1,14 @@
-use std::sync::Arc;
+use std::{borrow::Cow, sync::Arc};
 
 use axum::{Extension, Json};
-use caslex::{
-    errors::{AppJson, DefaultError},
-    middlewares::auth,
-};
-use chrono::Utc;
-use validator::Validate;
+use chrono::{Duration, Utc};
+use validator::{Validate, ValidationError, ValidationErrors};
 
-use crate::{api::State, domain, entities};
+use crate::{
+    api::{State, access::User, errors::ApiError, extract::AppJson},
+    commands::{self, CommandOutcome},
+    domain, entities,
+};
 
 /// Post message
 ///
@@ -22,36 +22,144 @@ use crate::{api::State, domain, entities};
     ),
     request_body = entities::message::PostMessageRequest,
     responses(
-            (status = 200, description = "", body = entities::message::PostMessageResponse)
+            (status = 200, description = "", body = entities::message::PostMessageResponse),
+        (status = 401, description = "Missing or invalid access token", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 422, description = "Request body is invalid", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 503, description = "Storage is unavailable", body = entities::problem::ProblemResponse, content_type = "application/problem+json")
     )
 )]
 pub async fn post_message_handler(
-    claims: auth::Claims,
+    User(claims): User,
     Extension(state): Extension<Arc<State>>,
     AppJson(payload): AppJson<entities::message::PostMessageRequest>,
-) -> Result<Json<entities::message::PostMessageResponse>, DefaultError> {
+) -> Result<Json<entities::message::PostMessageResponse>, ApiError> {
+    let response = publish_message(
+        &state,
+        claims.tenant_id,
+        claims.sub.parse::<i32>().unwrap(),
+        payload,
+    )
+    .await?;
+
+    Ok(Json(response))
+}
+
+/// Validates and saves message of the tenant on behalf of the user. Every way of posting goes
+/// through it, messages starting with `/` are routed to command handlers first. Messages with
+/// future `send_at` are kept aside until the worker publishes them.
+pub(crate) async fn publish_message(
+    state: &State,
+    tenant_id: i32,
+    user_id: i32,
+    payload: entities::message::PostMessageRequest,
+) -> Result<entities::message::PostMessageResponse, ApiError> {
     match payload.validate() {
         Ok(_) => {}
         Err(err) => {
-            return Err(DefaultError::ValidationError(err));
+            return Err(ApiError::Validation(err));
         }
     }
 
-    let result = state
-        .messages_repository
+    let send_at = payload.send_at.filter(|send_at| *send_at > Utc::now());
+    let expires_in = payload.expires_in;
+
+    let (author_id, text) = match commands::parse(&payload.text) {
+        commands::Input::Text(text) => (user_id, text.to_owned()),
+        commands::Input::Command { name, args } => {
+            let ctx = commands::CommandContext {
+                user_id,
+                name: name.to_owned(),
+                args: args.to_owned(),
+            };
+
+            let outcome = match state.commands.dispatch(ctx).await {
+                Ok(outcome) => outcome,
+                Err(err) => return Err(err.into()),
+            };
+
+            match outcome {
+                CommandOutcome::Ephemeral(reply) => {
+                    return Ok(entities::message::PostMessageResponse {
+                        message_id: None,
+                        ephemeral: Some(reply),
+                        scheduled_id: None,
+                    });
+                }
+                CommandOutcome::Reject(reason) => {
+                    return Err(ApiError::Validation(rejection(reason)));
+                }
+                CommandOutcome::Post(text) => (user_id, text),
+                CommandOutcome::PostAsBot(text) => (domain::message::SYSTEM_BOT_USER_ID, text),
+            }
+        }
+    };
+
+    // command output must satisfy the same limits as typed text
+    validate_text(&text)?;
+
+    if let Some(send_at) = send_at {
+        let result = state
+            .scheduled_messages_repository
+            .schedule_message(domain::scheduled::ScheduleMessage {
+                tenant_id,
+                content: text,
+                user_id: author_id,
+                send_at,
+                expires_in,
+            })
+            .await;
+
+        return match result {
+            Ok(scheduled_id) => Ok(entities::message::PostMessageResponse {
+                message_id: None,
+                ephemeral: None,
+                scheduled_id: Some(scheduled_id),
+            }),
+            Err(err) => Err(err.into()),
+        };
+    }
+
+    let posted_at = Utc::now();
+    // the message and its webhook event are written together
+    let mut unit = state.unit_of_work.begin(tenant_id).await?;
+    let message_id = unit
         .create_message(domain::message::PostMessage {
-            content: payload.text,
-            user_id: claims.sub.parse::<i32>().unwrap(),
-            posted_at: Utc::now(),
+            content: text,
+            user_id: author_id,
+            posted_at,
+            expires_at: expires_in.map(|secs| posted_at + Duration::seconds(secs)),
         })
-        .await;
+        .await?;
+    unit.commit().await?;
+
+    Ok(entities::message::PostMessageResponse {
+        message_id: Some(message_id),
+        ephemeral: None,
+        scheduled_id: None,
+    })
+}
 
-    let message_id = match result {
-        Ok(message_id) => message_id,
-        Err(err) => return Err(DefaultError::Other(err)),
+fn validate_text(text: &str) -> Result<(), ApiError> {
+    let request = entities::message::PostMessageRequest {
+        text: text.to_owned(),
+        send_at: None,
+        expires_in: None,
     };
 
-    Ok(Json(entities::message::PostMessageResponse { message_id }))
+    match request.validate() {
+        Ok(_) => Ok(()),
+        Err(err) => Err(ApiError::Validation(err)),
+    }
+}
+
+fn rejection(reason: String) -> ValidationErrors {
+    let mut errors = ValidationErrors::new();
+    errors.add(
+        "text",
+        ValidationError::new("command_rejected").with_message(Cow::Owned(reason)),
+    );
+    errors
 }
 
 #[cfg(test)]
@@ -59,6 +167,7 @@ mod tests {
     use std::sync::Arc;
 
     use axum::{Router, body::Body, extract::Request, http};
+    use chrono::{Duration, Utc};
     use http_body_util::BodyExt;
     use serde_json::{Value, json};
     use tower::ServiceExt;
@@ -66,22 +175,21 @@ mod tests {
     use crate::{
         api,
         api::{ApiRouterBuilder, State},
+        domain,
         infra::repositories,
     };
 
     #[tokio::test]
     async fn test_post_message_handler_ok() {
-        let mut messages_repository =
-            repositories::messages::MockMessagesRepositoryTrait::default();
-
-        messages_repository
-            .expect_create_message()
-            .withf(|x| x.content == *"test-msg" && x.user_id == 123)
-            .once()
-            .returning(|_| Box::pin(async { Ok(1) }));
+        let unit_of_work = repositories::unit_of_work::MockUnitOfWorkFactoryTrait::posting(
+            1,
+            |x| x.content == *"test-msg" && x.user_id == 123,
+            1,
+        );
 
         let state = State {
-            messages_repository: Arc::new(messages_repository),
+            unit_of_work: Arc::new(unit_of_work),
+            ..State::mocked()
         };
         let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
 
@@ -107,4 +215,169 @@ mod tests {
 
         assert_eq!(body_json, json!({"message_id": 1}));
     }
+
+    async fn post_text(state: State, text: &str) -> (http::StatusCode, Value) {
+        post_json(state, json!({ "text": text })).await
+    }
+
+    async fn post_json(state: State, payload: Value) -> (http::StatusCode, Value) {
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/api/v1/messages")
+                    .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::from(serde_json::to_vec(&payload).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_post_message_handler_scheduled() {
+        let mut unit_of_work = repositories::unit_of_work::MockUnitOfWorkFactoryTrait::default();
+        let mut scheduled_messages_repository =
+            repositories::scheduled_messages::MockScheduledMessagesRepositoryTrait::default();
+
+        unit_of_work.expect_begin().never();
+        scheduled_messages_repository
+            .expect_schedule_message()
+            .withf(|x| x.content == *"later" && x.user_id == 123)
+            .once()
+            .returning(|_| Box::pin(async { Ok(5) }));
+
+        let state = State {
+            unit_of_work: Arc::new(unit_of_work),
+            scheduled_messages_repository: Arc::new(scheduled_messages_repository),
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let send_at = Utc::now() + Duration::hours(1);
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/api/v1/messages")
+                    .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::from(
+                        serde_json::to_vec(&json!({ "text": "later", "send_at": send_at }))
+                            .unwrap(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body_json: Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(body_json, json!({"scheduled_id": 5}));
+    }
+
+    #[tokio::test]
+    async fn test_post_message_handler_expires_in() {
+        let unit_of_work = repositories::unit_of_work::MockUnitOfWorkFactoryTrait::posting(
+            1,
+            |x| x.expires_at == Some(x.posted_at + Duration::seconds(60)),
+            6,
+        );
+
+        let state = State {
+            unit_of_work: Arc::new(unit_of_work),
+            ..State::mocked()
+        };
+
+        let (status, body) =
+            post_json(state, json!({ "text": "gone soon", "expires_in": 60 })).await;
+
+        assert_eq!(status, http::StatusCode::OK);
+        assert_eq!(body, json!({"message_id": 6}));
+    }
+
+    #[tokio::test]
+    async fn test_post_message_handler_me_command() {
+        let unit_of_work = repositories::unit_of_work::MockUnitOfWorkFactoryTrait::posting(
+            1,
+            |x| x.content == *"_waves_" && x.user_id == 123,
+            2,
+        );
+
+        let state = State {
+            unit_of_work: Arc::new(unit_of_work),
+            ..State::mocked()
+        };
+
+        let (status, body) = post_text(state, "/me waves").await;
+
+        assert_eq!(status, http::StatusCode::OK);
+        assert_eq!(body, json!({"message_id": 2}));
+    }
+
+    #[tokio::test]
+    async fn test_post_message_handler_poll_command_posts_as_bot() {
+        let unit_of_work = repositories::unit_of_work::MockUnitOfWorkFactoryTrait::posting(
+            1,
+            |x| {
+                x.content == *"Poll by user 123: Lunch?\n1. pizza\n2. sushi"
+                    && x.user_id == domain::message::SYSTEM_BOT_USER_ID
+            },
+            3,
+        );
+
+        let state = State {
+            unit_of_work: Arc::new(unit_of_work),
+            ..State::mocked()
+        };
+
+        let (status, body) = post_text(state, "/poll Lunch? | pizza | sushi").await;
+
+        assert_eq!(status, http::StatusCode::OK);
+        assert_eq!(body, json!({"message_id": 3}));
+    }
+
+    #[tokio::test]
+    async fn test_post_message_handler_help_command_is_ephemeral() {
+        let mut unit_of_work = repositories::unit_of_work::MockUnitOfWorkFactoryTrait::default();
+        unit_of_work.expect_begin().never();
+
+        let state = State {
+            unit_of_work: Arc::new(unit_of_work),
+            ..State::mocked()
+        };
+
+        let (status, body) = post_text(state, "/help").await;
+
+        assert_eq!(status, http::StatusCode::OK);
+        assert!(body.get("message_id").is_none());
+        assert!(body["ephemeral"].as_str().unwrap().contains("/poll"));
+    }
+
+    #[tokio::test]
+    async fn test_post_message_handler_unknown_command_rejected() {
+        let mut unit_of_work = repositories::unit_of_work::MockUnitOfWorkFactoryTrait::default();
+        unit_of_work.expect_begin().never();
+
+        let state = State {
+            unit_of_work: Arc::new(unit_of_work),
+            ..State::mocked()
+        };
+
+        let (status, body) = post_text(state, "/nope").await;
+
+        assert_eq!(status, http::StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["code"], "validation_error");
+    }
 }
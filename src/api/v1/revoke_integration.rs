@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use axum::{Extension, extract::Path, http::StatusCode};
+
+use crate::{
+    api::{State, access::Admin, errors::ApiError},
+    entities,
+};
+
+/// Revoke integration
+///
+/// Permanently disable integration incoming webhook.
+#[utoipa::path(
+    delete,
+    path = "/integrations/{integration_id}",
+    tag = super::DOCS_INTEGRATIONS_TAG,
+    security(
+        ("api_key" = [])
+    ),
+    params(
+        ("integration_id" = i64, Path, description = "Integration id")
+    ),
+    responses(
+        (status = 204, description = "Integration revoked successfully"),
+        (status = 401, description = "Missing or invalid access token", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 403, description = "Access denied", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 404, description = "Integration not found", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 503, description = "Storage is unavailable", body = entities::problem::ProblemResponse, content_type = "application/problem+json")
+    )
+)]
+pub async fn revoke_integration_handler(
+    Admin(claims): Admin,
+    Extension(state): Extension<Arc<State>>,
+    Path(integration_id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    match state
+        .integrations_repository
+        .revoke_integration(claims.tenant_id, integration_id)
+        .await
+    {
+        Ok(true) => Ok(StatusCode::NO_CONTENT),
+        Ok(false) => Err(ApiError::NotFound("integration")),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, body::Body, extract::Request, http};
+    use mockall::predicate::*;
+    use tower::ServiceExt;
+
+    use crate::{
+        api,
+        api::{ApiRouterBuilder, State},
+        domain,
+        infra::repositories,
+    };
+
+    #[tokio::test]
+    async fn test_revoke_integration_handler_ok() {
+        let mut integrations_repository =
+            repositories::integrations::MockIntegrationsRepositoryTrait::default();
+
+        integrations_repository
+            .expect_revoke_integration()
+            .with(eq(1), eq(1))
+            .once()
+            .returning(|_, _| Box::pin(async { Ok(true) }));
+
+        let state = State {
+            integrations_repository: Arc::new(integrations_repository),
+            admin_user_ids: vec![domain::tenant::TenantUser::new(
+                domain::tenant::DEFAULT_TENANT_ID,
+                123,
+            )],
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::DELETE)
+                    .uri("/api/v1/integrations/1")
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::NO_CONTENT);
+    }
+}
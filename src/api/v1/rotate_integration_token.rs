@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use axum::{Extension, Json, extract::Path};
+
+use crate::{
+    api::{State, access::Admin, errors::ApiError, hooks},
+    domain, entities,
+};
+
+/// Rotate integration token
+///
+/// Issue new incoming webhook token, the previous one stops working immediately.
+#[utoipa::path(
+    post,
+    path = "/integrations/{integration_id}/token",
+    tag = super::DOCS_INTEGRATIONS_TAG,
+    security(
+        ("api_key" = [])
+    ),
+    params(
+        ("integration_id" = i64, Path, description = "Integration id")
+    ),
+    responses(
+        (status = 200, description = "Token rotated successfully", body = entities::integration::IntegrationTokenResponse),
+        (status = 401, description = "Missing or invalid access token", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 403, description = "Access denied", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 404, description = "Integration not found", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 503, description = "Storage is unavailable", body = entities::problem::ProblemResponse, content_type = "application/problem+json")
+    )
+)]
+pub async fn rotate_integration_token_handler(
+    Admin(claims): Admin,
+    Extension(state): Extension<Arc<State>>,
+    Path(integration_id): Path<i64>,
+) -> Result<Json<entities::integration::IntegrationTokenResponse>, ApiError> {
+    let token = domain::crypto::random_token();
+
+    let result = state
+        .integrations_repository
+        .rotate_integration_token(
+            claims.tenant_id,
+            integration_id,
+            domain::crypto::hash_token(&token),
+        )
+        .await;
+
+    match result {
+        Ok(true) => Ok(Json(entities::integration::IntegrationTokenResponse {
+            integration_id,
+            hook_path: hooks::hook_path(&token),
+            token,
+        })),
+        Ok(false) => Err(ApiError::NotFound("integration")),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, body::Body, extract::Request, http};
+    use mockall::predicate::*;
+    use tower::ServiceExt;
+
+    use crate::{
+        api,
+        api::{ApiRouterBuilder, State},
+        domain,
+        infra::repositories,
+    };
+
+    #[tokio::test]
+    async fn test_rotate_integration_token_handler_revoked() {
+        let mut integrations_repository =
+            repositories::integrations::MockIntegrationsRepositoryTrait::default();
+
+        integrations_repository
+            .expect_rotate_integration_token()
+            .with(eq(1), eq(1), always())
+            .once()
+            .returning(|_, _, _| Box::pin(async { Ok(false) }));
+
+        let state = State {
+            integrations_repository: Arc::new(integrations_repository),
+            admin_user_ids: vec![domain::tenant::TenantUser::new(
+                domain::tenant::DEFAULT_TENANT_ID,
+                123,
+            )],
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/api/v1/integrations/1/token")
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+    }
+}
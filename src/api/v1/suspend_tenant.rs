@@ -0,0 +1,140 @@
+use std::sync::Arc;
+
+use axum::{Extension, Json, extract::Path};
+
+use crate::{
+    api::{State, access::PlatformAdmin, errors::ApiError},
+    entities,
+};
+
+/// Suspend tenant
+///
+/// Reject further requests of tenant users and integrations, data of the tenant is kept.
+#[utoipa::path(
+    post,
+    path = "/tenants/{tenant_id}/suspend",
+    tag = super::DOCS_TENANTS_TAG,
+    security(
+        ("api_key" = [])
+    ),
+    params(
+        ("tenant_id" = i32, Path, description = "Tenant id")
+    ),
+    responses(
+        (status = 200, description = "Tenant suspended successfully", body = entities::tenant::TenantResponse),
+        (status = 401, description = "Missing or invalid access token", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 403, description = "Access denied", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 404, description = "Tenant not found", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 503, description = "Storage is unavailable", body = entities::problem::ProblemResponse, content_type = "application/problem+json")
+    )
+)]
+pub async fn suspend_tenant_handler(
+    _: PlatformAdmin,
+    Extension(state): Extension<Arc<State>>,
+    Path(tenant_id): Path<i32>,
+) -> Result<Json<entities::tenant::TenantResponse>, ApiError> {
+    match state.tenants_repository.suspend_tenant(tenant_id).await {
+        Ok(Some(tenant)) => Ok(Json(entities::tenant::TenantResponse {
+            tenant_id: tenant.tenant_id,
+            name: tenant.name,
+            created_at: tenant.created_at,
+            suspended_at: tenant.suspended_at,
+        })),
+        Ok(None) => Err(ApiError::NotFound("tenant")),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, body::Body, extract::Request, http};
+    use chrono::Utc;
+    use http_body_util::BodyExt;
+    use mockall::predicate::*;
+    use tower::ServiceExt;
+
+    use crate::{
+        api,
+        api::{ApiRouterBuilder, State},
+        domain, entities,
+        infra::repositories,
+    };
+
+    fn tenant(tenant_id: i32, suspended: bool) -> domain::tenant::Tenant {
+        domain::tenant::Tenant {
+            tenant_id,
+            name: "acme".to_owned(),
+            created_at: Utc::now(),
+            suspended_at: suspended.then(Utc::now),
+        }
+    }
+
+    async fn suspend(
+        tenants_repository: repositories::tenants::MockTenantsRepositoryTrait,
+    ) -> http::Response<Body> {
+        let state = State {
+            tenants_repository: Arc::new(tenants_repository),
+            admin_user_ids: vec![domain::tenant::TenantUser::new(
+                domain::tenant::DEFAULT_TENANT_ID,
+                123,
+            )],
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        app.oneshot(
+            Request::builder()
+                .method(http::Method::POST)
+                .uri("/api/v1/tenants/2/suspend")
+                .header(http::header::AUTHORIZATION, api::generate_test_token())
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_suspend_tenant_handler_ok() {
+        let mut tenants_repository = repositories::tenants::MockTenantsRepositoryTrait::default();
+
+        tenants_repository
+            .expect_get_tenant()
+            .returning(|tenant_id| Box::pin(async move { Ok(Some(tenant(tenant_id, false))) }));
+        tenants_repository
+            .expect_suspend_tenant()
+            .with(eq(2))
+            .once()
+            .returning(|tenant_id| Box::pin(async move { Ok(Some(tenant(tenant_id, true))) }));
+
+        let response = suspend(tenants_repository).await;
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: entities::tenant::TenantResponse = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(body.tenant_id, 2);
+        assert!(body.suspended_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_suspend_tenant_handler_not_found() {
+        let mut tenants_repository = repositories::tenants::MockTenantsRepositoryTrait::default();
+
+        tenants_repository
+            .expect_get_tenant()
+            .returning(|tenant_id| Box::pin(async move { Ok(Some(tenant(tenant_id, false))) }));
+        tenants_repository
+            .expect_suspend_tenant()
+            .once()
+            .returning(|_| Box::pin(async { Ok(None) }));
+
+        let response = suspend(tenants_repository).await;
+
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+    }
+}
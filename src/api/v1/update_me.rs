@@ -0,0 +1,198 @@
+use std::sync::Arc;
+
+use axum::{Extension, Json};
+use validator::Validate;
+
+use crate::{
+    api::{State, access::User, errors::ApiError, extract::AppJson},
+    domain, entities,
+};
+
+/// Update own profile
+///
+/// Change display name, avatar or bio of the current user.
+#[utoipa::path(
+    patch,
+    path = "/users/me",
+    tag = super::DOCS_USERS_TAG,
+    security(
+        ("api_key" = [])
+    ),
+    request_body = entities::user::UpdateProfileRequest,
+    responses(
+        (status = 200, description = "Profile updated successfully", body = entities::user::UserProfileResponse),
+        (status = 401, description = "Missing or invalid access token", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 422, description = "Request body is invalid", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 503, description = "Storage is unavailable", body = entities::problem::ProblemResponse, content_type = "application/problem+json")
+    )
+)]
+pub async fn update_me_handler(
+    User(claims): User,
+    Extension(state): Extension<Arc<State>>,
+    AppJson(payload): AppJson<entities::user::UpdateProfileRequest>,
+) -> Result<Json<entities::user::UserProfileResponse>, ApiError> {
+    match payload.validate() {
+        Ok(_) => {}
+        Err(err) => {
+            return Err(ApiError::Validation(err));
+        }
+    }
+
+    let result = state
+        .users_repository
+        .update_profile(
+            claims.tenant_id,
+            claims.sub.parse::<i32>().unwrap(),
+            domain::user::ProfileChange {
+                display_name: payload.display_name,
+                avatar_url: payload.avatar_url,
+                bio: payload.bio,
+            },
+        )
+        .await;
+
+    let profile = match result {
+        Ok(profile) => profile,
+        Err(err) => return Err(err.into()),
+    };
+
+    Ok(Json(entities::user::UserProfileResponse {
+        user_id: profile.user_id,
+        display_name: profile.display_name,
+        avatar_url: profile.avatar_url,
+        bio: profile.bio,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, body::Body, extract::Request, http};
+    use http_body_util::BodyExt;
+    use mockall::predicate::*;
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    use crate::{
+        api,
+        api::{ApiRouterBuilder, State},
+        domain,
+        infra::repositories,
+    };
+
+    fn update_request(payload: Value) -> Request<Body> {
+        Request::builder()
+            .method(http::Method::PATCH)
+            .uri("/api/v1/users/me")
+            .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+            .header(http::header::AUTHORIZATION, api::generate_test_token())
+            .body(Body::from(serde_json::to_vec(&payload).unwrap()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_update_me_handler_ok() {
+        let mut users_repository = repositories::users::MockUsersRepositoryTrait::default();
+
+        users_repository
+            .expect_update_profile()
+            .with(
+                eq(1),
+                eq(123),
+                eq(domain::user::ProfileChange {
+                    display_name: Some(Some("Alice".to_string())),
+                    ..Default::default()
+                }),
+            )
+            .once()
+            .returning(|_, user_id, change| {
+                Box::pin(async move {
+                    Ok(domain::user::UserProfile {
+                        display_name: change.display_name.flatten(),
+                        bio: Some("kept".to_string()),
+                        ..domain::user::UserProfile::empty(user_id)
+                    })
+                })
+            });
+
+        let state = State {
+            users_repository: Arc::new(users_repository),
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(update_request(json!({ "display_name": "Alice" })))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body_json: Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(
+            body_json,
+            json!({
+                "user_id": 123,
+                "display_name": "Alice",
+                "avatar_url": null,
+                "bio": "kept"
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_me_handler_clears_null_fields() {
+        let mut users_repository = repositories::users::MockUsersRepositoryTrait::default();
+
+        users_repository
+            .expect_update_profile()
+            .with(
+                eq(1),
+                eq(123),
+                eq(domain::user::ProfileChange {
+                    bio: Some(None),
+                    ..Default::default()
+                }),
+            )
+            .once()
+            .returning(|_, user_id, _| {
+                Box::pin(async move { Ok(domain::user::UserProfile::empty(user_id)) })
+            });
+
+        let state = State {
+            users_repository: Arc::new(users_repository),
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(update_request(json!({ "bio": null })))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_update_me_handler_invalid_avatar() {
+        let mut users_repository = repositories::users::MockUsersRepositoryTrait::default();
+        users_repository.expect_update_profile().never();
+
+        let state = State {
+            users_repository: Arc::new(users_repository),
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(update_request(json!({ "avatar_url": "not a url" })))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
@@ -0,0 +1,373 @@
+use std::sync::Arc;
+
+use axum::{
+    Extension, Json,
+    extract::OriginalUri,
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use validator::Validate;
+
+use crate::{
+    api::{
+        State, access::User, conditional::Validators, errors::ApiError, extract::Query, pagination,
+        query, transcode,
+    },
+    domain::message,
+    entities,
+    infra::cache,
+};
+
+/// List messages page
+///
+/// List messages page by page. Adjacent pages are linked by cursors in the body and by the
+/// `Link` header. Unchanged pages are answered with 304 to `If-None-Match`.
+#[utoipa::path(
+    get,
+    path = "/messages",
+    operation_id = "list_messages_v2",
+    tag = super::DOCS_MESSAGES_TAG,
+    security(
+        ("api_key" = [])
+    ),
+    params(
+        query::CursorPagination,
+        query::MessageFilters
+    ),
+    responses(
+        (status = 200, description = "List messages page successfully", body = entities::page::PageResponse<entities::message::MessageResponse>,
+            headers(
+                ("Link" = String, description = "Links to next and prev pages, RFC 8288"),
+                ("ETag" = String, description = "Validator of the page"),
+                ("Last-Modified" = String, description = "Time of the latest change in the listing")
+            )
+        ),
+        (status = 304, description = "Page has not changed since `If-None-Match` validator"),
+        (status = 400, description = "Malformed query parameters or cursor", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 401, description = "Missing or invalid access token", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 422, description = "Query parameters are invalid", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Internal error", body = entities::problem::ProblemResponse, content_type = "application/problem+json"),
+        (status = 503, description = "Storage is unavailable", body = entities::problem::ProblemResponse, content_type = "application/problem+json")
+    )
+)]
+pub async fn list_messages_handler(
+    User(claims): User,
+    Extension(state): Extension<Arc<State>>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+    Query(params): Query<query::CursorPagination>,
+    Query(filters): Query<query::MessageFilters>,
+) -> Result<Response, ApiError> {
+    params.validate()?;
+    filters.validate()?;
+
+    let cursor = match params.cursor.as_deref().map(pagination::decode_cursor) {
+        None => None,
+        Some(Some(cursor)) => Some(cursor),
+        Some(None) => {
+            return Err(ApiError::Rejected {
+                status: StatusCode::BAD_REQUEST,
+                code: "invalid_cursor".to_owned(),
+                detail: "cursor is malformed".to_owned(),
+            });
+        }
+    };
+    let limit = params.get_limit();
+
+    let version = match state
+        .messages_repository
+        .messages_version(claims.tenant_id, filters.to_filter())
+        .await
+    {
+        Ok(version) => version,
+        Err(err) => return Err(err.into()),
+    };
+
+    // v2 is not transcoded, listings are always JSON
+    let validators = Validators::new(&version, transcode::JSON, uri.query().unwrap_or_default());
+    if validators.matches(&headers) {
+        return Ok(validators.not_modified());
+    }
+
+    // one extra message tells whether the page has a neighbour in reading direction, cached
+    // pages are served only when read at the version the validators describe
+    let result = cache::at_version(
+        version,
+        state.messages_repository.list_messages_page(
+            claims.tenant_id,
+            filters.to_filter(),
+            cursor,
+            limit + 1,
+        ),
+    )
+    .await;
+
+    let mut db_messages = match result {
+        Ok(db_messages) => db_messages,
+        Err(err) => return Err(err.into()),
+    };
+
+    let backward = matches!(
+        cursor,
+        Some(message::Cursor {
+            direction: message::Direction::Prev,
+            ..
+        })
+    );
+    let has_more = db_messages.len() as i64 > limit;
+    if has_more {
+        if backward {
+            db_messages.remove(0);
+        } else {
+            db_messages.pop();
+        }
+    }
+
+    let cursor_at = |msg: &message::AuthoredMessage, direction| {
+        pagination::encode_cursor(&message::Cursor {
+            posted_at: msg.posted_at,
+            message_id: msg.message_id,
+            direction,
+        })
+    };
+    let (has_next, has_prev) = if backward {
+        (true, has_more)
+    } else {
+        (has_more, cursor.is_some())
+    };
+    let next_cursor = db_messages
+        .last()
+        .filter(|_| has_next)
+        .map(|msg| cursor_at(msg, message::Direction::Next));
+    let prev_cursor = db_messages
+        .first()
+        .filter(|_| has_prev)
+        .map(|msg| cursor_at(msg, message::Direction::Prev));
+
+    let total = if params.include_total {
+        match state
+            .messages_repository
+            .count_messages(claims.tenant_id, filters.to_filter())
+            .await
+        {
+            Ok(total) => Some(total),
+            Err(err) => return Err(err.into()),
+        }
+    } else {
+        None
+    };
+
+    let link = pagination::link_header(
+        uri.path(),
+        uri.query(),
+        next_cursor.as_deref(),
+        prev_cursor.as_deref(),
+    );
+
+    let page = entities::page::PageResponse {
+        items: db_messages
+            .into_iter()
+            .map(|msg| entities::message::MessageResponse {
+                message_id: msg.message_id,
+                author: entities::user::AuthorResponse {
+                    user_id: msg.user_id,
+                    display_name: msg.display_name,
+                    avatar_url: msg.avatar_url,
+                },
+                content: msg.message_content,
+                posted_at: msg.posted_at,
+                expires_at: msg.expires_at,
+            })
+            .collect(),
+        next_cursor,
+        prev_cursor,
+        total,
+    };
+
+    let mut response = Json(page).into_response();
+    validators.apply(&mut response);
+    if let Some(link) = link {
+        let value = HeaderValue::from_str(&link).map_err(|err| ApiError::Internal(err.into()))?;
+        response.headers_mut().insert(header::LINK, value);
+    }
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, body::Body, extract::Request, http};
+    use chrono::{DateTime, Duration, Utc};
+    use http_body_util::BodyExt;
+    use mockall::predicate::*;
+    use serde_json::Value;
+    use tower::ServiceExt;
+
+    use crate::{
+        api,
+        api::{ApiRouterBuilder, State, pagination},
+        domain,
+        infra::repositories,
+    };
+
+    fn authored_messages(ids: &[i64]) -> Vec<domain::message::AuthoredMessage> {
+        let posted_at = DateTime::parse_from_rfc3339("2020-04-12T22:10:57+02:00")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        ids.iter()
+            .map(|&message_id| domain::message::AuthoredMessage {
+                message_id,
+                message_content: format!("message {message_id}"),
+                user_id: 123,
+                posted_at: posted_at - Duration::minutes(message_id),
+                expires_at: None,
+                display_name: None,
+                avatar_url: None,
+            })
+            .collect()
+    }
+
+    async fn list(state: State, uri: &str) -> (http::StatusCode, Option<String>, Value) {
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::GET)
+                    .uri(uri)
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let status = response.status();
+        let link = response
+            .headers()
+            .get(http::header::LINK)
+            .map(|link| link.to_str().unwrap().to_owned());
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+
+        (status, link, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_list_messages_v2_handler_first_page() {
+        let mut messages_repository =
+            repositories::messages::MockMessagesRepositoryTrait::default();
+
+        messages_repository
+            .expect_messages_version()
+            .once()
+            .returning(|_, _| {
+                Box::pin(async {
+                    Ok(domain::message::ListingVersion {
+                        max_message_id: Some(3),
+                        last_modified: None,
+                        visible: 3,
+                    })
+                })
+            });
+        messages_repository
+            .expect_list_messages_page()
+            .with(eq(1), always(), eq(None), eq(3))
+            .once()
+            .returning(|_, _, _, _| Box::pin(async { Ok(authored_messages(&[1, 2, 3])) }));
+        messages_repository
+            .expect_count_messages()
+            .once()
+            .returning(|_, _| Box::pin(async { Ok(7) }));
+
+        let state = State {
+            messages_repository: Arc::new(messages_repository),
+            ..State::mocked()
+        };
+
+        let (status, link, body) = list(state, "/api/v2/messages?limit=2&include_total=true").await;
+
+        assert_eq!(status, http::StatusCode::OK);
+        assert_eq!(body["items"].as_array().unwrap().len(), 2);
+        assert_eq!(body["items"][1]["message_id"], 2);
+        assert_eq!(body["prev_cursor"], Value::Null);
+        assert_eq!(body["total"], 7);
+
+        let next_cursor = body["next_cursor"].as_str().unwrap();
+        let cursor = pagination::decode_cursor(next_cursor).unwrap();
+        assert_eq!(cursor.message_id, 2);
+        assert_eq!(cursor.direction, domain::message::Direction::Next);
+
+        assert_eq!(
+            link.unwrap(),
+            format!(
+                "</api/v2/messages?limit=2&include_total=true&cursor={next_cursor}>; rel=\"next\""
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_messages_v2_handler_prev_page() {
+        let posted_at = DateTime::parse_from_rfc3339("2020-04-12T22:00:00+02:00")
+            .unwrap()
+            .with_timezone(&Utc);
+        let cursor = domain::message::Cursor {
+            posted_at,
+            message_id: 3,
+            direction: domain::message::Direction::Prev,
+        };
+
+        let mut messages_repository =
+            repositories::messages::MockMessagesRepositoryTrait::default();
+
+        messages_repository
+            .expect_messages_version()
+            .once()
+            .returning(|_, _| {
+                Box::pin(async {
+                    Ok(domain::message::ListingVersion {
+                        max_message_id: Some(3),
+                        last_modified: None,
+                        visible: 3,
+                    })
+                })
+            });
+        messages_repository
+            .expect_list_messages_page()
+            .with(eq(1), always(), eq(Some(cursor)), eq(2))
+            .once()
+            .returning(|_, _, _, _| Box::pin(async { Ok(authored_messages(&[1, 2])) }));
+
+        let state = State {
+            messages_repository: Arc::new(messages_repository),
+            ..State::mocked()
+        };
+
+        let uri = format!(
+            "/api/v2/messages?limit=1&cursor={}",
+            pagination::encode_cursor(&cursor)
+        );
+        let (status, link, body) = list(state, &uri).await;
+
+        assert_eq!(status, http::StatusCode::OK);
+        assert_eq!(body["items"].as_array().unwrap().len(), 1);
+        assert_eq!(body["items"][0]["message_id"], 2);
+        assert!(body["next_cursor"].is_string());
+        assert!(body["prev_cursor"].is_string());
+        assert!(body.get("total").is_none());
+
+        let link = link.unwrap();
+        assert!(link.contains("rel=\"next\""));
+        assert!(link.contains("rel=\"prev\""));
+    }
+
+    #[tokio::test]
+    async fn test_list_messages_v2_handler_invalid_cursor() {
+        let (status, _, body) = list(State::mocked(), "/api/v2/messages?cursor=bogus").await;
+
+        assert_eq!(status, http::StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "invalid_cursor");
+    }
+}
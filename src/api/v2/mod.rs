@@ -0,0 +1,3 @@
+pub mod list_messages;
+
+const DOCS_MESSAGES_TAG: &str = "MESSAGES";
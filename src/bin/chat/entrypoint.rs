@@ -1,7 +1,13 @@
 use std::sync::Arc;
 
 use anyhow::anyhow;
-use app::{api, infra::repositories};
+use app::{
+    api, commands,
+    infra::{
+        cache, health::PostgresCheck, memory, migrations, postgres, repositories, sqlite, storage,
+        unsupported,
+    },
+};
 use caslex::server::{Config, Server};
 use caslex_extra::storages::postgres_pool;
 
@@ -15,31 +21,195 @@ impl Entrypoint {
     }
 
     pub async fn bootstrap_server(&mut self) -> anyhow::Result<()> {
-        let pool = postgres_pool::build_pool_from_config(postgres_pool::Config::parse())
+        let storage_config = storage::StorageConfig::parse();
+        let health = api::health::Health::new(api::health::HealthConfig::parse());
+        let (state, health) = match storage_config.backend {
+            storage::StorageBackend::Postgres => self.postgres_state(health).await?,
+            storage::StorageBackend::Memory => {
+                tracing::warn!("Using memory storage, data is lost on restart");
+                (memory_state(), health)
+            }
+            storage::StorageBackend::Sqlite => sqlite_state(&storage_config, health)?,
+        };
+        let state = with_messages_cache(state, cache::MessagesCacheConfig::parse());
+        let state = Arc::new(with_tenants_cache(
+            state,
+            cache::TenantsCacheConfig::parse(),
+        ));
+        let health = Arc::new(health);
+        health.shut_down_on_signal();
+
+        let router = api::ApiRouterBuilder::new()
+            .with_state(state.clone())
+            .build()
+            .merge(api::health::router(health));
+
+        Server::new(Config::parse())
+            .router(router)
+            .run()
+            .await
+            .map_err(|err| anyhow!("handling server error: {}", err))?;
+
+        Ok(())
+    }
+
+    async fn postgres_state(
+        &mut self,
+        health: api::health::Health,
+    ) -> anyhow::Result<(api::State, api::health::Health)> {
+        let pool_config = postgres_pool::Config::parse();
+        let schema_config = postgres::SchemaConfig::parse();
+        let pool = postgres::build_pool(pool_config.clone(), &schema_config)
             .await
             .map_err(|err| anyhow!("failed to create pool: {:?}", err))?;
+        migrations::check_version(&pool).await?;
 
         self.pool = Some(pool.clone());
         caslex_extra::closer::push_callback(Box::new(move || pool.clone().close()));
 
-        let messages_repository = Arc::new(repositories::MessagesRepository::new(
+        let mut health = health.with_dependency(
+            "postgres",
+            Arc::new(PostgresCheck::new(self.pool.clone().unwrap())),
+        );
+        let mut pools = postgres::Pools::new(self.pool.clone().unwrap());
+        let replica_config = postgres::ReplicaConfig::parse();
+        if let Some(config) = replica_config.pool_config(&pool_config) {
+            let replica = postgres::create_pool(&config, &schema_config)
+                .map_err(|err| anyhow!("failed to create replica pool: {:?}", err))?;
+            tracing::info!("Reading from replica {}:{}", config.host, config.port);
+
+            let closed = replica.clone();
+            caslex_extra::closer::push_callback(Box::new(move || closed.close()));
+            // reads fall back to the primary, so a failing replica doesn't fail readiness
+            health = health.with_optional_dependency(
+                "postgres_replica",
+                Arc::new(PostgresCheck::new(replica.clone())),
+            );
+            pools = pools.with_replica(replica, &replica_config);
+        }
+
+        let messages_repository =
+            Arc::new(repositories::MessagesRepository::with_pools(pools.clone()));
+
+        let webhooks_repository = Arc::new(repositories::WebhooksRepository::new(
+            self.pool.clone().unwrap(),
+        ));
+
+        let integrations_repository = Arc::new(repositories::IntegrationsRepository::new(
             self.pool.clone().unwrap(),
         ));
 
-        let state = Arc::new(api::State {
+        let scheduled_messages_repository = Arc::new(
+            repositories::ScheduledMessagesRepository::new(self.pool.clone().unwrap()),
+        );
+
+        let pins_repository = Arc::new(repositories::PinsRepository::with_pools(pools.clone()));
+
+        let bookmarks_repository =
+            Arc::new(repositories::BookmarksRepository::with_pools(pools.clone()));
+
+        let users_repository = Arc::new(repositories::UsersRepository::with_pools(pools.clone()));
+
+        let unit_of_work = Arc::new(repositories::UnitOfWorkFactory::with_pools(pools));
+
+        let tenants_repository = Arc::new(repositories::TenantsRepository::new(
+            self.pool.clone().unwrap(),
+        ));
+
+        let access_config = api::AccessConfig::parse();
+        let state = api::State {
             messages_repository,
-        });
+            webhooks_repository,
+            integrations_repository,
+            scheduled_messages_repository,
+            pins_repository,
+            bookmarks_repository,
+            users_repository,
+            tenants_repository,
+            unit_of_work,
+            admin_user_ids: access_config.admin_user_ids,
+            moderator_user_ids: access_config.moderator_user_ids,
+            commands: commands::CommandRegistry::with_builtins(),
+        };
 
-        let router = api::ApiRouterBuilder::new()
-            .with_state(state.clone())
-            .build();
+        Ok((state, health))
+    }
+}
 
-        Server::new(Config::parse())
-            .router(router)
-            .run()
-            .await
-            .map_err(|err| anyhow!("handling server error: {}", err))?;
+fn with_messages_cache(state: api::State, config: cache::MessagesCacheConfig) -> api::State {
+    if !config.enabled {
+        return state;
+    }
 
-        Ok(())
+    let cache = Arc::new(cache::CachedMessagesRepository::new(
+        state.messages_repository,
+        config.ttl.into(),
+        config.capacity,
+    ));
+
+    api::State {
+        messages_repository: cache.clone(),
+        unit_of_work: Arc::new(cache::CachedUnitOfWorkFactory::new(
+            state.unit_of_work,
+            cache,
+        )),
+        ..state
+    }
+}
+
+fn with_tenants_cache(state: api::State, config: cache::TenantsCacheConfig) -> api::State {
+    api::State {
+        tenants_repository: Arc::new(cache::CachedTenantsRepository::new(
+            state.tenants_repository,
+            config.ttl.into(),
+        )),
+        ..state
+    }
+}
+
+fn memory_state() -> api::State {
+    let store = memory::MemoryStore::new();
+
+    let access_config = api::AccessConfig::parse();
+    api::State {
+        messages_repository: Arc::new(memory::MemoryMessagesRepository::new(store.clone())),
+        webhooks_repository: Arc::new(unsupported::Unsupported),
+        integrations_repository: Arc::new(unsupported::Unsupported),
+        scheduled_messages_repository: Arc::new(unsupported::Unsupported),
+        pins_repository: Arc::new(memory::MemoryPinsRepository::new(store.clone())),
+        bookmarks_repository: Arc::new(memory::MemoryBookmarksRepository::new(store.clone())),
+        users_repository: Arc::new(memory::MemoryUsersRepository::new(store.clone())),
+        tenants_repository: Arc::new(memory::MemoryTenantsRepository::new(store.clone())),
+        unit_of_work: Arc::new(memory::MemoryUnitOfWorkFactory::new(store)),
+        admin_user_ids: access_config.admin_user_ids,
+        moderator_user_ids: access_config.moderator_user_ids,
+        commands: commands::CommandRegistry::with_builtins(),
     }
 }
+
+fn sqlite_state(
+    config: &storage::StorageConfig,
+    health: api::health::Health,
+) -> anyhow::Result<(api::State, api::health::Health)> {
+    let store = sqlite::SqliteStore::open(&config.sqlite_path)
+        .map_err(|err| anyhow!("opening sqlite database: {}", err))?;
+    let health = health.with_dependency("sqlite", Arc::new(store.clone()));
+
+    let access_config = api::AccessConfig::parse();
+    let state = api::State {
+        messages_repository: Arc::new(sqlite::SqliteMessagesRepository::new(store.clone())),
+        webhooks_repository: Arc::new(unsupported::Unsupported),
+        integrations_repository: Arc::new(unsupported::Unsupported),
+        scheduled_messages_repository: Arc::new(unsupported::Unsupported),
+        pins_repository: Arc::new(sqlite::SqlitePinsRepository::new(store.clone())),
+        bookmarks_repository: Arc::new(sqlite::SqliteBookmarksRepository::new(store.clone())),
+        users_repository: Arc::new(sqlite::SqliteUsersRepository::new(store.clone())),
+        tenants_repository: Arc::new(sqlite::SqliteTenantsRepository::new(store.clone())),
+        unit_of_work: Arc::new(sqlite::SqliteUnitOfWorkFactory::new(store)),
+        admin_user_ids: access_config.admin_user_ids,
+        moderator_user_ids: access_config.moderator_user_ids,
+        commands: commands::CommandRegistry::with_builtins(),
+    };
+
+    Ok((state, health))
+}
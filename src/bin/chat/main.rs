@@ -1,5 +1,7 @@
 extern crate rust_simple_chat as app;
 
+use app::infra::migrations;
+
 mod entrypoint;
 
 #[tokio::main]
@@ -7,7 +9,11 @@ async fn main() {
     caslex_extra::setup_application(env!("CARGO_PKG_NAME"));
 
     let mut entry = entrypoint::Entrypoint::new();
-    let entry_result = entry.bootstrap_server().await;
+    let entry_result = if migrations::MigrateConfig::parse().migrate {
+        migrations::run().await
+    } else {
+        entry.bootstrap_server().await
+    };
 
     caslex_extra::cleanup_resources();
 
@@ -0,0 +1,61 @@
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::anyhow;
+use app::{
+    importer::{ImportConfig, Importer},
+    infra::{migrations, postgres, repositories},
+};
+use caslex_extra::storages::postgres_pool;
+
+pub struct Entrypoint {
+    config: ImportConfig,
+}
+
+impl Entrypoint {
+    pub fn new() -> Self {
+        Self {
+            config: ImportConfig::parse(),
+        }
+    }
+
+    pub async fn run(&self) -> anyhow::Result<()> {
+        let users = match &self.config.users {
+            Some(path) => Importer::load_users(path)
+                .map_err(|err| anyhow!("failed to read users mapping: {:?}", err))?,
+            None => HashMap::new(),
+        };
+
+        let records = Importer::read_records(self.config.format, &self.config.path)
+            .map_err(|err| anyhow!("failed to read export: {:?}", err))?;
+
+        let pool = postgres::build_pool(
+            postgres_pool::Config::parse(),
+            &postgres::SchemaConfig::parse(),
+        )
+        .await
+        .map_err(|err| anyhow!("failed to create pool: {:?}", err))?;
+        migrations::check_version(&pool).await?;
+
+        let messages_repository = Arc::new(repositories::MessagesRepository::new(pool.clone()));
+
+        caslex_extra::closer::push_callback(Box::new(move || pool.clone().close()));
+
+        let report = Importer::new(
+            messages_repository,
+            self.config.tenant_id,
+            users,
+            self.config.batch_size,
+        )
+        .run(records)
+        .await;
+
+        tracing::info!(
+            "import finished: imported={}, skipped={}, failed={}",
+            report.imported,
+            report.skipped,
+            report.failed
+        );
+
+        Ok(())
+    }
+}
@@ -1,7 +1,14 @@
 use std::sync::Arc;
 
 use anyhow::anyhow;
-use app::{cronjob::DummyProcess, infra::repositories};
+use app::{
+    api::health::{self, Health, HealthConfig},
+    cronjob::{
+        ExpiryConfig, ExpiryProcess, RetentionConfig, RetentionProcess, ScheduledMessagesConfig,
+        ScheduledMessagesProcess, WebhookDeliveryConfig, WebhookDeliveryProcess,
+    },
+    infra::{health::PostgresCheck, migrations, postgres, repositories},
+};
 use caslex::server::{Config, Process, Server};
 use caslex_extra::storages::postgres_pool;
 
@@ -15,9 +22,13 @@ impl Entrypoint {
     }
 
     pub async fn bootstrap_server(&mut self) -> anyhow::Result<()> {
-        let pool = postgres_pool::build_pool_from_config(postgres_pool::Config::parse())
-            .await
-            .map_err(|err| anyhow!("failed to create pool: {:?}", err))?;
+        let pool = postgres::build_pool(
+            postgres_pool::Config::parse(),
+            &postgres::SchemaConfig::parse(),
+        )
+        .await
+        .map_err(|err| anyhow!("failed to create pool: {:?}", err))?;
+        migrations::check_version(&pool).await?;
 
         self.pool = Some(pool.clone());
         caslex_extra::closer::push_callback(Box::new(move || pool.clone().close()));
@@ -26,11 +37,40 @@ impl Entrypoint {
             self.pool.clone().unwrap(),
         ));
 
+        let webhooks_repository = Arc::new(repositories::WebhooksRepository::new(
+            self.pool.clone().unwrap(),
+        ));
+
+        let scheduled_messages_repository = Arc::new(
+            repositories::ScheduledMessagesRepository::new(self.pool.clone().unwrap()),
+        );
+
         // init processes
-        let dummy_process = DummyProcess::new(1, messages_repository);
-        let processes: Vec<&'static dyn Process> = vec![dummy_process];
+        let retention_process =
+            RetentionProcess::new(1, RetentionConfig::parse(), messages_repository.clone());
+        let webhook_delivery_process =
+            WebhookDeliveryProcess::new(2, WebhookDeliveryConfig::parse(), webhooks_repository);
+        let scheduled_messages_process = ScheduledMessagesProcess::new(
+            3,
+            ScheduledMessagesConfig::parse(),
+            scheduled_messages_repository,
+        );
+        let expiry_process = ExpiryProcess::new(4, ExpiryConfig::parse(), messages_repository);
+        let processes: Vec<&'static dyn Process> = vec![
+            retention_process,
+            webhook_delivery_process,
+            scheduled_messages_process,
+            expiry_process,
+        ];
+
+        let health = Arc::new(Health::new(HealthConfig::parse()).with_dependency(
+            "postgres",
+            Arc::new(PostgresCheck::new(self.pool.clone().unwrap())),
+        ));
+        health.shut_down_on_signal();
 
         Server::new(Config::parse())
+            .router(health::router(health))
             .processes(&processes)
             .run()
             .await
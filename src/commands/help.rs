@@ -0,0 +1,41 @@
+use async_trait::async_trait;
+
+use crate::commands::{
+    COMMAND_PREFIX, CommandContext, CommandHandler, CommandOutcome, CommandRegistry,
+};
+
+/// Lists commands available in the registry.
+pub struct HelpCommand {
+    usages: Vec<&'static str>,
+}
+
+impl HelpCommand {
+    pub const NAME: &'static str = "help";
+
+    pub fn new(registry: &CommandRegistry) -> Self {
+        Self {
+            usages: registry.usages(),
+        }
+    }
+}
+
+#[async_trait]
+impl CommandHandler for HelpCommand {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn usage(&self) -> &'static str {
+        "/help - list available commands"
+    }
+
+    async fn handle(&self, _: CommandContext) -> anyhow::Result<CommandOutcome, anyhow::Error> {
+        let mut lines = vec![self.usage()];
+        lines.extend(&self.usages);
+
+        Ok(CommandOutcome::Ephemeral(format!(
+            "Available commands ({COMMAND_PREFIX}{COMMAND_PREFIX} escapes):\n{}",
+            lines.join("\n")
+        )))
+    }
+}
@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+
+use crate::commands::{CommandContext, CommandHandler, CommandOutcome};
+
+/// Posts an action in third person, e.g. `/me waves` becomes `_waves_`.
+pub struct MeCommand;
+
+#[async_trait]
+impl CommandHandler for MeCommand {
+    fn name(&self) -> &'static str {
+        "me"
+    }
+
+    fn usage(&self) -> &'static str {
+        "/me <action> - post an action"
+    }
+
+    async fn handle(&self, ctx: CommandContext) -> anyhow::Result<CommandOutcome, anyhow::Error> {
+        if ctx.args.is_empty() {
+            return Ok(CommandOutcome::Reject(format!("usage: {}", self.usage())));
+        }
+
+        Ok(CommandOutcome::Post(format!("_{}_", ctx.args)))
+    }
+}
@@ -0,0 +1,154 @@
+mod help;
+mod me;
+mod poll;
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use async_trait::async_trait;
+pub use help::HelpCommand;
+pub use me::MeCommand;
+pub use poll::PollCommand;
+
+/// Prefix of messages routed to command handlers.
+pub const COMMAND_PREFIX: char = '/';
+
+/// Invocation of a command by a user.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandContext {
+    pub user_id: i32,
+    /// Command name without prefix.
+    pub name: String,
+    /// Everything after the command name, trimmed.
+    pub args: String,
+}
+
+/// Result of command execution.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommandOutcome {
+    /// Reply shown only to the caller, nothing is stored.
+    Ephemeral(String),
+    /// Store message on behalf of the caller.
+    Post(String),
+    /// Store message on behalf of the system bot.
+    PostAsBot(String),
+    /// Refuse command with reason shown to the caller.
+    Reject(String),
+}
+
+#[async_trait]
+pub trait CommandHandler: Send + Sync {
+    /// Name the command is invoked by, e.g. `poll` for `/poll`.
+    fn name(&self) -> &'static str;
+    /// One line usage shown by `/help`.
+    fn usage(&self) -> &'static str;
+    async fn handle(&self, ctx: CommandContext) -> anyhow::Result<CommandOutcome, anyhow::Error>;
+}
+
+/// Parsed message text.
+#[derive(Debug, PartialEq)]
+pub enum Input<'a> {
+    /// Plain text, stored as is.
+    Text(&'a str),
+    Command {
+        name: &'a str,
+        args: &'a str,
+    },
+}
+
+/// Splits text into command name and arguments. Doubled prefix escapes the command, so
+/// `//path` is posted as `/path`.
+pub fn parse(text: &str) -> Input<'_> {
+    let Some(rest) = text.strip_prefix(COMMAND_PREFIX) else {
+        return Input::Text(text);
+    };
+
+    if rest.starts_with(COMMAND_PREFIX) {
+        return Input::Text(rest);
+    }
+
+    match rest.split_once(char::is_whitespace) {
+        Some((name, args)) => Input::Command {
+            name,
+            args: args.trim(),
+        },
+        None => Input::Command {
+            name: rest,
+            args: "",
+        },
+    }
+}
+
+/// Registered command handlers by name.
+#[derive(Default, Clone)]
+pub struct CommandRegistry {
+    handlers: BTreeMap<&'static str, Arc<dyn CommandHandler>>,
+}
+
+impl CommandRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry with all built-in commands.
+    pub fn with_builtins() -> Self {
+        Self::new()
+            .register(Arc::new(MeCommand))
+            .register(Arc::new(PollCommand))
+    }
+
+    pub fn register(mut self, handler: Arc<dyn CommandHandler>) -> Self {
+        self.handlers.insert(handler.name(), handler);
+        self
+    }
+
+    /// Runs command, unknown commands are rejected.
+    pub async fn dispatch(
+        &self,
+        ctx: CommandContext,
+    ) -> anyhow::Result<CommandOutcome, anyhow::Error> {
+        if ctx.name == HelpCommand::NAME {
+            return HelpCommand::new(self).handle(ctx).await;
+        }
+
+        match self.handlers.get(ctx.name.as_str()) {
+            Some(handler) => handler.handle(ctx).await,
+            None => Ok(CommandOutcome::Reject(format!(
+                "unknown command {COMMAND_PREFIX}{}, see {COMMAND_PREFIX}{}",
+                ctx.name,
+                HelpCommand::NAME
+            ))),
+        }
+    }
+
+    fn usages(&self) -> Vec<&'static str> {
+        self.handlers
+            .values()
+            .map(|handler| handler.usage())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("hello"), Input::Text("hello"));
+        assert_eq!(parse("//etc"), Input::Text("/etc"));
+        assert_eq!(
+            parse("/me  waves "),
+            Input::Command {
+                name: "me",
+                args: "waves"
+            }
+        );
+        assert_eq!(
+            parse("/help"),
+            Input::Command {
+                name: "help",
+                args: ""
+            }
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use async_trait::async_trait;
+
+use crate::commands::{CommandContext, CommandHandler, CommandOutcome};
+
+const MIN_OPTIONS: usize = 2;
+const MAX_OPTIONS: usize = 10;
+
+/// Announces a poll on behalf of the system bot.
+pub struct PollCommand;
+
+#[async_trait]
+impl CommandHandler for PollCommand {
+    fn name(&self) -> &'static str {
+        "poll"
+    }
+
+    fn usage(&self) -> &'static str {
+        "/poll <question> | <option> | <option>... - start a poll"
+    }
+
+    async fn handle(&self, ctx: CommandContext) -> anyhow::Result<CommandOutcome, anyhow::Error> {
+        let mut parts = ctx
+            .args
+            .split('|')
+            .map(str::trim)
+            .filter(|part| !part.is_empty());
+
+        let question = parts.next();
+        let options: Vec<&str> = parts.collect();
+
+        let Some(question) = question else {
+            return Ok(CommandOutcome::Reject(format!("usage: {}", self.usage())));
+        };
+        if !(MIN_OPTIONS..=MAX_OPTIONS).contains(&options.len()) {
+            return Ok(CommandOutcome::Reject(format!(
+                "poll needs from {MIN_OPTIONS} to {MAX_OPTIONS} options"
+            )));
+        }
+
+        let mut text = format!("Poll by user {}: {question}", ctx.user_id);
+        for (i, option) in options.iter().enumerate() {
+            text.push_str(&format!("\n{}. {option}", i + 1));
+        }
+
+        Ok(CommandOutcome::PostAsBot(text))
+    }
+}
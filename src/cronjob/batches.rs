@@ -0,0 +1,86 @@
+use std::future::Future;
+
+use tokio_util::sync::CancellationToken;
+
+use crate::domain::errors::DomainError;
+
+/// Parser of job `--batch-size` options, an empty batch would never be shorter than the limit.
+pub fn batch_size_parser() -> clap::builder::RangedI64ValueParser<i64> {
+    clap::value_parser!(i64).range(1..)
+}
+
+/// Runs `batch` with `batch_size` limit until a batch handles fewer rows or token cancelled,
+/// returns rows handled in total.
+pub async fn drain<F, Fut>(
+    token: &CancellationToken,
+    batch_size: i64,
+    mut batch: F,
+) -> anyhow::Result<u64>
+where
+    F: FnMut(i64) -> Fut,
+    Fut: Future<Output = Result<u64, DomainError>>,
+{
+    // an empty batch would never be shorter than the limit
+    anyhow::ensure!(
+        batch_size > 0,
+        "batch size must be positive, got {batch_size}"
+    );
+
+    let mut total = 0;
+    while !token.is_cancelled() {
+        let handled = batch(batch_size).await?;
+
+        total += handled;
+
+        if handled < batch_size as u64 {
+            break;
+        }
+    }
+
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    #[derive(Parser)]
+    struct Config {
+        #[arg(long, value_parser = batch_size_parser())]
+        batch_size: i64,
+    }
+
+    #[test]
+    fn test_batch_size_parser_rejects_empty_batches() {
+        for batch_size in ["--batch-size=0", "--batch-size=-1"] {
+            assert!(Config::try_parse_from(["worker", batch_size]).is_err());
+        }
+        let config = Config::try_parse_from(["worker", "--batch-size=1"]).unwrap();
+        assert_eq!(config.batch_size, 1);
+    }
+
+    #[tokio::test]
+    async fn test_drain_rejects_empty_batches() {
+        let result = drain(&CancellationToken::new(), 0, |_| async {
+            panic!("batch must not run")
+        })
+        .await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_drain_stops_when_cancelled() {
+        let token = CancellationToken::new();
+        let total = drain(&token, 2, |limit| {
+            token.cancel();
+            async move { Ok(limit as u64) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(total, 2);
+    }
+}
@@ -0,0 +1,148 @@
+use std::{
+    sync::{Arc, OnceLock},
+    time,
+};
+
+use async_trait::async_trait;
+use caslex::server::Process;
+use clap::Parser;
+use tokio_util::sync::CancellationToken;
+
+use crate::{cronjob::batches, infra::repositories::messages::MessagesRepositoryTrait};
+
+/// Define expired messages cleanup config.
+#[derive(Parser, Debug, Clone)]
+pub struct ExpiryConfig {
+    /// Delay between expired messages cleanups. Env variable name: `EXPIRY_INTERVAL`.
+    #[arg(long, env = "EXPIRY_INTERVAL", default_value = "1m")]
+    pub interval: humantime::Duration,
+
+    /// Maximum rows removed by a single statement. Env variable name: `EXPIRY_BATCH_SIZE`.
+    #[arg(
+        long,
+        env = "EXPIRY_BATCH_SIZE",
+        default_value = "1000",
+        value_parser = batches::batch_size_parser()
+    )]
+    pub batch_size: i64,
+}
+
+impl ExpiryConfig {
+    pub fn parse() -> ExpiryConfig {
+        ExpiryConfig::try_parse().expect("Parsing configuration failed.")
+    }
+}
+
+pub struct ExpiryProcess {
+    pub ps_num: usize,
+    pub config: ExpiryConfig,
+    pub messages_repository: Arc<dyn MessagesRepositoryTrait>,
+}
+
+impl ExpiryProcess {
+    pub fn new(
+        ps_num: usize,
+        config: ExpiryConfig,
+        messages_repository: Arc<dyn MessagesRepositoryTrait>,
+    ) -> &'static Self {
+        static INSTANCE: OnceLock<ExpiryProcess> = OnceLock::new();
+        INSTANCE.get_or_init(|| ExpiryProcess {
+            ps_num,
+            config,
+            messages_repository,
+        })
+    }
+
+    /// Deletes expired messages in batches until none left or token cancelled. Expired
+    /// messages are already hidden from readers, so the cleanup may lag behind.
+    pub async fn purge(&self, token: &CancellationToken) -> anyhow::Result<u64> {
+        batches::drain(token, self.config.batch_size, |limit| {
+            self.messages_repository.delete_expired_messages(limit)
+        })
+        .await
+    }
+}
+
+#[async_trait]
+impl Process for ExpiryProcess {
+    async fn pre_run(&self) -> anyhow::Result<()> {
+        tracing::info!("successfully pre run process #{}", self.ps_num);
+        Ok(())
+    }
+
+    async fn run(&self, token: CancellationToken) -> anyhow::Result<()> {
+        let delay: time::Duration = self.config.interval.into();
+
+        loop {
+            tokio::select! {
+                _ = token.cancelled() => {
+                    tracing::info!("process: #{} successfully stopped", self.ps_num);
+                    return Ok(());
+                }
+                _ = tokio::time::sleep(delay) => {
+                    match self.purge(&token).await {
+                        Ok(0) => {}
+                        Ok(total) => {
+                            tracing::info!(
+                                "process: #{}, {} expired messages deleted",
+                                self.ps_num,
+                                total
+                            );
+                        }
+                        Err(e) => {
+                            tracing::error!(
+                                "process: #{}, expiry job error: {:?}",
+                                self.ps_num,
+                                e
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use mockall::predicate::*;
+    use tokio_util::sync::CancellationToken;
+
+    use super::*;
+    use crate::infra::repositories;
+
+    #[tokio::test]
+    async fn test_expiry_purge_in_batches() {
+        let mut messages_repository =
+            repositories::messages::MockMessagesRepositoryTrait::default();
+
+        let mut seq = mockall::Sequence::new();
+        messages_repository
+            .expect_delete_expired_messages()
+            .with(eq(3))
+            .once()
+            .in_sequence(&mut seq)
+            .returning(|_| Box::pin(async { Ok(3) }));
+        messages_repository
+            .expect_delete_expired_messages()
+            .with(eq(3))
+            .once()
+            .in_sequence(&mut seq)
+            .returning(|_| Box::pin(async { Ok(1) }));
+
+        let process = ExpiryProcess {
+            ps_num: 1,
+            config: ExpiryConfig {
+                interval: "1m".parse().unwrap(),
+                batch_size: 3,
+            },
+            messages_repository: Arc::new(messages_repository),
+        };
+
+        let total = process.purge(&CancellationToken::new()).await.unwrap();
+
+        assert_eq!(total, 4);
+    }
+}
@@ -1,3 +1,10 @@
-pub mod dummy_job;
+pub mod batches;
+pub mod expiry_job;
+pub mod retention_job;
+pub mod scheduled_messages_job;
+pub mod webhook_delivery_job;
 
-pub use dummy_job::DummyProcess;
+pub use expiry_job::{ExpiryConfig, ExpiryProcess};
+pub use retention_job::{RetentionConfig, RetentionProcess};
+pub use scheduled_messages_job::{ScheduledMessagesConfig, ScheduledMessagesProcess};
+pub use webhook_delivery_job::{WebhookDeliveryConfig, WebhookDeliveryProcess};
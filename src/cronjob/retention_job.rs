@@ -0,0 +1,216 @@
+use std::{
+    sync::{Arc, OnceLock},
+    time,
+};
+
+use async_trait::async_trait;
+use caslex::server::Process;
+use chrono::Utc;
+use clap::Parser;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    cronjob::batches, domain::message::RetentionAction,
+    infra::repositories::messages::MessagesRepositoryTrait,
+};
+
+/// Define retention policy config.
+#[derive(Parser, Debug, Clone)]
+pub struct RetentionConfig {
+    /// Messages older than this age are removed. Env variable name: `RETENTION_MAX_AGE`.
+    #[arg(long, env = "RETENTION_MAX_AGE", default_value = "90days")]
+    pub max_age: humantime::Duration,
+
+    /// Action applied to expired messages. Env variable name: `RETENTION_ACTION`.
+    #[arg(long, env = "RETENTION_ACTION", value_enum, default_value = "delete")]
+    pub action: RetentionAction,
+
+    /// Maximum rows touched by a single statement. Env variable name: `RETENTION_BATCH_SIZE`.
+    #[arg(
+        long,
+        env = "RETENTION_BATCH_SIZE",
+        default_value = "1000",
+        value_parser = batches::batch_size_parser()
+    )]
+    pub batch_size: i64,
+
+    /// Delay between retention runs. Env variable name: `RETENTION_INTERVAL`.
+    #[arg(long, env = "RETENTION_INTERVAL", default_value = "1h")]
+    pub interval: humantime::Duration,
+
+    /// Only report how many messages would be affected. Env variable name: `RETENTION_DRY_RUN`.
+    #[arg(long, env = "RETENTION_DRY_RUN", default_value = "false")]
+    pub dry_run: bool,
+}
+
+impl RetentionConfig {
+    pub fn parse() -> RetentionConfig {
+        RetentionConfig::try_parse().expect("Parsing configuration failed.")
+    }
+}
+
+pub struct RetentionProcess {
+    pub ps_num: usize,
+    pub config: RetentionConfig,
+    pub messages_repository: Arc<dyn MessagesRepositoryTrait>,
+}
+
+impl RetentionProcess {
+    pub fn new(
+        ps_num: usize,
+        config: RetentionConfig,
+        messages_repository: Arc<dyn MessagesRepositoryTrait>,
+    ) -> &'static Self {
+        static INSTANCE: OnceLock<RetentionProcess> = OnceLock::new();
+        INSTANCE.get_or_init(|| RetentionProcess {
+            ps_num,
+            config,
+            messages_repository,
+        })
+    }
+
+    /// Applies retention policy in batches until no expired messages left or token cancelled.
+    pub async fn purge(&self, token: &CancellationToken) -> anyhow::Result<u64> {
+        let max_age = chrono::Duration::from_std(self.config.max_age.into())?;
+        let before = Utc::now() - max_age;
+
+        if self.config.dry_run {
+            let total = self
+                .messages_repository
+                .count_retention_candidates(before, self.config.action)
+                .await?;
+            return Ok(total as u64);
+        }
+
+        batches::drain(token, self.config.batch_size, |limit| {
+            self.messages_repository
+                .apply_retention(before, self.config.action, limit)
+        })
+        .await
+    }
+}
+
+#[async_trait]
+impl Process for RetentionProcess {
+    async fn pre_run(&self) -> anyhow::Result<()> {
+        tracing::info!(
+            "successfully pre run process #{}, retention: max_age={}, action={:?}, dry_run={}",
+            self.ps_num,
+            self.config.max_age,
+            self.config.action,
+            self.config.dry_run
+        );
+        Ok(())
+    }
+
+    async fn run(&self, token: CancellationToken) -> anyhow::Result<()> {
+        let delay: time::Duration = self.config.interval.into();
+
+        loop {
+            tokio::select! {
+                _ = token.cancelled() => {
+                    tracing::info!("process: #{} successfully stopped", self.ps_num);
+                    return Ok(());
+                }
+                _ = tokio::time::sleep(delay) => {
+                    match self.purge(&token).await {
+                        Ok(total) if self.config.dry_run => {
+                            tracing::info!(
+                                "process: #{}, retention dry run: {} messages would be affected",
+                                self.ps_num,
+                                total
+                            );
+                        }
+                        Ok(total) => {
+                            tracing::info!(
+                                "process: #{}, retention: {} messages affected",
+                                self.ps_num,
+                                total
+                            );
+                        }
+                        Err(e) => {
+                            tracing::error!(
+                                "process: #{}, retention job error: {:?}",
+                                self.ps_num,
+                                e
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use mockall::predicate::*;
+    use tokio_util::sync::CancellationToken;
+
+    use super::*;
+    use crate::infra::repositories;
+
+    fn config(dry_run: bool) -> RetentionConfig {
+        RetentionConfig {
+            max_age: "1day".parse().unwrap(),
+            action: RetentionAction::Delete,
+            batch_size: 2,
+            interval: "1h".parse().unwrap(),
+            dry_run,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retention_purge_in_batches() {
+        let mut messages_repository =
+            repositories::messages::MockMessagesRepositoryTrait::default();
+
+        let mut seq = mockall::Sequence::new();
+        messages_repository
+            .expect_apply_retention()
+            .with(always(), eq(RetentionAction::Delete), eq(2))
+            .times(2)
+            .in_sequence(&mut seq)
+            .returning(|_, _, _| Box::pin(async { Ok(2) }));
+        messages_repository
+            .expect_apply_retention()
+            .once()
+            .in_sequence(&mut seq)
+            .returning(|_, _, _| Box::pin(async { Ok(1) }));
+
+        let process = RetentionProcess {
+            ps_num: 1,
+            config: config(false),
+            messages_repository: Arc::new(messages_repository),
+        };
+
+        let total = process.purge(&CancellationToken::new()).await.unwrap();
+
+        assert_eq!(total, 5);
+    }
+
+    #[tokio::test]
+    async fn test_retention_purge_dry_run() {
+        let mut messages_repository =
+            repositories::messages::MockMessagesRepositoryTrait::default();
+
+        messages_repository
+            .expect_count_retention_candidates()
+            .with(always(), eq(RetentionAction::Delete))
+            .once()
+            .returning(|_, _| Box::pin(async { Ok(42) }));
+        messages_repository.expect_apply_retention().never();
+
+        let process = RetentionProcess {
+            ps_num: 1,
+            config: config(true),
+            messages_repository: Arc::new(messages_repository),
+        };
+
+        let total = process.purge(&CancellationToken::new()).await.unwrap();
+
+        assert_eq!(total, 42);
+    }
+}
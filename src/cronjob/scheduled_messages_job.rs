@@ -0,0 +1,154 @@
+use std::{
+    sync::{Arc, OnceLock},
+    time,
+};
+
+use async_trait::async_trait;
+use caslex::server::Process;
+use clap::Parser;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    cronjob::batches, infra::repositories::scheduled_messages::ScheduledMessagesRepositoryTrait,
+};
+
+/// Define scheduled messages publishing config.
+#[derive(Parser, Debug, Clone)]
+pub struct ScheduledMessagesConfig {
+    /// Delay between scheduled messages polls. Env variable name: `SCHEDULED_POLL_INTERVAL`.
+    #[arg(long, env = "SCHEDULED_POLL_INTERVAL", default_value = "5s")]
+    pub poll_interval: humantime::Duration,
+
+    /// Maximum messages published by a single transaction. Env variable name:
+    /// `SCHEDULED_BATCH_SIZE`.
+    #[arg(
+        long,
+        env = "SCHEDULED_BATCH_SIZE",
+        default_value = "100",
+        value_parser = batches::batch_size_parser()
+    )]
+    pub batch_size: i64,
+}
+
+impl ScheduledMessagesConfig {
+    pub fn parse() -> ScheduledMessagesConfig {
+        ScheduledMessagesConfig::try_parse().expect("Parsing configuration failed.")
+    }
+}
+
+pub struct ScheduledMessagesProcess {
+    pub ps_num: usize,
+    pub config: ScheduledMessagesConfig,
+    pub scheduled_messages_repository: Arc<dyn ScheduledMessagesRepositoryTrait>,
+}
+
+impl ScheduledMessagesProcess {
+    pub fn new(
+        ps_num: usize,
+        config: ScheduledMessagesConfig,
+        scheduled_messages_repository: Arc<dyn ScheduledMessagesRepositoryTrait>,
+    ) -> &'static Self {
+        static INSTANCE: OnceLock<ScheduledMessagesProcess> = OnceLock::new();
+        INSTANCE.get_or_init(|| ScheduledMessagesProcess {
+            ps_num,
+            config,
+            scheduled_messages_repository,
+        })
+    }
+
+    /// Publishes due messages in batches until none left or token cancelled.
+    pub async fn publish_due(&self, token: &CancellationToken) -> anyhow::Result<u64> {
+        batches::drain(token, self.config.batch_size, |limit| {
+            self.scheduled_messages_repository
+                .publish_due_messages(limit)
+        })
+        .await
+    }
+}
+
+#[async_trait]
+impl Process for ScheduledMessagesProcess {
+    async fn pre_run(&self) -> anyhow::Result<()> {
+        tracing::info!("successfully pre run process #{}", self.ps_num);
+        Ok(())
+    }
+
+    async fn run(&self, token: CancellationToken) -> anyhow::Result<()> {
+        let delay: time::Duration = self.config.poll_interval.into();
+
+        loop {
+            tokio::select! {
+                _ = token.cancelled() => {
+                    tracing::info!("process: #{} successfully stopped", self.ps_num);
+                    return Ok(());
+                }
+                _ = tokio::time::sleep(delay) => {
+                    match self.publish_due(&token).await {
+                        Ok(0) => {}
+                        Ok(total) => {
+                            tracing::info!(
+                                "process: #{}, {} scheduled messages published",
+                                self.ps_num,
+                                total
+                            );
+                        }
+                        Err(e) => {
+                            tracing::error!(
+                                "process: #{}, scheduled messages job error: {:?}",
+                                self.ps_num,
+                                e
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use mockall::predicate::*;
+    use tokio_util::sync::CancellationToken;
+
+    use super::*;
+    use crate::infra::repositories;
+
+    #[tokio::test]
+    async fn test_scheduled_messages_publish_in_batches() {
+        let mut scheduled_messages_repository =
+            repositories::scheduled_messages::MockScheduledMessagesRepositoryTrait::default();
+
+        let mut seq = mockall::Sequence::new();
+        scheduled_messages_repository
+            .expect_publish_due_messages()
+            .with(eq(2))
+            .once()
+            .in_sequence(&mut seq)
+            .returning(|_| Box::pin(async { Ok(2) }));
+        scheduled_messages_repository
+            .expect_publish_due_messages()
+            .with(eq(2))
+            .once()
+            .in_sequence(&mut seq)
+            .returning(|_| Box::pin(async { Ok(0) }));
+
+        let process = ScheduledMessagesProcess {
+            ps_num: 1,
+            config: ScheduledMessagesConfig {
+                poll_interval: "1s".parse().unwrap(),
+                batch_size: 2,
+            },
+            scheduled_messages_repository: Arc::new(scheduled_messages_repository),
+        };
+
+        let total = process
+            .publish_due(&CancellationToken::new())
+            .await
+            .unwrap();
+
+        assert_eq!(total, 2);
+    }
+}
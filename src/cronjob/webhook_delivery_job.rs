@@ -0,0 +1,355 @@
+use std::{
+    sync::{Arc, OnceLock},
+    time,
+};
+
+use async_trait::async_trait;
+use caslex::server::Process;
+use chrono::Utc;
+use clap::Parser;
+use futures_util::future;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    cronjob::batches, domain::webhook, infra::repositories::webhooks::WebhooksRepositoryTrait,
+};
+
+/// Define webhook delivery config.
+#[derive(Parser, Debug, Clone)]
+pub struct WebhookDeliveryConfig {
+    /// Delay between outbox polls. Env variable name: `WEBHOOK_POLL_INTERVAL`.
+    #[arg(long, env = "WEBHOOK_POLL_INTERVAL", default_value = "5s")]
+    pub poll_interval: humantime::Duration,
+
+    /// Maximum deliveries sent per poll. Env variable name: `WEBHOOK_BATCH_SIZE`.
+    #[arg(
+        long,
+        env = "WEBHOOK_BATCH_SIZE",
+        default_value = "50",
+        value_parser = batches::batch_size_parser()
+    )]
+    pub batch_size: i64,
+
+    /// Attempts before delivery is moved to dead letters. Env variable name:
+    /// `WEBHOOK_MAX_ATTEMPTS`.
+    #[arg(
+        long,
+        env = "WEBHOOK_MAX_ATTEMPTS",
+        default_value = "8",
+        value_parser = clap::value_parser!(i32).range(1..)
+    )]
+    pub max_attempts: i32,
+
+    /// Delay before the first retry, doubled on each next one. Env variable name:
+    /// `WEBHOOK_BACKOFF_BASE`.
+    #[arg(long, env = "WEBHOOK_BACKOFF_BASE", default_value = "10s")]
+    pub backoff_base: humantime::Duration,
+
+    /// Upper bound of retry delay. Env variable name: `WEBHOOK_BACKOFF_MAX`.
+    #[arg(long, env = "WEBHOOK_BACKOFF_MAX", default_value = "1h")]
+    pub backoff_max: humantime::Duration,
+
+    /// Webhook request timeout. Env variable name: `WEBHOOK_REQUEST_TIMEOUT`.
+    #[arg(long, env = "WEBHOOK_REQUEST_TIMEOUT", default_value = "10s")]
+    pub request_timeout: humantime::Duration,
+}
+
+impl WebhookDeliveryConfig {
+    pub fn parse() -> WebhookDeliveryConfig {
+        WebhookDeliveryConfig::try_parse().expect("Parsing configuration failed.")
+    }
+}
+
+pub struct WebhookDeliveryProcess {
+    pub ps_num: usize,
+    pub config: WebhookDeliveryConfig,
+    pub webhooks_repository: Arc<dyn WebhooksRepositoryTrait>,
+    pub client: reqwest::Client,
+}
+
+impl WebhookDeliveryProcess {
+    pub fn new(
+        ps_num: usize,
+        config: WebhookDeliveryConfig,
+        webhooks_repository: Arc<dyn WebhooksRepositoryTrait>,
+    ) -> &'static Self {
+        static INSTANCE: OnceLock<WebhookDeliveryProcess> = OnceLock::new();
+        INSTANCE.get_or_init(|| WebhookDeliveryProcess {
+            ps_num,
+            client: build_client(&config),
+            config,
+            webhooks_repository,
+        })
+    }
+
+    /// Sends all due deliveries, returns number of processed ones.
+    pub async fn deliver_due(&self) -> anyhow::Result<usize> {
+        let request_timeout: time::Duration = self.config.request_timeout.into();
+
+        let deliveries = self
+            .webhooks_repository
+            .claim_deliveries(self.config.batch_size, request_timeout * 3)
+            .await?;
+
+        future::join_all(deliveries.iter().map(|delivery| self.process(delivery))).await;
+
+        Ok(deliveries.len())
+    }
+
+    async fn process(&self, delivery: &webhook::Delivery) {
+        let result = match self.send(delivery).await {
+            Ok(_) => {
+                self.webhooks_repository
+                    .complete_delivery(delivery.delivery_id)
+                    .await
+            }
+            Err(e) => {
+                let retry_at = if delivery.attempts >= self.config.max_attempts {
+                    tracing::warn!(
+                        "process: #{}, webhook delivery #{} moved to dead letters: {:?}",
+                        self.ps_num,
+                        delivery.delivery_id,
+                        e
+                    );
+                    None
+                } else {
+                    Some(Utc::now() + self.backoff(delivery.attempts))
+                };
+
+                self.webhooks_repository
+                    .fail_delivery(delivery.delivery_id, e.to_string(), retry_at)
+                    .await
+            }
+        };
+
+        if let Err(e) = result {
+            tracing::error!(
+                "process: #{}, failed to save webhook delivery #{} state: {:?}",
+                self.ps_num,
+                delivery.delivery_id,
+                e
+            );
+        }
+    }
+
+    async fn send(&self, delivery: &webhook::Delivery) -> anyhow::Result<()> {
+        let body = serde_json::to_vec(&delivery.payload)?;
+        let timestamp = Utc::now().timestamp();
+
+        self.client
+            .post(&delivery.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(webhook::TIMESTAMP_HEADER, timestamp.to_string())
+            .header(
+                webhook::SIGNATURE_HEADER,
+                webhook::sign(&delivery.secret, timestamp, &body),
+            )
+            .header(webhook::EVENT_HEADER, &delivery.event_type)
+            .header(webhook::DELIVERY_HEADER, delivery.delivery_id.to_string())
+            .body(body)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+
+    fn backoff(&self, attempts: i32) -> chrono::Duration {
+        let base: time::Duration = self.config.backoff_base.into();
+        let max: time::Duration = self.config.backoff_max.into();
+
+        let exponent = attempts.saturating_sub(1).clamp(0, 31) as u32;
+        let delay = base.saturating_mul(2u32.saturating_pow(exponent)).min(max);
+
+        chrono::Duration::from_std(delay).unwrap_or(chrono::Duration::MAX)
+    }
+}
+
+fn build_client(config: &WebhookDeliveryConfig) -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(config.request_timeout.into())
+        .build()
+        .expect("Building webhook http client failed.")
+}
+
+#[async_trait]
+impl Process for WebhookDeliveryProcess {
+    async fn pre_run(&self) -> anyhow::Result<()> {
+        tracing::info!("successfully pre run process #{}", self.ps_num);
+        Ok(())
+    }
+
+    async fn run(&self, token: CancellationToken) -> anyhow::Result<()> {
+        let delay: time::Duration = self.config.poll_interval.into();
+
+        loop {
+            tokio::select! {
+                _ = token.cancelled() => {
+                    tracing::info!("process: #{} successfully stopped", self.ps_num);
+                    return Ok(());
+                }
+                _ = tokio::time::sleep(delay) => {
+                    if let Err(e) = self.deliver_due().await {
+                        tracing::error!(
+                            "process: #{}, webhook delivery job error: {:?}",
+                            self.ps_num,
+                            e
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, http::HeaderMap, routing::post};
+    use mockall::predicate::*;
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::infra::repositories;
+
+    fn config() -> WebhookDeliveryConfig {
+        WebhookDeliveryConfig {
+            poll_interval: "1s".parse().unwrap(),
+            batch_size: 10,
+            max_attempts: 3,
+            backoff_base: "10s".parse().unwrap(),
+            backoff_max: "1h".parse().unwrap(),
+            request_timeout: "5s".parse().unwrap(),
+        }
+    }
+
+    fn delivery(url: String, attempts: i32) -> webhook::Delivery {
+        webhook::Delivery {
+            delivery_id: 1,
+            webhook_id: 2,
+            url,
+            secret: "secret".to_owned(),
+            event_type: "message.created".to_owned(),
+            payload: serde_json::json!({"event": "message.created"}),
+            attempts,
+        }
+    }
+
+    /// Starts local webhook receiver responding with `status`.
+    async fn start_stub(
+        status: axum::http::StatusCode,
+    ) -> (String, mpsc::UnboundedReceiver<(HeaderMap, Vec<u8>)>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let app = Router::new().route(
+            "/hook",
+            post(
+                move |headers: HeaderMap, body: axum::body::Bytes| async move {
+                    tx.send((headers, body.to_vec())).unwrap();
+                    status
+                },
+            ),
+        );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        (format!("http://{addr}/hook"), rx)
+    }
+
+    #[test]
+    fn test_webhook_delivery_config_rejects_no_attempts() {
+        // every delivery is attempted at least once, fewer attempts are a misconfiguration
+        for max_attempts in ["--max-attempts=0", "--max-attempts=-1"] {
+            assert!(WebhookDeliveryConfig::try_parse_from(["worker", max_attempts]).is_err());
+        }
+        let config = WebhookDeliveryConfig::try_parse_from(["worker", "--max-attempts=1"]).unwrap();
+        assert_eq!(config.max_attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_delivery_signed() {
+        let (url, mut requests) = start_stub(axum::http::StatusCode::NO_CONTENT).await;
+
+        let mut webhooks_repository =
+            repositories::webhooks::MockWebhooksRepositoryTrait::default();
+        webhooks_repository
+            .expect_claim_deliveries()
+            .with(eq(10), always())
+            .once()
+            .returning(move |_, _| {
+                let deliveries = vec![delivery(url.clone(), 1)];
+                Box::pin(async move { Ok(deliveries) })
+            });
+        webhooks_repository
+            .expect_complete_delivery()
+            .with(eq(1))
+            .once()
+            .returning(|_| Box::pin(async { Ok(()) }));
+
+        let process = WebhookDeliveryProcess {
+            ps_num: 1,
+            client: build_client(&config()),
+            config: config(),
+            webhooks_repository: Arc::new(webhooks_repository),
+        };
+
+        assert_eq!(process.deliver_due().await.unwrap(), 1);
+
+        let (headers, body) = requests.recv().await.unwrap();
+        assert_eq!(headers[webhook::EVENT_HEADER], "message.created");
+        assert_eq!(headers[webhook::DELIVERY_HEADER], "1");
+        let timestamp: i64 = headers[webhook::TIMESTAMP_HEADER]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((Utc::now().timestamp() - timestamp).abs() < 60);
+        assert_eq!(
+            headers[webhook::SIGNATURE_HEADER],
+            webhook::sign("secret", timestamp, &body).as_str()
+        );
+        // the signature doesn't fit the body at another time
+        assert_ne!(
+            headers[webhook::SIGNATURE_HEADER],
+            webhook::sign("secret", timestamp - 600, &body).as_str()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_webhook_delivery_retry_and_dead_letter() {
+        let (url, _requests) = start_stub(axum::http::StatusCode::INTERNAL_SERVER_ERROR).await;
+
+        let mut webhooks_repository =
+            repositories::webhooks::MockWebhooksRepositoryTrait::default();
+        let mut seq = mockall::Sequence::new();
+        for attempts in [1, 3] {
+            let url = url.clone();
+            webhooks_repository
+                .expect_claim_deliveries()
+                .once()
+                .in_sequence(&mut seq)
+                .returning(move |_, _| {
+                    let deliveries = vec![delivery(url.clone(), attempts)];
+                    Box::pin(async move { Ok(deliveries) })
+                });
+            webhooks_repository
+                .expect_fail_delivery()
+                .withf(move |id, _, retry_at| *id == 1 && retry_at.is_some() == (attempts < 3))
+                .once()
+                .in_sequence(&mut seq)
+                .returning(|_, _, _| Box::pin(async { Ok(()) }));
+        }
+
+        let process = WebhookDeliveryProcess {
+            ps_num: 1,
+            client: build_client(&config()),
+            config: config(),
+            webhooks_repository: Arc::new(webhooks_repository),
+        };
+
+        process.deliver_due().await.unwrap();
+        process.deliver_due().await.unwrap();
+    }
+}
@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+use tokio_postgres_utils::FromRow;
+
+/// Message pinned by a moderator for the whole room.
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct Pin {
+    pub message_id: i64,
+    pub message_content: String,
+    pub user_id: i32,
+    pub posted_at: DateTime<Utc>,
+    pub pinned_by: i32,
+    pub pinned_at: DateTime<Utc>,
+}
+
+/// Message privately saved by a user.
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct Bookmark {
+    pub message_id: i64,
+    pub message_content: String,
+    pub user_id: i32,
+    pub posted_at: DateTime<Utc>,
+    pub bookmarked_at: DateTime<Utc>,
+}
@@ -0,0 +1,20 @@
+use sha2::{Digest, Sha256};
+
+/// Generates random 256-bit token encoded as hex.
+pub fn random_token() -> String {
+    to_hex(&rand::random::<[u8; 32]>())
+}
+
+/// Returns hex encoded SHA-256 digest of the token, used to store tokens at rest.
+pub fn hash_token(token: &str) -> String {
+    to_hex(&Sha256::digest(token.as_bytes()))
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Generates random 128-bit id in the W3C trace id format.
+pub fn random_trace_id() -> String {
+    to_hex(&rand::random::<[u8; 16]>())
+}
@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+/// Failure of a domain operation, shared by repositories and services so callers can tell
+/// a missing row from a dead database.
+#[derive(Debug, Error)]
+pub enum DomainError {
+    /// Requested entity does not exist or is not visible to the caller.
+    #[error("{0} not found")]
+    NotFound(&'static str),
+    /// Operation clashes with current state, e.g. unique constraint or serialization failure.
+    #[error("conflict: {0}")]
+    Conflict(String),
+    /// Caller is not allowed to touch the entity.
+    #[error("forbidden")]
+    Forbidden,
+    /// Storage can not be reached, the operation may succeed later.
+    #[error("storage unavailable: {0}")]
+    Unavailable(String),
+    #[error(transparent)]
+    Internal(#[from] anyhow::Error),
+}
@@ -0,0 +1,27 @@
+use chrono::{DateTime, Utc};
+use tokio_postgres_utils::FromRow;
+
+#[derive(Debug, PartialEq)]
+pub struct NewIntegration {
+    pub tenant_id: i32,
+    pub name: String,
+    pub token_hash: String,
+    pub created_by: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct Integration {
+    pub integration_id: i64,
+    pub tenant_id: i32,
+    pub name: String,
+    pub created_by: i32,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl Integration {
+    /// Bots post with negative user ids, so they never collide with human accounts.
+    pub fn bot_user_id(&self) -> i32 {
+        -(self.integration_id as i32)
+    }
+}
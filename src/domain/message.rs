@@ -7,6 +7,15 @@ pub struct PostMessage {
     pub content: String,
     pub user_id: i32,
     pub posted_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+pub struct ImportMessage {
+    pub external_id: String,
+    pub content: String,
+    pub user_id: i32,
+    pub posted_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Deserialize, Serialize, FromRow)]
@@ -15,4 +24,87 @@ pub struct Message {
     pub message_content: String,
     pub user_id: i32,
     pub posted_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Message joined with author profile. Bots are named after their integration.
+#[derive(Debug, Clone, Deserialize, Serialize, FromRow)]
+pub struct AuthoredMessage {
+    pub message_id: i64,
+    pub message_content: String,
+    pub user_id: i32,
+    pub posted_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+}
+
+/// Criteria of listed messages, every set field narrows the result.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MessageFilter {
+    pub user_id: Option<i32>,
+    /// Messages posted at or after this time.
+    pub since: Option<DateTime<Utc>>,
+    /// Messages posted before this time.
+    pub until: Option<DateTime<Utc>>,
+    pub text: Option<TextMatch>,
+    pub order: SortOrder,
+}
+
+/// Case-insensitive match of message content.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextMatch {
+    Contains(String),
+    Prefix(String),
+}
+
+/// Order of messages by posting time.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SortOrder {
+    Asc,
+    /// Latest messages first.
+    #[default]
+    Desc,
+}
+
+/// Cheap fingerprint of messages matching a filter, changes whenever a message is posted,
+/// edited, deleted or expires, or its author changes profile.
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct ListingVersion {
+    pub max_message_id: Option<i64>,
+    /// Latest post, edit or delete time, or profile change of an author.
+    pub last_modified: Option<DateTime<Utc>>,
+    pub visible: i64,
+}
+
+/// Position in a message listing, pages continue strictly after or before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor {
+    pub posted_at: DateTime<Utc>,
+    pub message_id: i64,
+    pub direction: Direction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Messages following the cursor in listing order.
+    Next,
+    /// Messages preceding the cursor in listing order.
+    Prev,
 }
+
+/// Action applied to messages that fall out of the retention window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum RetentionAction {
+    /// Remove messages from storage.
+    Delete,
+    /// Keep message content but detach it from its author.
+    Anonymize,
+}
+
+/// User id assigned to anonymized messages.
+pub const ANONYMOUS_USER_ID: i32 = 0;
+
+/// User id of the system bot posting command results. Integration bots use negated
+/// integration ids, so it never collides with them.
+pub const SYSTEM_BOT_USER_ID: i32 = i32::MIN;
@@ -1 +1,9 @@
+pub mod bookmark;
+pub mod crypto;
+pub mod errors;
+pub mod integration;
 pub mod message;
+pub mod scheduled;
+pub mod tenant;
+pub mod user;
+pub mod webhook;
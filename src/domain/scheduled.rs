@@ -0,0 +1,22 @@
+use chrono::{DateTime, Utc};
+use tokio_postgres_utils::FromRow;
+
+#[derive(Debug, PartialEq)]
+pub struct ScheduleMessage {
+    pub tenant_id: i32,
+    pub content: String,
+    pub user_id: i32,
+    pub send_at: DateTime<Utc>,
+    /// Message lifetime in seconds counted from `send_at`.
+    pub expires_in: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct ScheduledMessage {
+    pub scheduled_id: i64,
+    pub message_content: String,
+    pub user_id: i32,
+    pub send_at: DateTime<Utc>,
+    pub expires_in: Option<i64>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,60 @@
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use tokio_postgres_utils::FromRow;
+
+/// Tenant owning data written before workspaces existed, and the one issued by login.
+pub const DEFAULT_TENANT_ID: i32 = 1;
+
+/// Workspace of a customer organisation, its data is never visible to other tenants.
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct Tenant {
+    pub tenant_id: i32,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub suspended_at: Option<DateTime<Utc>>,
+}
+
+impl Tenant {
+    pub fn is_active(&self) -> bool {
+        self.suspended_at.is_none()
+    }
+}
+
+/// User of a tenant, user ids are unique within their tenant only.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TenantUser {
+    pub tenant_id: i32,
+    pub user_id: i32,
+}
+
+impl TenantUser {
+    pub fn new(tenant_id: i32, user_id: i32) -> Self {
+        Self { tenant_id, user_id }
+    }
+}
+
+impl FromStr for TenantUser {
+    type Err = anyhow::Error;
+
+    /// Parses `tenant_id:user_id`, a bare user id is a user of the default tenant.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (tenant_id, user_id) = match s.split_once(':') {
+            Some((tenant_id, user_id)) => (
+                tenant_id
+                    .trim()
+                    .parse()
+                    .map_err(|_| anyhow!("invalid tenant id: {s}"))?,
+                user_id,
+            ),
+            None => (DEFAULT_TENANT_ID, s),
+        };
+        let user_id = user_id
+            .trim()
+            .parse()
+            .map_err(|_| anyhow!("invalid user id: {s}"))?;
+
+        Ok(Self::new(tenant_id, user_id))
+    }
+}
@@ -0,0 +1,29 @@
+use tokio_postgres_utils::FromRow;
+
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct UserProfile {
+    pub user_id: i32,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub bio: Option<String>,
+}
+
+impl UserProfile {
+    /// Profile of a user who has never filled it in.
+    pub fn empty(user_id: i32) -> Self {
+        Self {
+            user_id,
+            display_name: None,
+            avatar_url: None,
+            bio: None,
+        }
+    }
+}
+
+/// Profile fields to change, `None` keeps current value and `Some(None)` clears it.
+#[derive(Debug, Default, PartialEq)]
+pub struct ProfileChange {
+    pub display_name: Option<Option<String>>,
+    pub avatar_url: Option<Option<String>>,
+    pub bio: Option<Option<String>>,
+}
@@ -0,0 +1,22 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PinResponse {
+    pub message_id: i64,
+    pub content: String,
+    pub user_id: i32,
+    pub posted_at: DateTime<Utc>,
+    pub pinned_by: i32,
+    pub pinned_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BookmarkResponse {
+    pub message_id: i64,
+    pub content: String,
+    pub user_id: i32,
+    pub posted_at: DateTime<Utc>,
+    pub bookmarked_at: DateTime<Utc>,
+}
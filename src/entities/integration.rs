@@ -0,0 +1,29 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct CreateIntegrationRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct IntegrationTokenResponse {
+    pub integration_id: i64,
+    /// Secret token, returned only once.
+    pub token: String,
+    /// Path accepting incoming messages.
+    pub hook_path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct IntegrationResponse {
+    pub integration_id: i64,
+    pub name: String,
+    pub bot_user_id: i32,
+    pub created_by: i32,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
@@ -3,20 +3,70 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use validator::Validate;
 
+use crate::entities::user::AuthorResponse;
+
 #[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
 pub struct PostMessageRequest {
     #[validate(length(min = 1, max = 300))]
     pub text: String,
+    /// Publish message at this time instead of now.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub send_at: Option<DateTime<Utc>>,
+    /// Seconds after publishing when message disappears, up to 30 days.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1, max = 2_592_000))]
+    pub expires_in: Option<i64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct PostMessageResponse {
-    pub(crate) message_id: i64,
+    /// Id of stored message, absent when command replied ephemerally.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) message_id: Option<i64>,
+    /// Command reply visible only to the caller.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) ephemeral: Option<String>,
+    /// Id of scheduled message, present when message was posted with future `send_at`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) scheduled_id: Option<i64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct MessageResponse {
     pub message_id: i64,
+    pub author: AuthorResponse,
     pub content: String,
     pub posted_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Maximum number of ids accepted by a single batch get.
+pub const MAX_BATCH_GET_IDS: u64 = 100;
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct BatchGetMessagesRequest {
+    #[validate(length(min = 1, max = MAX_BATCH_GET_IDS))]
+    pub message_ids: Vec<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BatchGetMessagesResponse {
+    /// Found messages in order of requested ids.
+    pub messages: Vec<MessageResponse>,
+    /// Requested ids of missing, deleted or expired messages.
+    pub not_found: Vec<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ExportedMessageResponse {
+    pub message_id: i64,
+    pub user_id: i32,
+    pub content: String,
+    pub posted_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct EditMessageRequest {
+    #[validate(length(min = 1, max = 300))]
+    pub text: String,
 }
@@ -1,2 +1,11 @@
 pub mod auth;
+pub mod bookmark;
+pub mod health;
+pub mod integration;
 pub mod message;
+pub mod page;
+pub mod problem;
+pub mod scheduled;
+pub mod tenant;
+pub mod user;
+pub mod webhook;
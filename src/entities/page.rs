@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Page of a cursor paginated listing.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PageResponse<T> {
+    pub items: Vec<T>,
+    /// Cursor of the following page, absent on the last page.
+    pub next_cursor: Option<String>,
+    /// Cursor of the preceding page, absent on the first page.
+    pub prev_cursor: Option<String>,
+    /// Number of matching items, present when requested with `include_total`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<i64>,
+}
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Media type of error responses.
+pub const PROBLEM_CONTENT_TYPE: &str = "application/problem+json";
+
+/// Error response as defined by RFC 9457.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ProblemResponse {
+    /// Problem type URI, `about:blank` means the HTTP status says it all.
+    #[serde(rename = "type")]
+    pub type_: String,
+    /// HTTP status reason phrase.
+    pub title: String,
+    pub status: u16,
+    /// Human-readable explanation of this occurrence.
+    pub detail: String,
+    /// Machine-readable error code, e.g. `message_not_found`.
+    pub code: String,
+    /// Trace id to look the request up in logs and traces.
+    pub trace_id: String,
+    /// Field errors of a rejected request body.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub errors: Option<serde_json::Value>,
+}
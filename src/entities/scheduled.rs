@@ -0,0 +1,31 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ScheduledMessageResponse {
+    pub scheduled_id: i64,
+    pub content: String,
+    pub send_at: DateTime<Utc>,
+    pub expires_in: Option<i64>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct EditScheduledMessageRequest {
+    /// New text, stored as is without running commands.
+    #[validate(length(min = 1, max = 300))]
+    pub text: Option<String>,
+    /// New send time, must be in the future.
+    #[validate(custom(function = "validate_send_at"))]
+    pub send_at: Option<DateTime<Utc>>,
+}
+
+fn validate_send_at(send_at: &DateTime<Utc>) -> Result<(), ValidationError> {
+    if *send_at <= Utc::now() {
+        return Err(ValidationError::new("send_at_not_in_future")
+            .with_message("send_at must be in the future".into()));
+    }
+    Ok(())
+}
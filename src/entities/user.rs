@@ -0,0 +1,45 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UserProfileResponse {
+    pub user_id: i32,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub bio: Option<String>,
+}
+
+/// Omitted fields keep their current values, `null` clears them.
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct UpdateProfileRequest {
+    #[serde(default, deserialize_with = "present")]
+    #[schema(value_type = Option<String>)]
+    #[validate(length(min = 1, max = 64))]
+    pub display_name: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present")]
+    #[schema(value_type = Option<String>)]
+    #[validate(url, length(max = 2048))]
+    pub avatar_url: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present")]
+    #[schema(value_type = Option<String>)]
+    #[validate(length(max = 500))]
+    pub bio: Option<Option<String>>,
+}
+
+/// Tells `null` apart from a missing field, which `default` leaves as `None`.
+fn present<'de, D, T>(de: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::deserialize(de).map(Some)
+}
+
+/// Short author profile embedded into messages.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuthorResponse {
+    pub user_id: i32,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+}
@@ -0,0 +1,30 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::domain::webhook::EventType;
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct CreateWebhookRequest {
+    #[validate(url, length(max = 2048))]
+    pub url: String,
+    #[validate(length(min = 1))]
+    pub events: Vec<EventType>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CreateWebhookResponse {
+    pub webhook_id: i64,
+    /// HMAC-SHA256 signing secret, returned only once.
+    pub secret: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WebhookResponse {
+    pub webhook_id: i64,
+    pub url: String,
+    pub events: Vec<EventType>,
+    pub created_by: i32,
+    pub created_at: DateTime<Utc>,
+}
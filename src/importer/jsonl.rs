@@ -0,0 +1,42 @@
+//! JSONL export reader.
+//!
+//! Each line holds one message: `{"id": "42", "user": "alice", "text": "hi", "posted_at":
+//! "2020-04-12T20:10:57Z"}`.
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use super::{RawMessage, Records};
+
+#[derive(Debug, Deserialize)]
+struct JsonlMessage {
+    id: String,
+    user: String,
+    text: String,
+    posted_at: DateTime<Utc>,
+}
+
+pub fn read_export(path: &Path) -> anyhow::Result<Records> {
+    let reader = BufReader::new(File::open(path)?);
+
+    Ok(Box::new(
+        reader
+            .lines()
+            .filter(|line| !line.as_ref().is_ok_and(|line| line.trim().is_empty()))
+            .map(|line| {
+                let msg: JsonlMessage = serde_json::from_str(&line?)?;
+                Ok(Some(RawMessage {
+                    external_id: format!("jsonl:{}", msg.id),
+                    user: msg.user,
+                    text: msg.text,
+                    posted_at: msg.posted_at,
+                }))
+            }),
+    ))
+}
@@ -0,0 +1,243 @@
+pub mod jsonl;
+pub mod slack;
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use chrono::{DateTime, Utc};
+use clap::Parser;
+use validator::Validate;
+
+use crate::{domain::message, entities, infra::repositories::messages::MessagesRepositoryTrait};
+
+/// Define import config.
+#[derive(Parser, Debug, Clone)]
+pub struct ImportConfig {
+    /// Export format. Env variable name: `IMPORT_FORMAT`.
+    #[arg(long, env = "IMPORT_FORMAT", value_enum)]
+    pub format: ImportFormat,
+
+    /// Path to Slack export directory/file or JSONL file. Env variable name: `IMPORT_PATH`.
+    #[arg(long, env = "IMPORT_PATH")]
+    pub path: PathBuf,
+
+    /// Path to JSON object mapping source user names to user ids. Env variable name:
+    /// `IMPORT_USERS`.
+    #[arg(long, env = "IMPORT_USERS")]
+    pub users: Option<PathBuf>,
+
+    /// Messages inserted per statement. Env variable name: `IMPORT_BATCH_SIZE`.
+    #[arg(long, env = "IMPORT_BATCH_SIZE", default_value = "500")]
+    pub batch_size: usize,
+
+    /// Tenant receiving imported messages. Env variable name: `IMPORT_TENANT_ID`.
+    #[arg(long, env = "IMPORT_TENANT_ID", default_value = "1")]
+    pub tenant_id: i32,
+}
+
+impl ImportConfig {
+    pub fn parse() -> ImportConfig {
+        ImportConfig::try_parse().expect("Parsing configuration failed.")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ImportFormat {
+    Slack,
+    Jsonl,
+}
+
+/// Message read from an export before user mapping.
+#[derive(Debug, PartialEq)]
+pub struct RawMessage {
+    pub external_id: String,
+    pub user: String,
+    pub text: String,
+    pub posted_at: DateTime<Utc>,
+}
+
+/// Export records. `Ok(None)` stands for an entry that is not a chat message.
+pub type Records = Box<dyn Iterator<Item = anyhow::Result<Option<RawMessage>>>>;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct ImportReport {
+    pub imported: u64,
+    pub skipped: u64,
+    pub failed: u64,
+}
+
+pub struct Importer {
+    messages_repository: Arc<dyn MessagesRepositoryTrait>,
+    tenant_id: i32,
+    users: HashMap<String, i32>,
+    batch_size: usize,
+}
+
+impl Importer {
+    pub fn new(
+        messages_repository: Arc<dyn MessagesRepositoryTrait>,
+        tenant_id: i32,
+        users: HashMap<String, i32>,
+        batch_size: usize,
+    ) -> Self {
+        Self {
+            messages_repository,
+            tenant_id,
+            users,
+            batch_size: batch_size.max(1),
+        }
+    }
+
+    /// Reads user mapping from JSON object like `{"U012AB3CD": 123}`.
+    pub fn load_users(path: &Path) -> anyhow::Result<HashMap<String, i32>> {
+        let users = serde_json::from_slice(&fs::read(path)?)?;
+        Ok(users)
+    }
+
+    pub fn read_records(format: ImportFormat, path: &Path) -> anyhow::Result<Records> {
+        match format {
+            ImportFormat::Slack => slack::read_export(path),
+            ImportFormat::Jsonl => jsonl::read_export(path),
+        }
+    }
+
+    /// Imports records in batches. Already imported messages are counted as skipped.
+    pub async fn run(
+        &self,
+        records: impl Iterator<Item = anyhow::Result<Option<RawMessage>>>,
+    ) -> ImportReport {
+        let mut report = ImportReport::default();
+        let mut batch = Vec::with_capacity(self.batch_size);
+
+        for record in records {
+            match record.and_then(|record| record.map(|raw| self.map_message(raw)).transpose()) {
+                Ok(Some(msg)) => batch.push(msg),
+                Ok(None) => report.skipped += 1,
+                Err(e) => {
+                    tracing::warn!("import: skip invalid record: {:?}", e);
+                    report.failed += 1;
+                }
+            }
+
+            if batch.len() >= self.batch_size {
+                self.flush(&mut batch, &mut report).await;
+            }
+        }
+
+        if !batch.is_empty() {
+            self.flush(&mut batch, &mut report).await;
+        }
+
+        report
+    }
+
+    fn map_message(&self, raw: RawMessage) -> anyhow::Result<message::ImportMessage> {
+        let user_id = match self.users.get(&raw.user) {
+            Some(user_id) => *user_id,
+            None => raw
+                .user
+                .parse::<i32>()
+                .map_err(|_| anyhow::anyhow!("unmapped user: {}", raw.user))?,
+        };
+
+        let request = entities::message::PostMessageRequest {
+            text: raw.text,
+            send_at: None,
+            expires_in: None,
+        };
+        request.validate()?;
+
+        Ok(message::ImportMessage {
+            external_id: raw.external_id,
+            content: request.text,
+            user_id,
+            posted_at: raw.posted_at,
+        })
+    }
+
+    async fn flush(&self, batch: &mut Vec<message::ImportMessage>, report: &mut ImportReport) {
+        let total = batch.len() as u64;
+
+        match self
+            .messages_repository
+            .import_messages(self.tenant_id, std::mem::take(batch))
+            .await
+        {
+            Ok(inserted) => {
+                report.imported += inserted;
+                report.skipped += total - inserted;
+            }
+            Err(e) => {
+                tracing::error!(
+                    "import: failed to insert batch of {} messages: {:?}",
+                    total,
+                    e
+                );
+                report.failed += total;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc};
+
+    use chrono::Utc;
+
+    use super::*;
+    use crate::infra::repositories;
+
+    fn raw(external_id: &str, user: &str, text: &str) -> anyhow::Result<Option<RawMessage>> {
+        Ok(Some(RawMessage {
+            external_id: external_id.to_owned(),
+            user: user.to_owned(),
+            text: text.to_owned(),
+            posted_at: Utc::now(),
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_importer_report() {
+        let mut messages_repository =
+            repositories::messages::MockMessagesRepositoryTrait::default();
+
+        messages_repository
+            .expect_import_messages()
+            .withf(|tenant_id, msgs| {
+                *tenant_id == 5 && msgs.len() == 2 && msgs[0].user_id == 123 && msgs[1].user_id == 7
+            })
+            .once()
+            .returning(|_, _| Box::pin(async { Ok(1) }));
+
+        let importer = Importer::new(
+            Arc::new(messages_repository),
+            5,
+            HashMap::from([("U123".to_owned(), 123)]),
+            10,
+        );
+
+        let records = vec![
+            raw("a", "U123", "hello"),
+            raw("b", "7", "already imported"),
+            raw("c", "U404", "unmapped"),
+            raw("d", "U123", ""),
+            Ok(None),
+        ];
+
+        let report = importer.run(records.into_iter()).await;
+
+        assert_eq!(
+            report,
+            ImportReport {
+                imported: 1,
+                skipped: 2,
+                failed: 2,
+            }
+        );
+    }
+}
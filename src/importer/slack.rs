@@ -0,0 +1,141 @@
+//! Slack export reader.
+//!
+//! Slack exports contain a directory per channel with a JSON file per day. Both the export root
+//! and a single day file are accepted.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use super::{RawMessage, Records};
+
+#[derive(Debug, Deserialize)]
+struct SlackMessage {
+    #[serde(rename = "type")]
+    kind: String,
+    subtype: Option<String>,
+    user: Option<String>,
+    text: Option<String>,
+    ts: String,
+}
+
+pub fn read_export(path: &Path) -> anyhow::Result<Records> {
+    let files = if path.is_dir() {
+        let mut files = Vec::new();
+        for channel in sorted_entries(path)? {
+            if channel.is_dir() {
+                files.extend(
+                    sorted_entries(&channel)?
+                        .into_iter()
+                        .filter(|file| file.extension().is_some_and(|ext| ext == "json")),
+                );
+            }
+        }
+        files
+    } else {
+        vec![path.to_path_buf()]
+    };
+
+    Ok(Box::new(files.into_iter().flat_map(
+        |file| match read_day_file(&file) {
+            Ok(records) => records,
+            Err(e) => vec![Err(e)],
+        },
+    )))
+}
+
+fn sorted_entries(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut entries = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    entries.sort();
+    Ok(entries)
+}
+
+fn read_day_file(file: &Path) -> anyhow::Result<Vec<anyhow::Result<Option<RawMessage>>>> {
+    let channel = file
+        .parent()
+        .and_then(Path::file_name)
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let messages: Vec<SlackMessage> = serde_json::from_slice(&fs::read(file)?)
+        .map_err(|e| anyhow!("invalid slack export file {}: {}", file.display(), e))?;
+
+    Ok(messages
+        .into_iter()
+        .map(|msg| convert(&channel, msg))
+        .collect())
+}
+
+fn convert(channel: &str, msg: SlackMessage) -> anyhow::Result<Option<RawMessage>> {
+    // joins, topic changes and other service events are not chat messages
+    if msg.kind != "message" || msg.subtype.is_some() {
+        return Ok(None);
+    }
+
+    let user = msg
+        .user
+        .ok_or_else(|| anyhow!("slack message {} has no user", msg.ts))?;
+
+    Ok(Some(RawMessage {
+        external_id: format!("slack:{channel}:{}", msg.ts),
+        user,
+        text: msg.text.unwrap_or_default(),
+        posted_at: parse_ts(&msg.ts)?,
+    }))
+}
+
+/// Parses Slack timestamp like `1355517523.000005`.
+fn parse_ts(ts: &str) -> anyhow::Result<DateTime<Utc>> {
+    let (secs, micros) = ts.split_once('.').unwrap_or((ts, "0"));
+    let secs = secs.parse::<i64>()?;
+    let micros = format!("{micros:0<6}")
+        .get(..6)
+        .unwrap_or_default()
+        .parse::<u32>()?;
+
+    DateTime::from_timestamp(secs, micros * 1_000)
+        .ok_or_else(|| anyhow!("invalid slack timestamp: {ts}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_slack_message() {
+        let messages: Vec<SlackMessage> = serde_json::from_str(
+            r#"[
+                {"type": "message", "user": "U123", "text": "hello", "ts": "1355517523.000005"},
+                {"type": "message", "subtype": "channel_join", "user": "U123", "ts": "1355517524.000000"}
+            ]"#,
+        )
+        .unwrap();
+
+        let records: Vec<_> = messages
+            .into_iter()
+            .map(|msg| convert("general", msg).unwrap())
+            .collect();
+
+        assert_eq!(
+            records,
+            vec![
+                Some(RawMessage {
+                    external_id: "slack:general:1355517523.000005".to_owned(),
+                    user: "U123".to_owned(),
+                    text: "hello".to_owned(),
+                    posted_at: DateTime::parse_from_rfc3339("2012-12-14T20:38:43.000005Z")
+                        .unwrap()
+                        .with_timezone(&Utc),
+                }),
+                None,
+            ]
+        );
+    }
+}
@@ -0,0 +1,822 @@
+//! Read-through cache of unfiltered message listings, the pages nearly every client asks for.
+//!
+//! Pages are kept per process together with the listing version they were read at, and served
+//! only to listings run [`at_version`] of the same version. Writes of other processes, e.g.
+//! messages published by the worker, change the version, so they are never hidden behind
+//! validators of the new version. Units of work created through [`CachedUnitOfWorkFactory`]
+//! invalidate pages of their tenant on commit.
+//!
+//! Tenants looked up by access checks of every request are cached too, see
+//! [`CachedTenantsRepository`].
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{
+        Arc, LazyLock, Mutex, MutexGuard,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use clap::Parser;
+use futures_util::stream::BoxStream;
+use prometheus::{IntCounter, register_int_counter};
+
+use crate::{
+    domain::{errors::DomainError, message, tenant},
+    infra::repositories::{
+        messages::MessagesRepositoryTrait,
+        tenants::TenantsRepositoryTrait,
+        unit_of_work::{UnitOfWorkFactoryTrait, UnitOfWorkTrait},
+    },
+};
+
+static CACHE_HITS: LazyLock<IntCounter> = LazyLock::new(|| {
+    register_int_counter!(
+        "messages_cache_hits_total",
+        "Message listings served from cache."
+    )
+    .unwrap()
+});
+
+static CACHE_MISSES: LazyLock<IntCounter> = LazyLock::new(|| {
+    register_int_counter!(
+        "messages_cache_misses_total",
+        "Message listings read from storage because they were not cached."
+    )
+    .unwrap()
+});
+
+tokio::task_local! {
+    static LISTING_VERSION: message::ListingVersion;
+}
+
+/// Runs `fut` with listings read as of `version`, the one validators of the response are
+/// derived from. Listings outside of it are not cached.
+pub async fn at_version<F: Future>(version: message::ListingVersion, fut: F) -> F::Output {
+    LISTING_VERSION.scope(version, fut).await
+}
+
+/// Define messages cache config.
+#[derive(Parser, Debug, Clone)]
+pub struct MessagesCacheConfig {
+    /// Cache unfiltered message listings in process memory. Env variable name:
+    /// `MESSAGES_CACHE`.
+    #[arg(
+        long = "messages-cache",
+        env = "MESSAGES_CACHE",
+        default_value = "false"
+    )]
+    pub enabled: bool,
+
+    /// How long a cached page is served. Env variable name: `MESSAGES_CACHE_TTL`.
+    #[arg(long, env = "MESSAGES_CACHE_TTL", default_value = "5s")]
+    pub ttl: humantime::Duration,
+
+    /// Maximum cached pages, the oldest one is dropped first. Env variable name:
+    /// `MESSAGES_CACHE_CAPACITY`.
+    #[arg(long, env = "MESSAGES_CACHE_CAPACITY", default_value = "1000")]
+    pub capacity: usize,
+}
+
+impl MessagesCacheConfig {
+    pub fn parse() -> MessagesCacheConfig {
+        MessagesCacheConfig::try_parse().expect("Parsing configuration failed.")
+    }
+}
+
+/// Position of a cached page in the listing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Page {
+    Offset(i64),
+    /// First page of cursor pagination.
+    First,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct PageKey {
+    tenant_id: i32,
+    order: message::SortOrder,
+    page: Page,
+    limit: i64,
+}
+
+struct CachedPage {
+    cached_at: Instant,
+    valid_until: Instant,
+    version: message::ListingVersion,
+    messages: Vec<message::AuthoredMessage>,
+}
+
+/// Caching decorator of another messages repository.
+pub struct CachedMessagesRepository {
+    inner: Arc<dyn MessagesRepositoryTrait>,
+    ttl: Duration,
+    capacity: usize,
+    pages: Mutex<HashMap<PageKey, CachedPage>>,
+    /// Bumped on every invalidation, so pages read before a write are not stored after it.
+    generation: AtomicU64,
+}
+
+impl CachedMessagesRepository {
+    pub fn new(inner: Arc<dyn MessagesRepositoryTrait>, ttl: Duration, capacity: usize) -> Self {
+        Self {
+            inner,
+            ttl,
+            capacity,
+            pages: Mutex::new(HashMap::new()),
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    fn pages(&self) -> MutexGuard<'_, HashMap<PageKey, CachedPage>> {
+        // pages are plain values, a panicked holder can't leave them half written
+        self.pages.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Returns key of the page and the version it is read at when the listing is cacheable.
+    fn key(
+        tenant_id: i32,
+        filter: &message::MessageFilter,
+        page: Page,
+        limit: i64,
+    ) -> Option<(PageKey, message::ListingVersion)> {
+        let unfiltered = message::MessageFilter {
+            order: filter.order,
+            ..Default::default()
+        };
+        if *filter != unfiltered {
+            return None;
+        }
+        // without the version a cached page can't be told apart from a stale one
+        let version = LISTING_VERSION.try_with(Clone::clone).ok()?;
+
+        Some((
+            PageKey {
+                tenant_id,
+                order: filter.order,
+                page,
+                limit,
+            },
+            version,
+        ))
+    }
+
+    fn get(
+        &self,
+        key: &PageKey,
+        version: &message::ListingVersion,
+    ) -> Option<Vec<message::AuthoredMessage>> {
+        let mut pages = self.pages();
+        match pages.get(key) {
+            Some(cached) if cached.valid_until > Instant::now() && cached.version == *version => {
+                CACHE_HITS.inc();
+                return Some(cached.messages.clone());
+            }
+            Some(_) => {
+                pages.remove(key);
+            }
+            None => {}
+        }
+        CACHE_MISSES.inc();
+        None
+    }
+
+    fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    fn put(
+        &self,
+        key: PageKey,
+        version: message::ListingVersion,
+        generation: u64,
+        messages: &[message::AuthoredMessage],
+    ) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let now = Instant::now();
+        // a page must not outlive its earliest expiring message
+        let valid_until = messages
+            .iter()
+            .filter_map(|msg| msg.expires_at)
+            .min()
+            .map(|expires_at| (expires_at - Utc::now()).to_std().unwrap_or_default())
+            .map_or(self.ttl, |left| left.min(self.ttl));
+
+        let mut pages = self.pages();
+        if self.generation() != generation {
+            return;
+        }
+        pages.retain(|_, cached| cached.valid_until > now);
+        if pages.len() >= self.capacity && !pages.contains_key(&key) {
+            let oldest = pages
+                .iter()
+                .min_by_key(|(_, cached)| cached.cached_at)
+                .map(|(key, _)| *key);
+            if let Some(oldest) = oldest {
+                pages.remove(&oldest);
+            }
+        }
+        pages.insert(
+            key,
+            CachedPage {
+                cached_at: now,
+                valid_until: now + valid_until,
+                version,
+                messages: messages.to_vec(),
+            },
+        );
+    }
+
+    fn invalidate_tenant(&self, tenant_id: i32) {
+        let mut pages = self.pages();
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        pages.retain(|key, _| key.tenant_id != tenant_id);
+    }
+
+    fn invalidate_all(&self) {
+        let mut pages = self.pages();
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        pages.clear();
+    }
+}
+
+#[async_trait]
+impl MessagesRepositoryTrait for CachedMessagesRepository {
+    async fn create_message(
+        &self,
+        tenant_id: i32,
+        msg: message::PostMessage,
+    ) -> Result<i64, DomainError> {
+        let message_id = self.inner.create_message(tenant_id, msg).await?;
+        self.invalidate_tenant(tenant_id);
+        Ok(message_id)
+    }
+
+    async fn update_message(
+        &self,
+        tenant_id: i32,
+        message_id: i64,
+        user_id: i32,
+        content: String,
+        edited_at: DateTime<Utc>,
+    ) -> Result<message::Message, DomainError> {
+        let msg = self
+            .inner
+            .update_message(tenant_id, message_id, user_id, content, edited_at)
+            .await?;
+        self.invalidate_tenant(tenant_id);
+        Ok(msg)
+    }
+
+    async fn delete_message(
+        &self,
+        tenant_id: i32,
+        message_id: i64,
+        user_id: i32,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<message::Message, DomainError> {
+        let msg = self
+            .inner
+            .delete_message(tenant_id, message_id, user_id, deleted_at)
+            .await?;
+        self.invalidate_tenant(tenant_id);
+        Ok(msg)
+    }
+
+    async fn import_messages(
+        &self,
+        tenant_id: i32,
+        msgs: Vec<message::ImportMessage>,
+    ) -> Result<u64, DomainError> {
+        let inserted = self.inner.import_messages(tenant_id, msgs).await?;
+        self.invalidate_tenant(tenant_id);
+        Ok(inserted)
+    }
+
+    async fn list_messages(
+        &self,
+        tenant_id: i32,
+        filter: message::MessageFilter,
+        offset: i64,
+        limit: i64,
+    ) -> Result<Vec<message::AuthoredMessage>, DomainError> {
+        let Some((key, version)) = Self::key(tenant_id, &filter, Page::Offset(offset), limit)
+        else {
+            return self
+                .inner
+                .list_messages(tenant_id, filter, offset, limit)
+                .await;
+        };
+        if let Some(messages) = self.get(&key, &version) {
+            return Ok(messages);
+        }
+        let generation = self.generation();
+
+        let messages = self
+            .inner
+            .list_messages(tenant_id, filter, offset, limit)
+            .await?;
+        self.put(key, version, generation, &messages);
+        Ok(messages)
+    }
+
+    async fn list_messages_page(
+        &self,
+        tenant_id: i32,
+        filter: message::MessageFilter,
+        cursor: Option<message::Cursor>,
+        limit: i64,
+    ) -> Result<Vec<message::AuthoredMessage>, DomainError> {
+        let key = match cursor {
+            None => Self::key(tenant_id, &filter, Page::First, limit),
+            Some(_) => None,
+        };
+        let Some((key, version)) = key else {
+            return self
+                .inner
+                .list_messages_page(tenant_id, filter, cursor, limit)
+                .await;
+        };
+        if let Some(messages) = self.get(&key, &version) {
+            return Ok(messages);
+        }
+        let generation = self.generation();
+
+        let messages = self
+            .inner
+            .list_messages_page(tenant_id, filter, cursor, limit)
+            .await?;
+        self.put(key, version, generation, &messages);
+        Ok(messages)
+    }
+
+    async fn count_messages(
+        &self,
+        tenant_id: i32,
+        filter: message::MessageFilter,
+    ) -> Result<i64, DomainError> {
+        self.inner.count_messages(tenant_id, filter).await
+    }
+
+    async fn messages_version(
+        &self,
+        tenant_id: i32,
+        filter: message::MessageFilter,
+    ) -> Result<message::ListingVersion, DomainError> {
+        self.inner.messages_version(tenant_id, filter).await
+    }
+
+    async fn get_message(
+        &self,
+        tenant_id: i32,
+        message_id: i64,
+    ) -> Result<message::AuthoredMessage, DomainError> {
+        self.inner.get_message(tenant_id, message_id).await
+    }
+
+    async fn get_messages(
+        &self,
+        tenant_id: i32,
+        message_ids: Vec<i64>,
+    ) -> Result<Vec<message::AuthoredMessage>, DomainError> {
+        self.inner.get_messages(tenant_id, message_ids).await
+    }
+
+    fn stream_messages(
+        &self,
+        tenant_id: i32,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> BoxStream<'static, Result<message::Message, DomainError>> {
+        self.inner.stream_messages(tenant_id, since, until)
+    }
+
+    async fn count_retention_candidates(
+        &self,
+        before: DateTime<Utc>,
+        action: message::RetentionAction,
+    ) -> Result<i64, DomainError> {
+        self.inner.count_retention_candidates(before, action).await
+    }
+
+    async fn apply_retention(
+        &self,
+        before: DateTime<Utc>,
+        action: message::RetentionAction,
+        limit: i64,
+    ) -> Result<u64, DomainError> {
+        let affected = self.inner.apply_retention(before, action, limit).await?;
+        self.invalidate_all();
+        Ok(affected)
+    }
+
+    async fn delete_expired_messages(&self, limit: i64) -> Result<u64, DomainError> {
+        let deleted = self.inner.delete_expired_messages(limit).await?;
+        self.invalidate_all();
+        Ok(deleted)
+    }
+}
+
+/// Unit of work factory invalidating pages of a [`CachedMessagesRepository`], whose listings
+/// the units change behind its back.
+pub struct CachedUnitOfWorkFactory {
+    inner: Arc<dyn UnitOfWorkFactoryTrait>,
+    cache: Arc<CachedMessagesRepository>,
+}
+
+impl CachedUnitOfWorkFactory {
+    pub fn new(
+        inner: Arc<dyn UnitOfWorkFactoryTrait>,
+        cache: Arc<CachedMessagesRepository>,
+    ) -> Self {
+        Self { inner, cache }
+    }
+}
+
+#[async_trait]
+impl UnitOfWorkFactoryTrait for CachedUnitOfWorkFactory {
+    async fn begin(&self, tenant_id: i32) -> Result<Box<dyn UnitOfWorkTrait>, DomainError> {
+        Ok(Box::new(CachedUnitOfWork {
+            inner: self.inner.begin(tenant_id).await?,
+            cache: self.cache.clone(),
+            tenant_id,
+        }))
+    }
+}
+
+/// Invalidates pages of the tenant once its writes are committed.
+struct CachedUnitOfWork {
+    inner: Box<dyn UnitOfWorkTrait>,
+    cache: Arc<CachedMessagesRepository>,
+    tenant_id: i32,
+}
+
+#[async_trait]
+impl UnitOfWorkTrait for CachedUnitOfWork {
+    async fn create_message(&mut self, msg: message::PostMessage) -> Result<i64, DomainError> {
+        self.inner.create_message(msg).await
+    }
+
+    async fn update_message(
+        &mut self,
+        message_id: i64,
+        user_id: i32,
+        content: String,
+        edited_at: DateTime<Utc>,
+    ) -> Result<message::Message, DomainError> {
+        self.inner
+            .update_message(message_id, user_id, content, edited_at)
+            .await
+    }
+
+    async fn delete_message(
+        &mut self,
+        message_id: i64,
+        user_id: i32,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<message::Message, DomainError> {
+        self.inner
+            .delete_message(message_id, user_id, deleted_at)
+            .await
+    }
+
+    async fn pin_message(&mut self, message_id: i64, pinned_by: i32) -> Result<bool, DomainError> {
+        self.inner.pin_message(message_id, pinned_by).await
+    }
+
+    async fn unpin_message(&mut self, message_id: i64) -> Result<bool, DomainError> {
+        self.inner.unpin_message(message_id).await
+    }
+
+    async fn add_bookmark(&mut self, user_id: i32, message_id: i64) -> Result<bool, DomainError> {
+        self.inner.add_bookmark(user_id, message_id).await
+    }
+
+    async fn remove_bookmark(
+        &mut self,
+        user_id: i32,
+        message_id: i64,
+    ) -> Result<bool, DomainError> {
+        self.inner.remove_bookmark(user_id, message_id).await
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), DomainError> {
+        self.inner.commit().await?;
+        self.cache.invalidate_tenant(self.tenant_id);
+        Ok(())
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), DomainError> {
+        self.inner.rollback().await
+    }
+}
+
+/// Define tenants cache config.
+#[derive(Parser, Debug, Clone)]
+pub struct TenantsCacheConfig {
+    /// How long a looked up tenant is reused, suspension by another process takes effect after
+    /// it at most. `0s` looks every tenant up. Env variable name: `TENANTS_CACHE_TTL`.
+    #[arg(long, env = "TENANTS_CACHE_TTL", default_value = "5s")]
+    pub ttl: humantime::Duration,
+}
+
+impl TenantsCacheConfig {
+    pub fn parse() -> TenantsCacheConfig {
+        TenantsCacheConfig::try_parse().expect("Parsing configuration failed.")
+    }
+}
+
+struct CachedTenant {
+    valid_until: Instant,
+    tenant: Option<tenant::Tenant>,
+}
+
+/// Caching decorator of another tenants repository, unknown tenants are cached as well.
+/// Tenants created or suspended through it are refreshed right away.
+pub struct CachedTenantsRepository {
+    inner: Arc<dyn TenantsRepositoryTrait>,
+    ttl: Duration,
+    tenants: Mutex<HashMap<i32, CachedTenant>>,
+}
+
+impl CachedTenantsRepository {
+    pub fn new(inner: Arc<dyn TenantsRepositoryTrait>, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            tenants: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn tenants(&self) -> MutexGuard<'_, HashMap<i32, CachedTenant>> {
+        self.tenants.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn put(&self, tenant_id: i32, tenant: Option<tenant::Tenant>) {
+        let now = Instant::now();
+        let mut tenants = self.tenants();
+        // tenants are few, dropping the expired ones on write keeps the map bounded by them
+        tenants.retain(|_, cached| cached.valid_until > now);
+        tenants.insert(
+            tenant_id,
+            CachedTenant {
+                valid_until: now + self.ttl,
+                tenant,
+            },
+        );
+    }
+}
+
+#[async_trait]
+impl TenantsRepositoryTrait for CachedTenantsRepository {
+    async fn create_tenant(&self, name: String) -> Result<tenant::Tenant, DomainError> {
+        let created = self.inner.create_tenant(name).await?;
+        self.put(created.tenant_id, Some(created.clone()));
+
+        Ok(created)
+    }
+
+    async fn get_tenant(&self, tenant_id: i32) -> Result<Option<tenant::Tenant>, DomainError> {
+        if let Some(cached) = self.tenants().get(&tenant_id)
+            && cached.valid_until > Instant::now()
+        {
+            return Ok(cached.tenant.clone());
+        }
+
+        let tenant = self.inner.get_tenant(tenant_id).await?;
+        self.put(tenant_id, tenant.clone());
+
+        Ok(tenant)
+    }
+
+    async fn suspend_tenant(&self, tenant_id: i32) -> Result<Option<tenant::Tenant>, DomainError> {
+        let suspended = self.inner.suspend_tenant(tenant_id).await?;
+        self.put(tenant_id, suspended.clone());
+
+        Ok(suspended)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mockall::predicate::*;
+
+    use super::*;
+    use crate::infra::repositories::{
+        messages::MockMessagesRepositoryTrait, tenants::MockTenantsRepositoryTrait,
+    };
+
+    fn authored(message_id: i64) -> message::AuthoredMessage {
+        message::AuthoredMessage {
+            message_id,
+            message_content: "hello".to_string(),
+            user_id: 123,
+            posted_at: Utc::now(),
+            expires_at: None,
+            display_name: None,
+            avatar_url: None,
+        }
+    }
+
+    fn version(visible: i64) -> message::ListingVersion {
+        message::ListingVersion {
+            max_message_id: Some(visible),
+            last_modified: None,
+            visible,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cached_messages_pages() {
+        let mut inner = MockMessagesRepositoryTrait::default();
+        inner
+            .expect_list_messages()
+            .with(eq(1), eq(message::MessageFilter::default()), eq(0), eq(100))
+            .times(2)
+            .returning(|_, _, _, _| Box::pin(async { Ok(vec![authored(1)]) }));
+        inner
+            .expect_list_messages()
+            .with(always(), always(), eq(100), eq(100))
+            .once()
+            .returning(|_, _, _, _| Box::pin(async { Ok(vec![]) }));
+        inner
+            .expect_create_message()
+            .once()
+            .returning(|_, _| Box::pin(async { Ok(2) }));
+        let repository = CachedMessagesRepository::new(Arc::new(inner), Duration::from_secs(60), 1);
+
+        let first = |tenant_id| {
+            at_version(
+                version(1),
+                repository.list_messages(tenant_id, Default::default(), 0, 100),
+            )
+        };
+        assert_eq!(first(1).await.unwrap().len(), 1);
+        assert_eq!(first(1).await.unwrap().len(), 1);
+
+        // capacity of one page evicts the first page
+        for _ in 0..2 {
+            at_version(
+                version(1),
+                repository.list_messages(1, Default::default(), 100, 100),
+            )
+            .await
+            .unwrap();
+        }
+
+        repository
+            .create_message(
+                1,
+                message::PostMessage {
+                    content: "hello".to_string(),
+                    user_id: 123,
+                    posted_at: Utc::now(),
+                    expires_at: None,
+                },
+            )
+            .await
+            .unwrap();
+        assert!(repository.pages().is_empty());
+        assert_eq!(first(1).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cached_messages_skips_filtered_listings() {
+        let mut inner = MockMessagesRepositoryTrait::default();
+        inner
+            .expect_list_messages()
+            .times(2)
+            .returning(|_, _, _, _| Box::pin(async { Ok(vec![authored(1)]) }));
+        let repository =
+            CachedMessagesRepository::new(Arc::new(inner), Duration::from_secs(60), 10);
+
+        let filter = message::MessageFilter {
+            user_id: Some(123),
+            ..Default::default()
+        };
+        for _ in 0..2 {
+            at_version(
+                version(1),
+                repository.list_messages(1, filter.clone(), 0, 100),
+            )
+            .await
+            .unwrap();
+        }
+        assert!(repository.pages().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cached_messages_follow_listing_version() {
+        let mut inner = MockMessagesRepositoryTrait::default();
+        let mut seq = mockall::Sequence::new();
+        inner
+            .expect_list_messages()
+            .once()
+            .in_sequence(&mut seq)
+            .returning(|_, _, _, _| Box::pin(async { Ok(vec![authored(1)]) }));
+        // another process posted, which moved the version past the cached page
+        inner
+            .expect_list_messages()
+            .times(3)
+            .in_sequence(&mut seq)
+            .returning(|_, _, _, _| Box::pin(async { Ok(vec![authored(2), authored(1)]) }));
+        let repository =
+            CachedMessagesRepository::new(Arc::new(inner), Duration::from_secs(60), 10);
+        let list = || repository.list_messages(1, Default::default(), 0, 100);
+
+        assert_eq!(at_version(version(1), list()).await.unwrap().len(), 1);
+        assert_eq!(at_version(version(1), list()).await.unwrap().len(), 1);
+        assert_eq!(at_version(version(2), list()).await.unwrap().len(), 2);
+        assert_eq!(at_version(version(2), list()).await.unwrap().len(), 2);
+
+        // listings without a version can't be checked, so they skip the cache
+        assert_eq!(list().await.unwrap().len(), 2);
+        assert_eq!(list().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_cached_units_invalidate_on_commit() {
+        let mut inner = MockMessagesRepositoryTrait::default();
+        inner
+            .expect_list_messages()
+            .times(2)
+            .returning(|_, _, _, _| Box::pin(async { Ok(vec![authored(1)]) }));
+        let repository = Arc::new(CachedMessagesRepository::new(
+            Arc::new(inner),
+            Duration::from_secs(60),
+            10,
+        ));
+        let store = crate::infra::memory::MemoryStore::new();
+        let units = CachedUnitOfWorkFactory::new(
+            Arc::new(crate::infra::memory::MemoryUnitOfWorkFactory::new(store)),
+            repository.clone(),
+        );
+        let list = || {
+            at_version(
+                version(1),
+                repository.list_messages(1, Default::default(), 0, 100),
+            )
+        };
+        let post = || message::PostMessage {
+            content: "hello".to_string(),
+            user_id: 123,
+            posted_at: Utc::now(),
+            expires_at: None,
+        };
+
+        list().await.unwrap();
+        let mut unit = units.begin(1).await.unwrap();
+        unit.create_message(post()).await.unwrap();
+        unit.rollback().await.unwrap();
+        assert_eq!(repository.pages().len(), 1);
+
+        let mut unit = units.begin(1).await.unwrap();
+        unit.create_message(post()).await.unwrap();
+        unit.commit().await.unwrap();
+        assert!(repository.pages().is_empty());
+        list().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_cached_tenants_status() {
+        let tenant = |suspended_at| tenant::Tenant {
+            tenant_id: 2,
+            name: "acme".to_string(),
+            created_at: Utc::now(),
+            suspended_at,
+        };
+        let mut inner = MockTenantsRepositoryTrait::default();
+        inner
+            .expect_get_tenant()
+            .with(eq(2))
+            .once()
+            .returning(move |_| Box::pin(async move { Ok(Some(tenant(None))) }));
+        inner
+            .expect_get_tenant()
+            .with(eq(3))
+            .once()
+            .returning(|_| Box::pin(async { Ok(None) }));
+        inner
+            .expect_suspend_tenant()
+            .with(eq(2))
+            .once()
+            .returning(move |_| Box::pin(async move { Ok(Some(tenant(Some(Utc::now())))) }));
+        let repository = CachedTenantsRepository::new(Arc::new(inner), Duration::from_secs(60));
+
+        assert!(repository.get_tenant(2).await.unwrap().unwrap().is_active());
+        assert!(repository.get_tenant(2).await.unwrap().unwrap().is_active());
+        assert!(repository.get_tenant(3).await.unwrap().is_none());
+        assert!(repository.get_tenant(3).await.unwrap().is_none());
+
+        // suspension is seen by the next access check of this process
+        repository.suspend_tenant(2).await.unwrap();
+        assert!(!repository.get_tenant(2).await.unwrap().unwrap().is_active());
+    }
+}
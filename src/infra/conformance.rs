@@ -0,0 +1,797 @@
+//! Behaviour every [`MessagesRepositoryTrait`] implementation, and units of work of the same
+//! storage, have to share, so that listings look the same whatever storage is configured.
+//!
+//! Memory and SQLite storages, and the cache over memory storage, are always checked. Postgres is
+//! checked when `TEST_POSTGRES_URL` points to a database, every run migrates and drops its own
+//! schema there.
+
+use std::{future::Future, sync::Arc};
+
+use chrono::{DateTime, Duration, Utc};
+use futures_util::TryStreamExt;
+
+use crate::{
+    domain::{crypto::random_token, errors::DomainError, message, tenant::DEFAULT_TENANT_ID, user},
+    infra::{
+        cache, memory, migrations, postgres, repositories,
+        repositories::{
+            messages::MessagesRepositoryTrait, tenants::TenantsRepositoryTrait,
+            unit_of_work::UnitOfWorkFactoryTrait, users::UsersRepositoryTrait,
+        },
+        sqlite,
+    },
+};
+
+type Repository = Arc<dyn MessagesRepositoryTrait>;
+
+const TENANT: i32 = DEFAULT_TENANT_ID;
+
+/// Repositories sharing one store.
+struct Storage {
+    messages: Repository,
+    tenants: Arc<dyn TenantsRepositoryTrait>,
+    users: Arc<dyn UsersRepositoryTrait>,
+    units: Arc<dyn UnitOfWorkFactoryTrait>,
+}
+
+fn at(minutes: i64) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339("2020-04-12T22:00:00Z")
+        .unwrap()
+        .with_timezone(&Utc)
+        + Duration::minutes(minutes)
+}
+
+/// Posts messages a minute apart, message ids start from 1.
+async fn seed(repository: &Repository, tenant_id: i32, contents: &[&str]) {
+    for (minutes, content) in contents.iter().enumerate() {
+        repository
+            .create_message(
+                tenant_id,
+                message::PostMessage {
+                    content: content.to_string(),
+                    user_id: 123,
+                    posted_at: at(minutes as i64),
+                    expires_at: None,
+                },
+            )
+            .await
+            .unwrap();
+    }
+}
+
+fn ids(messages: &[message::AuthoredMessage]) -> Vec<i64> {
+    messages.iter().map(|msg| msg.message_id).collect()
+}
+
+async fn list_order_and_pagination(repository: Repository) {
+    seed(&repository, TENANT, &["a", "b", "c", "d"]).await;
+
+    let latest = repository
+        .list_messages(TENANT, Default::default(), 1, 2)
+        .await
+        .unwrap();
+    assert_eq!(ids(&latest), vec![3, 2]);
+
+    let oldest = repository
+        .list_messages(
+            TENANT,
+            message::MessageFilter {
+                order: message::SortOrder::Asc,
+                ..Default::default()
+            },
+            0,
+            3,
+        )
+        .await
+        .unwrap();
+    assert_eq!(ids(&oldest), vec![1, 2, 3]);
+}
+
+async fn ties_are_ordered_by_id(repository: Repository) {
+    for content in ["a", "b", "c"] {
+        repository
+            .create_message(
+                TENANT,
+                message::PostMessage {
+                    content: content.to_string(),
+                    user_id: 123,
+                    posted_at: at(0),
+                    expires_at: None,
+                },
+            )
+            .await
+            .unwrap();
+    }
+
+    let latest = repository
+        .list_messages(TENANT, Default::default(), 0, 10)
+        .await
+        .unwrap();
+    assert_eq!(ids(&latest), vec![3, 2, 1]);
+
+    let next = repository
+        .list_messages_page(
+            TENANT,
+            Default::default(),
+            Some(message::Cursor {
+                posted_at: at(0),
+                message_id: 3,
+                direction: message::Direction::Next,
+            }),
+            1,
+        )
+        .await
+        .unwrap();
+    assert_eq!(ids(&next), vec![2]);
+}
+
+async fn cursor_pages(repository: Repository) {
+    seed(&repository, TENANT, &["a", "b", "c", "d", "e"]).await;
+    let cursor = |message_id: i64, direction| message::Cursor {
+        posted_at: at(message_id - 1),
+        message_id,
+        direction,
+    };
+
+    let first = repository
+        .list_messages_page(TENANT, Default::default(), None, 2)
+        .await
+        .unwrap();
+    assert_eq!(ids(&first), vec![5, 4]);
+
+    let next = repository
+        .list_messages_page(
+            TENANT,
+            Default::default(),
+            Some(cursor(4, message::Direction::Next)),
+            2,
+        )
+        .await
+        .unwrap();
+    assert_eq!(ids(&next), vec![3, 2]);
+
+    let prev = repository
+        .list_messages_page(
+            TENANT,
+            Default::default(),
+            Some(cursor(2, message::Direction::Prev)),
+            2,
+        )
+        .await
+        .unwrap();
+    assert_eq!(ids(&prev), vec![4, 3]);
+
+    let ascending = message::MessageFilter {
+        order: message::SortOrder::Asc,
+        ..Default::default()
+    };
+    let next = repository
+        .list_messages_page(
+            TENANT,
+            ascending.clone(),
+            Some(cursor(2, message::Direction::Next)),
+            2,
+        )
+        .await
+        .unwrap();
+    assert_eq!(ids(&next), vec![3, 4]);
+
+    let prev = repository
+        .list_messages_page(
+            TENANT,
+            ascending,
+            Some(cursor(2, message::Direction::Prev)),
+            2,
+        )
+        .await
+        .unwrap();
+    assert_eq!(ids(&prev), vec![1]);
+}
+
+/// Timestamps are kept to microseconds, the precision cursors of the API carry.
+async fn sub_microsecond_cursors(repository: Repository) {
+    for nanos in [700, 400, 900] {
+        repository
+            .create_message(
+                TENANT,
+                message::PostMessage {
+                    content: "same microsecond".to_string(),
+                    user_id: 123,
+                    posted_at: at(0) + Duration::nanoseconds(nanos),
+                    expires_at: None,
+                },
+            )
+            .await
+            .unwrap();
+    }
+    let page = |order, after: Option<&message::AuthoredMessage>| {
+        let filter = message::MessageFilter {
+            order,
+            ..Default::default()
+        };
+        let cursor = after.map(|msg| message::Cursor {
+            posted_at: DateTime::from_timestamp_micros(msg.posted_at.timestamp_micros()).unwrap(),
+            message_id: msg.message_id,
+            direction: message::Direction::Next,
+        });
+        let repository = repository.clone();
+        async move {
+            repository
+                .list_messages_page(TENANT, filter, cursor, 1)
+                .await
+                .unwrap()
+        }
+    };
+
+    for (order, expected) in [
+        (message::SortOrder::Asc, [1, 2, 3]),
+        (message::SortOrder::Desc, [3, 2, 1]),
+    ] {
+        let mut listed = vec![];
+        let mut last = None;
+        for _ in 0..3 {
+            let next = page(order, last.as_ref()).await;
+            assert_eq!(next.len(), 1);
+            assert_eq!(next[0].posted_at, at(0));
+            listed.extend(ids(&next));
+            last = next.into_iter().next();
+        }
+        assert_eq!(listed, expected);
+        assert!(page(order, last.as_ref()).await.is_empty());
+    }
+
+    let edited = repository
+        .update_message(
+            TENANT,
+            1,
+            123,
+            "x".to_string(),
+            at(1) + Duration::nanoseconds(1),
+        )
+        .await
+        .unwrap();
+    assert_eq!(edited.posted_at, at(0));
+    let version = repository
+        .messages_version(TENANT, Default::default())
+        .await
+        .unwrap();
+    assert_eq!(version.last_modified, Some(at(1)));
+}
+
+async fn filters(repository: Repository) {
+    seed(
+        &repository,
+        TENANT,
+        &["Hello world", "hello again", "50% off", "bye"],
+    )
+    .await;
+    repository
+        .create_message(
+            TENANT,
+            message::PostMessage {
+                content: "hello from 7".to_string(),
+                user_id: 7,
+                posted_at: at(10),
+                expires_at: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    let filtered = |filter: message::MessageFilter| {
+        let repository = repository.clone();
+        async move {
+            let messages = repository
+                .list_messages(TENANT, filter.clone(), 0, 10)
+                .await
+                .unwrap();
+            let total = repository.count_messages(TENANT, filter).await.unwrap();
+            assert_eq!(total, messages.len() as i64);
+            ids(&messages)
+        }
+    };
+
+    let by_user = filtered(message::MessageFilter {
+        user_id: Some(7),
+        ..Default::default()
+    });
+    assert_eq!(by_user.await, vec![5]);
+
+    let window = filtered(message::MessageFilter {
+        since: Some(at(1)),
+        until: Some(at(3)),
+        ..Default::default()
+    });
+    assert_eq!(window.await, vec![3, 2]);
+
+    let prefix = filtered(message::MessageFilter {
+        text: Some(message::TextMatch::Prefix("HELLO".to_string())),
+        ..Default::default()
+    });
+    assert_eq!(prefix.await, vec![5, 2, 1]);
+
+    let contains = filtered(message::MessageFilter {
+        text: Some(message::TextMatch::Contains("% o".to_string())),
+        ..Default::default()
+    });
+    assert_eq!(contains.await, vec![3]);
+
+    let wildcard = filtered(message::MessageFilter {
+        text: Some(message::TextMatch::Contains("_".to_string())),
+        ..Default::default()
+    });
+    assert_eq!(wildcard.await, Vec::<i64>::new());
+}
+
+async fn change_and_delete(repository: Repository) {
+    seed(&repository, TENANT, &["a", "b"]).await;
+
+    assert!(matches!(
+        repository
+            .update_message(TENANT, 1, 7, "x".to_string(), at(5))
+            .await,
+        Err(DomainError::Forbidden)
+    ));
+    assert!(matches!(
+        repository
+            .update_message(TENANT, 42, 123, "x".to_string(), at(5))
+            .await,
+        Err(DomainError::NotFound("message"))
+    ));
+
+    let edited = repository
+        .update_message(TENANT, 1, 123, "edited".to_string(), at(5))
+        .await
+        .unwrap();
+    assert_eq!(edited.message_content, "edited");
+    assert_eq!(edited.posted_at, at(0));
+
+    repository
+        .delete_message(TENANT, 1, 123, at(6))
+        .await
+        .unwrap();
+    assert!(matches!(
+        repository.get_message(TENANT, 1).await,
+        Err(DomainError::NotFound("message"))
+    ));
+    assert!(matches!(
+        repository.delete_message(TENANT, 1, 123, at(7)).await,
+        Err(DomainError::NotFound("message"))
+    ));
+
+    let found = repository
+        .get_messages(TENANT, vec![1, 2, 3])
+        .await
+        .unwrap();
+    assert_eq!(ids(&found), vec![2]);
+}
+
+async fn import_skips_duplicates(repository: Repository) {
+    let import = |external_id: &str, minutes| message::ImportMessage {
+        external_id: external_id.to_string(),
+        content: "imported".to_string(),
+        user_id: 123,
+        posted_at: at(minutes),
+    };
+
+    let inserted = repository
+        .import_messages(TENANT, vec![import("1", 2), import("2", 0)])
+        .await
+        .unwrap();
+    assert_eq!(inserted, 2);
+
+    let inserted = repository
+        .import_messages(TENANT, vec![import("2", 0), import("3", 1)])
+        .await
+        .unwrap();
+    assert_eq!(inserted, 1);
+
+    let exported: Vec<_> = repository
+        .stream_messages(TENANT, None, None)
+        .try_collect()
+        .await
+        .unwrap();
+    let posted: Vec<_> = exported.iter().map(|msg| msg.posted_at).collect();
+    assert_eq!(posted, vec![at(0), at(1), at(2)]);
+}
+
+async fn version_tracks_changes(repository: Repository) {
+    let empty = repository
+        .messages_version(TENANT, Default::default())
+        .await
+        .unwrap();
+    assert_eq!(empty.max_message_id, None);
+    assert_eq!(empty.last_modified, None);
+    assert_eq!(empty.visible, 0);
+
+    seed(&repository, TENANT, &["a", "b"]).await;
+    let posted = repository
+        .messages_version(TENANT, Default::default())
+        .await
+        .unwrap();
+    assert_eq!(posted.max_message_id, Some(2));
+    assert_eq!(posted.last_modified, Some(at(1)));
+    assert_eq!(posted.visible, 2);
+
+    repository
+        .delete_message(TENANT, 1, 123, at(9))
+        .await
+        .unwrap();
+    let deleted = repository
+        .messages_version(TENANT, Default::default())
+        .await
+        .unwrap();
+    assert_eq!(deleted.max_message_id, Some(2));
+    assert_eq!(deleted.last_modified, Some(at(9)));
+    assert_eq!(deleted.visible, 1);
+}
+
+async fn version_tracks_authors(storage: Storage) {
+    let repository = storage.messages;
+    seed(&repository, TENANT, &["a", "b"]).await;
+    let version = || async {
+        repository
+            .messages_version(TENANT, Default::default())
+            .await
+            .unwrap()
+    };
+    let posted = version().await;
+
+    // listings show author profiles, so changing one changes them
+    storage
+        .users
+        .update_profile(
+            TENANT,
+            123,
+            user::ProfileChange {
+                display_name: Some(Some("Alice".to_string())),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    let renamed = version().await;
+    assert!(renamed.last_modified > posted.last_modified);
+
+    let anonymized = repository
+        .apply_retention(at(9), message::RetentionAction::Anonymize, 10)
+        .await
+        .unwrap();
+    assert_eq!(anonymized, 2);
+    let anonymized = version().await;
+    assert!(anonymized.last_modified > renamed.last_modified);
+    assert_eq!(anonymized.visible, posted.visible);
+}
+
+async fn tenants_are_isolated(storage: Storage) {
+    let repository = storage.messages;
+    let other = storage
+        .tenants
+        .create_tenant("other".to_string())
+        .await
+        .unwrap()
+        .tenant_id;
+    seed(&repository, TENANT, &["a", "b"]).await;
+    seed(&repository, other, &["c"]).await;
+
+    let listed = repository
+        .list_messages(other, Default::default(), 0, 10)
+        .await
+        .unwrap();
+    assert_eq!(ids(&listed), vec![3]);
+    assert_eq!(
+        repository
+            .count_messages(other, Default::default())
+            .await
+            .unwrap(),
+        1
+    );
+    assert_eq!(
+        repository
+            .messages_version(other, Default::default())
+            .await
+            .unwrap()
+            .visible,
+        1
+    );
+    assert!(matches!(
+        repository.get_message(other, 1).await,
+        Err(DomainError::NotFound("message"))
+    ));
+    assert_eq!(
+        ids(&repository.get_messages(other, vec![1, 2, 3]).await.unwrap()),
+        vec![3]
+    );
+    assert!(matches!(
+        repository
+            .update_message(other, 1, 123, "x".to_string(), at(5))
+            .await,
+        Err(DomainError::NotFound("message"))
+    ));
+    assert!(matches!(
+        repository.delete_message(other, 1, 123, at(5)).await,
+        Err(DomainError::NotFound("message"))
+    ));
+    assert_eq!(
+        repository
+            .get_message(TENANT, 1)
+            .await
+            .unwrap()
+            .message_content,
+        "a"
+    );
+
+    let import = || {
+        vec![message::ImportMessage {
+            external_id: "1".to_string(),
+            content: "imported".to_string(),
+            user_id: 123,
+            posted_at: at(9),
+        }]
+    };
+    assert_eq!(
+        repository.import_messages(TENANT, import()).await.unwrap(),
+        1
+    );
+    assert_eq!(
+        repository.import_messages(other, import()).await.unwrap(),
+        1
+    );
+
+    let exported: Vec<_> = repository
+        .stream_messages(other, None, None)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(exported.len(), 2);
+
+    // the same user id is a different person in another tenant
+    storage
+        .users
+        .update_profile(
+            other,
+            123,
+            user::ProfileChange {
+                display_name: Some(Some("Other".to_string())),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert!(
+        storage
+            .users
+            .get_profile(TENANT, 123)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert_eq!(
+        storage
+            .users
+            .get_profile(other, 123)
+            .await
+            .unwrap()
+            .unwrap()
+            .display_name
+            .as_deref(),
+        Some("Other")
+    );
+    let listed = repository
+        .list_messages(TENANT, Default::default(), 0, 10)
+        .await
+        .unwrap();
+    assert!(listed.iter().all(|msg| msg.display_name.is_none()));
+    assert_eq!(
+        repository
+            .get_message(TENANT, 1)
+            .await
+            .unwrap()
+            .display_name,
+        None
+    );
+    let listed = repository
+        .list_messages(other, Default::default(), 0, 10)
+        .await
+        .unwrap();
+    assert!(
+        listed
+            .iter()
+            .all(|msg| msg.display_name.as_deref() == Some("Other"))
+    );
+}
+
+async fn profile_changes(storage: Storage) {
+    let users = storage.users;
+    let change = |display_name, bio| user::ProfileChange {
+        display_name,
+        bio,
+        ..Default::default()
+    };
+
+    users
+        .update_profile(
+            TENANT,
+            123,
+            change(
+                Some(Some("Alice".to_string())),
+                Some(Some("hi".to_string())),
+            ),
+        )
+        .await
+        .unwrap();
+
+    // omitted fields are kept, cleared ones are removed
+    let profile = users
+        .update_profile(TENANT, 123, change(None, Some(None)))
+        .await
+        .unwrap();
+    assert_eq!(profile.display_name.as_deref(), Some("Alice"));
+    assert_eq!(profile.bio, None);
+    assert_eq!(users.get_profile(TENANT, 123).await.unwrap(), Some(profile));
+}
+
+async fn units_of_work_are_atomic(storage: Storage) {
+    let repository = storage.messages;
+    let post = |content: &str| message::PostMessage {
+        content: content.to_string(),
+        user_id: 123,
+        posted_at: at(0),
+        expires_at: None,
+    };
+
+    let mut unit = storage.units.begin(TENANT).await.unwrap();
+    let kept = unit.create_message(post("kept")).await.unwrap();
+    unit.update_message(kept, 123, "edited".to_string(), at(1))
+        .await
+        .unwrap();
+    assert!(unit.pin_message(kept, 123).await.unwrap());
+    unit.commit().await.unwrap();
+    let found = repository.get_message(TENANT, kept).await.unwrap();
+    assert_eq!(found.message_content, "edited");
+
+    let mut unit = storage.units.begin(TENANT).await.unwrap();
+    let rolled_back = unit.create_message(post("rolled back")).await.unwrap();
+    unit.delete_message(kept, 123, at(2)).await.unwrap();
+    unit.rollback().await.unwrap();
+    assert!(matches!(
+        repository.get_message(TENANT, rolled_back).await,
+        Err(DomainError::NotFound("message"))
+    ));
+    assert!(repository.get_message(TENANT, kept).await.is_ok());
+
+    let mut unit = storage.units.begin(TENANT).await.unwrap();
+    unit.create_message(post("dropped")).await.unwrap();
+    unit.delete_message(kept, 123, at(2)).await.unwrap();
+    drop(unit);
+    assert!(repository.get_message(TENANT, kept).await.is_ok());
+
+    let mut unit = storage.units.begin(TENANT).await.unwrap();
+    assert!(matches!(
+        unit.update_message(kept, 7, "x".to_string(), at(3)).await,
+        Err(DomainError::Forbidden)
+    ));
+    unit.rollback().await.unwrap();
+
+    let visible = repository
+        .count_messages(TENANT, Default::default())
+        .await
+        .unwrap();
+    assert_eq!(visible, 1);
+}
+
+/// Runs every case against a fresh storage.
+async fn check<F, Fut>(fresh: F)
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Storage>,
+{
+    list_order_and_pagination(fresh().await.messages).await;
+    ties_are_ordered_by_id(fresh().await.messages).await;
+    cursor_pages(fresh().await.messages).await;
+    sub_microsecond_cursors(fresh().await.messages).await;
+    filters(fresh().await.messages).await;
+    change_and_delete(fresh().await.messages).await;
+    import_skips_duplicates(fresh().await.messages).await;
+    version_tracks_changes(fresh().await.messages).await;
+    version_tracks_authors(fresh().await).await;
+    tenants_are_isolated(fresh().await).await;
+    profile_changes(fresh().await).await;
+    units_of_work_are_atomic(fresh().await).await;
+}
+
+#[tokio::test]
+async fn test_memory_messages_conformance() {
+    check(|| async {
+        let store = memory::MemoryStore::new();
+        Storage {
+            messages: Arc::new(memory::MemoryMessagesRepository::new(store.clone())),
+            tenants: Arc::new(memory::MemoryTenantsRepository::new(store.clone())),
+            users: Arc::new(memory::MemoryUsersRepository::new(store.clone())),
+            units: Arc::new(memory::MemoryUnitOfWorkFactory::new(store)),
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_cached_messages_conformance() {
+    check(|| async {
+        let store = memory::MemoryStore::new();
+        let inner = Arc::new(memory::MemoryMessagesRepository::new(store.clone()));
+        let cached = Arc::new(cache::CachedMessagesRepository::new(
+            inner,
+            std::time::Duration::from_secs(60),
+            100,
+        ));
+        Storage {
+            messages: cached.clone(),
+            tenants: Arc::new(memory::MemoryTenantsRepository::new(store.clone())),
+            users: Arc::new(memory::MemoryUsersRepository::new(store.clone())),
+            units: Arc::new(cache::CachedUnitOfWorkFactory::new(
+                Arc::new(memory::MemoryUnitOfWorkFactory::new(store)),
+                cached,
+            )),
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_sqlite_messages_conformance() {
+    check(|| async {
+        let store = sqlite::SqliteStore::open_in_memory().unwrap();
+        Storage {
+            messages: Arc::new(sqlite::SqliteMessagesRepository::new(store.clone())),
+            tenants: Arc::new(sqlite::SqliteTenantsRepository::new(store.clone())),
+            users: Arc::new(sqlite::SqliteUsersRepository::new(store.clone())),
+            units: Arc::new(sqlite::SqliteUnitOfWorkFactory::new(store)),
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_postgres_messages_conformance() {
+    let Ok(url) = std::env::var("TEST_POSTGRES_URL") else {
+        return;
+    };
+    let schema = format!("conformance_{}", &random_token()[..12]);
+    let pool = deadpool_postgres::Config {
+        url: Some(url),
+        options: Some(postgres::search_path_option(&schema)),
+        ..Default::default()
+    }
+    .create_pool(
+        Some(deadpool_postgres::Runtime::Tokio1),
+        tokio_postgres::NoTls,
+    )
+    .unwrap();
+    migrations::migrate(&pool, &schema).await.unwrap();
+
+    check(|| async {
+        pool.get()
+            .await
+            .unwrap()
+            .batch_execute(
+                // language=postgresql
+                "TRUNCATE messages, users RESTART IDENTITY CASCADE;",
+            )
+            .await
+            .unwrap();
+        Storage {
+            messages: Arc::new(repositories::MessagesRepository::new(pool.clone())),
+            tenants: Arc::new(repositories::TenantsRepository::new(pool.clone())),
+            users: Arc::new(repositories::UsersRepository::new(pool.clone())),
+            units: Arc::new(repositories::UnitOfWorkFactory::new(pool.clone())),
+        }
+    })
+    .await;
+
+    pool.get()
+        .await
+        .unwrap()
+        .batch_execute(&format!("DROP SCHEMA {schema} CASCADE;"))
+        .await
+        .unwrap();
+}
@@ -0,0 +1,33 @@
+use async_trait::async_trait;
+use deadpool_postgres::Pool;
+use mockall::*;
+
+use crate::domain::errors::DomainError;
+
+/// Dependency probed by readiness checks.
+#[async_trait]
+#[automock]
+pub trait DependencyCheckTrait: Send + Sync {
+    async fn check(&self) -> Result<(), DomainError>;
+}
+
+/// Checks out a pooled connection and runs a trivial query on it.
+pub struct PostgresCheck {
+    pool: Pool,
+}
+
+impl PostgresCheck {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl DependencyCheckTrait for PostgresCheck {
+    async fn check(&self) -> Result<(), DomainError> {
+        let client = self.pool.get().await?;
+        client.simple_query("SELECT 1;").await?;
+
+        Ok(())
+    }
+}
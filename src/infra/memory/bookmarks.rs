@@ -0,0 +1,110 @@
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::{
+    domain::{bookmark, errors::DomainError},
+    infra::{
+        memory::{MemoryStore, Tables},
+        repositories::bookmarks::BookmarksRepositoryTrait,
+    },
+};
+
+#[derive(Clone)]
+pub struct MemoryBookmarksRepository {
+    store: MemoryStore,
+}
+
+impl MemoryBookmarksRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+impl Tables {
+    pub(super) fn insert_bookmark(
+        &mut self,
+        tenant_id: i32,
+        user_id: i32,
+        message_id: i64,
+    ) -> bool {
+        let now = Utc::now();
+
+        if self.visible_message(tenant_id, message_id, now).is_none() {
+            return false;
+        }
+        self.bookmarks.entry((user_id, message_id)).or_insert(now);
+
+        true
+    }
+
+    pub(super) fn delete_bookmark(
+        &mut self,
+        tenant_id: i32,
+        user_id: i32,
+        message_id: i64,
+    ) -> bool {
+        let owned = self
+            .messages
+            .get(&message_id)
+            .is_some_and(|row| row.tenant_id == tenant_id);
+
+        owned && self.bookmarks.remove(&(user_id, message_id)).is_some()
+    }
+}
+
+#[async_trait]
+impl BookmarksRepositoryTrait for MemoryBookmarksRepository {
+    async fn add_bookmark(
+        &self,
+        tenant_id: i32,
+        user_id: i32,
+        message_id: i64,
+    ) -> Result<bool, DomainError> {
+        Ok(self
+            .store
+            .write()
+            .await
+            .insert_bookmark(tenant_id, user_id, message_id))
+    }
+
+    async fn remove_bookmark(
+        &self,
+        tenant_id: i32,
+        user_id: i32,
+        message_id: i64,
+    ) -> Result<bool, DomainError> {
+        Ok(self
+            .store
+            .write()
+            .await
+            .delete_bookmark(tenant_id, user_id, message_id))
+    }
+
+    async fn list_bookmarks(
+        &self,
+        tenant_id: i32,
+        user_id: i32,
+    ) -> Result<Vec<bookmark::Bookmark>, DomainError> {
+        let now = Utc::now();
+        let tables = self.store.read().await;
+
+        let mut bookmarks: Vec<_> = tables
+            .bookmarks
+            .iter()
+            .filter(|((owner_id, _), _)| *owner_id == user_id)
+            .filter_map(|(&(_, message_id), &bookmarked_at)| {
+                let row = tables.visible_message(tenant_id, message_id, now)?;
+                Some(bookmark::Bookmark {
+                    message_id,
+                    message_content: row.message_content.clone(),
+                    user_id: row.user_id,
+                    posted_at: row.posted_at,
+                    bookmarked_at,
+                })
+            })
+            .collect();
+        bookmarks.sort_by(|a, b| b.bookmarked_at.cmp(&a.bookmarked_at));
+
+        Ok(bookmarks)
+    }
+}
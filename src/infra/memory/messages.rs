@@ -0,0 +1,541 @@
+use std::cmp::Ordering;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures_util::{StreamExt, stream, stream::BoxStream};
+
+use crate::{
+    domain::{errors::DomainError, message},
+    infra::{
+        memory::{MemoryStore, MessageRow, Tables, to_micros},
+        repositories::messages::MessagesRepositoryTrait,
+    },
+};
+
+/// Memory counterpart of [`crate::infra::repositories::MessagesRepository`]. Webhook events
+/// are not recorded, since there is no worker to deliver them.
+#[derive(Clone)]
+pub struct MemoryMessagesRepository {
+    store: MemoryStore,
+}
+
+impl MemoryMessagesRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+impl Tables {
+    pub(super) fn insert_message(&mut self, tenant_id: i32, msg: message::PostMessage) -> i64 {
+        self.last_message_id += 1;
+        let message_id = self.last_message_id;
+        self.messages.insert(
+            message_id,
+            MessageRow {
+                tenant_id,
+                message_id,
+                external_id: None,
+                message_content: msg.content,
+                user_id: msg.user_id,
+                posted_at: to_micros(msg.posted_at),
+                expires_at: msg.expires_at.map(to_micros),
+                edited_at: None,
+                deleted_at: None,
+            },
+        );
+
+        message_id
+    }
+
+    pub(super) fn edit_message(
+        &mut self,
+        tenant_id: i32,
+        message_id: i64,
+        user_id: i32,
+        content: String,
+        edited_at: DateTime<Utc>,
+    ) -> Result<message::Message, DomainError> {
+        self.change_message(tenant_id, message_id, user_id, |row| {
+            row.message_content = content;
+            row.edited_at = Some(to_micros(edited_at));
+        })
+    }
+
+    pub(super) fn mark_message_deleted(
+        &mut self,
+        tenant_id: i32,
+        message_id: i64,
+        user_id: i32,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<message::Message, DomainError> {
+        self.change_message(tenant_id, message_id, user_id, |row| {
+            row.deleted_at = Some(to_micros(deleted_at));
+        })
+    }
+
+    /// Applies change to own visible message of the user.
+    fn change_message(
+        &mut self,
+        tenant_id: i32,
+        message_id: i64,
+        user_id: i32,
+        change: impl FnOnce(&mut MessageRow),
+    ) -> Result<message::Message, DomainError> {
+        let now = Utc::now();
+
+        let row = match self.messages.get_mut(&message_id) {
+            Some(row) if row.tenant_id == tenant_id && row.is_visible(now) => row,
+            _ => return Err(DomainError::NotFound("message")),
+        };
+        if row.user_id != user_id {
+            return Err(DomainError::Forbidden);
+        }
+
+        change(row);
+        let changed = row.to_message();
+        if row.deleted_at.is_some() {
+            self.forget_message_references(message_id);
+        }
+
+        Ok(changed)
+    }
+}
+
+#[async_trait]
+impl MessagesRepositoryTrait for MemoryMessagesRepository {
+    async fn create_message(
+        &self,
+        tenant_id: i32,
+        msg: message::PostMessage,
+    ) -> Result<i64, DomainError> {
+        let message_id = self.store.write().await.insert_message(tenant_id, msg);
+
+        Ok(message_id)
+    }
+
+    async fn update_message(
+        &self,
+        tenant_id: i32,
+        message_id: i64,
+        user_id: i32,
+        content: String,
+        edited_at: DateTime<Utc>,
+    ) -> Result<message::Message, DomainError> {
+        self.store
+            .write()
+            .await
+            .edit_message(tenant_id, message_id, user_id, content, edited_at)
+    }
+
+    async fn delete_message(
+        &self,
+        tenant_id: i32,
+        message_id: i64,
+        user_id: i32,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<message::Message, DomainError> {
+        self.store
+            .write()
+            .await
+            .mark_message_deleted(tenant_id, message_id, user_id, deleted_at)
+    }
+
+    async fn import_messages(
+        &self,
+        tenant_id: i32,
+        msgs: Vec<message::ImportMessage>,
+    ) -> Result<u64, DomainError> {
+        let mut tables = self.store.write().await;
+        let mut inserted = 0;
+
+        for msg in msgs {
+            let imported = tables.messages.values().any(|row| {
+                row.tenant_id == tenant_id && row.external_id.as_ref() == Some(&msg.external_id)
+            });
+            if imported {
+                continue;
+            }
+
+            tables.last_message_id += 1;
+            let message_id = tables.last_message_id;
+            tables.messages.insert(
+                message_id,
+                MessageRow {
+                    tenant_id,
+                    message_id,
+                    external_id: Some(msg.external_id),
+                    message_content: msg.content,
+                    user_id: msg.user_id,
+                    posted_at: to_micros(msg.posted_at),
+                    expires_at: None,
+                    edited_at: None,
+                    deleted_at: None,
+                },
+            );
+            inserted += 1;
+        }
+
+        Ok(inserted)
+    }
+
+    async fn list_messages(
+        &self,
+        tenant_id: i32,
+        filter: message::MessageFilter,
+        offset: i64,
+        limit: i64,
+    ) -> Result<Vec<message::AuthoredMessage>, DomainError> {
+        let tables = self.store.read().await;
+        let mut rows = matching_rows(&tables, tenant_id, &filter);
+        rows.sort_by(|a, b| listing_order(filter.order, a, b));
+
+        Ok(rows
+            .into_iter()
+            .skip(offset.max(0) as usize)
+            .take(limit.max(0) as usize)
+            .map(|row| authored(&tables, row))
+            .collect())
+    }
+
+    async fn list_messages_page(
+        &self,
+        tenant_id: i32,
+        filter: message::MessageFilter,
+        cursor: Option<message::Cursor>,
+        limit: i64,
+    ) -> Result<Vec<message::AuthoredMessage>, DomainError> {
+        let tables = self.store.read().await;
+        let mut rows = matching_rows(&tables, tenant_id, &filter);
+        rows.sort_by(|a, b| listing_order(filter.order, a, b));
+
+        let key = |row: &MessageRow| (row.posted_at, row.message_id);
+        let page: Vec<&MessageRow> = match cursor {
+            None => rows.into_iter().take(limit.max(0) as usize).collect(),
+            Some(cursor) => {
+                let position = (cursor.posted_at, cursor.message_id);
+                let follows = |row: &&MessageRow| match filter.order {
+                    message::SortOrder::Asc => key(row) > position,
+                    message::SortOrder::Desc => key(row) < position,
+                };
+
+                match cursor.direction {
+                    message::Direction::Next => rows
+                        .into_iter()
+                        .filter(follows)
+                        .take(limit.max(0) as usize)
+                        .collect(),
+                    message::Direction::Prev => {
+                        let mut page: Vec<_> = rows
+                            .into_iter()
+                            .rev()
+                            .filter(|row| key(row) != position && !follows(row))
+                            .take(limit.max(0) as usize)
+                            .collect();
+                        page.reverse();
+                        page
+                    }
+                }
+            }
+        };
+
+        Ok(page.into_iter().map(|row| authored(&tables, row)).collect())
+    }
+
+    async fn count_messages(
+        &self,
+        tenant_id: i32,
+        filter: message::MessageFilter,
+    ) -> Result<i64, DomainError> {
+        let tables = self.store.read().await;
+
+        Ok(matching_rows(&tables, tenant_id, &filter).len() as i64)
+    }
+
+    async fn messages_version(
+        &self,
+        tenant_id: i32,
+        filter: message::MessageFilter,
+    ) -> Result<message::ListingVersion, DomainError> {
+        let now = Utc::now();
+        let tables = self.store.read().await;
+        let rows: Vec<_> = tables
+            .messages
+            .values()
+            .filter(|row| row.tenant_id == tenant_id && matches_filter(row, &filter))
+            .collect();
+
+        Ok(message::ListingVersion {
+            max_message_id: rows.iter().map(|row| row.message_id).max(),
+            last_modified: rows
+                .iter()
+                .flat_map(|row| {
+                    let profile_updated_at = tables
+                        .users
+                        .get(&(row.tenant_id, row.user_id))
+                        .map(|user| user.updated_at);
+                    [
+                        Some(row.posted_at),
+                        row.edited_at,
+                        row.deleted_at,
+                        profile_updated_at,
+                    ]
+                })
+                .flatten()
+                .max(),
+            visible: rows.iter().filter(|row| row.is_visible(now)).count() as i64,
+        })
+    }
+
+    async fn get_message(
+        &self,
+        tenant_id: i32,
+        message_id: i64,
+    ) -> Result<message::AuthoredMessage, DomainError> {
+        let tables = self.store.read().await;
+
+        match tables.visible_message(tenant_id, message_id, Utc::now()) {
+            Some(row) => Ok(authored(&tables, row)),
+            None => Err(DomainError::NotFound("message")),
+        }
+    }
+
+    async fn get_messages(
+        &self,
+        tenant_id: i32,
+        message_ids: Vec<i64>,
+    ) -> Result<Vec<message::AuthoredMessage>, DomainError> {
+        let now = Utc::now();
+        let tables = self.store.read().await;
+
+        Ok(tables
+            .messages
+            .values()
+            .filter(|row| {
+                row.tenant_id == tenant_id
+                    && row.is_visible(now)
+                    && message_ids.contains(&row.message_id)
+            })
+            .map(|row| authored(&tables, row))
+            .collect())
+    }
+
+    fn stream_messages(
+        &self,
+        tenant_id: i32,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> BoxStream<'static, Result<message::Message, DomainError>> {
+        let filter = message::MessageFilter {
+            since,
+            until,
+            order: message::SortOrder::Asc,
+            ..Default::default()
+        };
+
+        let store = self.store.clone();
+        let messages = async move {
+            let tables = store.read().await;
+            let mut rows = matching_rows(&tables, tenant_id, &filter);
+            rows.sort_by(|a, b| listing_order(filter.order, a, b));
+            let messages: Vec<_> = rows.into_iter().map(|row| Ok(row.to_message())).collect();
+
+            stream::iter(messages)
+        };
+
+        stream::once(messages).flatten().boxed()
+    }
+
+    async fn count_retention_candidates(
+        &self,
+        before: DateTime<Utc>,
+        action: message::RetentionAction,
+    ) -> Result<i64, DomainError> {
+        let tables = self.store.read().await;
+
+        Ok(tables
+            .messages
+            .values()
+            .filter(|row| is_retention_candidate(row, before, action))
+            .count() as i64)
+    }
+
+    async fn apply_retention(
+        &self,
+        before: DateTime<Utc>,
+        action: message::RetentionAction,
+        limit: i64,
+    ) -> Result<u64, DomainError> {
+        let now = to_micros(Utc::now());
+        let mut tables = self.store.write().await;
+        let candidates: Vec<i64> = tables
+            .messages
+            .values()
+            .filter(|row| is_retention_candidate(row, before, action))
+            .take(limit.max(0) as usize)
+            .map(|row| row.message_id)
+            .collect();
+
+        for message_id in &candidates {
+            match action {
+                message::RetentionAction::Delete => {
+                    tables.messages.remove(message_id);
+                    tables.forget_message_references(*message_id);
+                }
+                message::RetentionAction::Anonymize => {
+                    if let Some(row) = tables.messages.get_mut(message_id) {
+                        row.user_id = message::ANONYMOUS_USER_ID;
+                        // moves listing versions, the author is part of the response
+                        row.edited_at = Some(now);
+                    }
+                }
+            }
+        }
+
+        Ok(candidates.len() as u64)
+    }
+
+    async fn delete_expired_messages(&self, limit: i64) -> Result<u64, DomainError> {
+        let now = Utc::now();
+        let mut tables = self.store.write().await;
+        let expired: Vec<i64> = tables
+            .messages
+            .values()
+            .filter(|row| row.expires_at.is_some_and(|expires_at| expires_at <= now))
+            .take(limit.max(0) as usize)
+            .map(|row| row.message_id)
+            .collect();
+
+        for message_id in &expired {
+            tables.messages.remove(message_id);
+            tables.forget_message_references(*message_id);
+        }
+
+        Ok(expired.len() as u64)
+    }
+}
+
+fn matching_rows<'a>(
+    tables: &'a Tables,
+    tenant_id: i32,
+    filter: &message::MessageFilter,
+) -> Vec<&'a MessageRow> {
+    let now = Utc::now();
+
+    tables
+        .messages
+        .values()
+        .filter(|row| {
+            row.tenant_id == tenant_id && row.is_visible(now) && matches_filter(row, filter)
+        })
+        .collect()
+}
+
+/// Mirrors conditions built by the postgres repository, text match is case-insensitive.
+fn matches_filter(row: &MessageRow, filter: &message::MessageFilter) -> bool {
+    filter.user_id.is_none_or(|user_id| row.user_id == user_id)
+        && filter.since.is_none_or(|since| row.posted_at >= since)
+        && filter.until.is_none_or(|until| row.posted_at < until)
+        && filter.text.as_ref().is_none_or(|text| {
+            let content = row.message_content.to_lowercase();
+            match text {
+                message::TextMatch::Contains(text) => content.contains(&text.to_lowercase()),
+                message::TextMatch::Prefix(text) => content.starts_with(&text.to_lowercase()),
+            }
+        })
+}
+
+fn listing_order(order: message::SortOrder, a: &MessageRow, b: &MessageRow) -> Ordering {
+    let ordering = (a.posted_at, a.message_id).cmp(&(b.posted_at, b.message_id));
+    match order {
+        message::SortOrder::Asc => ordering,
+        message::SortOrder::Desc => ordering.reverse(),
+    }
+}
+
+fn is_retention_candidate(
+    row: &MessageRow,
+    before: DateTime<Utc>,
+    action: message::RetentionAction,
+) -> bool {
+    row.posted_at < before
+        && (action == message::RetentionAction::Delete || row.user_id != message::ANONYMOUS_USER_ID)
+}
+
+fn authored(tables: &Tables, row: &MessageRow) -> message::AuthoredMessage {
+    let profile = tables
+        .users
+        .get(&(row.tenant_id, row.user_id))
+        .map(|user| &user.profile);
+
+    message::AuthoredMessage {
+        message_id: row.message_id,
+        message_content: row.message_content.clone(),
+        user_id: row.user_id,
+        posted_at: row.posted_at,
+        expires_at: row.expires_at,
+        display_name: profile.and_then(|profile| profile.display_name.clone()),
+        avatar_url: profile.and_then(|profile| profile.avatar_url.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{Router, body::Body, extract::Request, http};
+    use http_body_util::BodyExt;
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::api::{self, ApiRouterBuilder, State};
+
+    #[tokio::test]
+    async fn test_memory_messages_behind_api() {
+        let store = MemoryStore::new();
+        let state = State {
+            messages_repository: Arc::new(MemoryMessagesRepository::new(store.clone())),
+            users_repository: Arc::new(crate::infra::memory::MemoryUsersRepository::new(
+                store.clone(),
+            )),
+            unit_of_work: Arc::new(crate::infra::memory::MemoryUnitOfWorkFactory::new(store)),
+            ..State::mocked()
+        };
+        let app = Router::from(ApiRouterBuilder::new().with_state(Arc::from(state)).build());
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/api/v1/messages")
+                    .header(http::header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::from(
+                        serde_json::to_vec(&json!({ "text": "hello" })).unwrap(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::GET)
+                    .uri("/api/v1/messages/1")
+                    .header(http::header::AUTHORIZATION, api::generate_test_token())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["content"], "hello");
+        assert_eq!(body["author"]["user_id"], 123);
+    }
+}
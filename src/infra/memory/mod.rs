@@ -0,0 +1,146 @@
+//! Repositories keeping data in process memory, for local development without a database.
+//! Everything is lost on restart.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+use chrono::{DateTime, SubsecRound, Utc};
+use tokio::sync::{OwnedRwLockWriteGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::domain::{message, tenant, user};
+
+pub mod bookmarks;
+pub mod messages;
+pub mod pins;
+pub mod tenants;
+pub mod unit_of_work;
+pub mod users;
+
+pub use bookmarks::MemoryBookmarksRepository;
+pub use messages::MemoryMessagesRepository;
+pub use pins::MemoryPinsRepository;
+pub use tenants::MemoryTenantsRepository;
+pub use unit_of_work::MemoryUnitOfWorkFactory;
+pub use users::MemoryUsersRepository;
+
+/// Tables shared by memory repositories, the counterpart of a database.
+#[derive(Clone)]
+pub struct MemoryStore {
+    tables: Arc<RwLock<Tables>>,
+}
+
+impl Default for MemoryStore {
+    fn default() -> Self {
+        let default_tenant = tenant::Tenant {
+            tenant_id: tenant::DEFAULT_TENANT_ID,
+            name: "default".to_string(),
+            created_at: Utc::now(),
+            suspended_at: None,
+        };
+        let tables = Tables {
+            last_tenant_id: tenant::DEFAULT_TENANT_ID,
+            tenants: BTreeMap::from([(tenant::DEFAULT_TENANT_ID, default_tenant)]),
+            ..Default::default()
+        };
+
+        Self {
+            tables: Arc::new(RwLock::new(tables)),
+        }
+    }
+}
+
+impl MemoryStore {
+    /// Creates store holding the default tenant only.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    async fn read(&self) -> RwLockReadGuard<'_, Tables> {
+        self.tables.read().await
+    }
+
+    async fn write(&self) -> RwLockWriteGuard<'_, Tables> {
+        self.tables.write().await
+    }
+
+    /// Locks tables for writing until the guard is dropped, held by units of work.
+    async fn write_owned(&self) -> OwnedRwLockWriteGuard<Tables> {
+        self.tables.clone().write_owned().await
+    }
+}
+
+#[derive(Default)]
+struct Tables {
+    last_tenant_id: i32,
+    tenants: BTreeMap<i32, tenant::Tenant>,
+    last_message_id: i64,
+    messages: BTreeMap<i64, MessageRow>,
+    /// Profiles by `(tenant_id, user_id)`.
+    users: HashMap<(i32, i32), UserRow>,
+    /// Pinned message ids with moderator and pin time.
+    pins: HashMap<i64, (i32, DateTime<Utc>)>,
+    /// Bookmarked `(user_id, message_id)` with bookmark time.
+    bookmarks: HashMap<(i32, i64), DateTime<Utc>>,
+}
+
+impl Tables {
+    fn visible_message(
+        &self,
+        tenant_id: i32,
+        message_id: i64,
+        now: DateTime<Utc>,
+    ) -> Option<&MessageRow> {
+        self.messages
+            .get(&message_id)
+            .filter(|row| row.tenant_id == tenant_id && row.is_visible(now))
+    }
+
+    /// Drops pins and bookmarks of a message that is gone.
+    fn forget_message_references(&mut self, message_id: i64) {
+        self.pins.remove(&message_id);
+        self.bookmarks.retain(|(_, id), _| *id != message_id);
+    }
+}
+
+/// Drops precision below microseconds, which postgres and SQLite don't keep either, so that
+/// cursors carrying microseconds point at rows exactly.
+fn to_micros(at: DateTime<Utc>) -> DateTime<Utc> {
+    at.trunc_subsecs(6)
+}
+
+#[derive(Debug, Clone)]
+struct UserRow {
+    profile: user::UserProfile,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+struct MessageRow {
+    tenant_id: i32,
+    message_id: i64,
+    external_id: Option<String>,
+    message_content: String,
+    user_id: i32,
+    posted_at: DateTime<Utc>,
+    expires_at: Option<DateTime<Utc>>,
+    edited_at: Option<DateTime<Utc>>,
+    deleted_at: Option<DateTime<Utc>>,
+}
+
+impl MessageRow {
+    fn is_visible(&self, now: DateTime<Utc>) -> bool {
+        self.deleted_at.is_none() && self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+
+    fn to_message(&self) -> message::Message {
+        message::Message {
+            message_id: self.message_id,
+            message_content: self.message_content.clone(),
+            user_id: self.user_id,
+            posted_at: self.posted_at,
+            expires_at: self.expires_at,
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::{
+    domain::{bookmark, errors::DomainError},
+    infra::{
+        memory::{MemoryStore, Tables},
+        repositories::pins::PinsRepositoryTrait,
+    },
+};
+
+#[derive(Clone)]
+pub struct MemoryPinsRepository {
+    store: MemoryStore,
+}
+
+impl MemoryPinsRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+impl Tables {
+    pub(super) fn insert_pin(&mut self, tenant_id: i32, message_id: i64, pinned_by: i32) -> bool {
+        let now = Utc::now();
+
+        if self.visible_message(tenant_id, message_id, now).is_none() {
+            return false;
+        }
+        self.pins.entry(message_id).or_insert((pinned_by, now));
+
+        true
+    }
+
+    pub(super) fn delete_pin(&mut self, tenant_id: i32, message_id: i64) -> bool {
+        let owned = self
+            .messages
+            .get(&message_id)
+            .is_some_and(|row| row.tenant_id == tenant_id);
+
+        owned && self.pins.remove(&message_id).is_some()
+    }
+}
+
+#[async_trait]
+impl PinsRepositoryTrait for MemoryPinsRepository {
+    async fn pin_message(
+        &self,
+        tenant_id: i32,
+        message_id: i64,
+        pinned_by: i32,
+    ) -> Result<bool, DomainError> {
+        Ok(self
+            .store
+            .write()
+            .await
+            .insert_pin(tenant_id, message_id, pinned_by))
+    }
+
+    async fn unpin_message(&self, tenant_id: i32, message_id: i64) -> Result<bool, DomainError> {
+        Ok(self.store.write().await.delete_pin(tenant_id, message_id))
+    }
+
+    async fn list_pins(&self, tenant_id: i32) -> Result<Vec<bookmark::Pin>, DomainError> {
+        let now = Utc::now();
+        let tables = self.store.read().await;
+
+        let mut pins: Vec<_> = tables
+            .pins
+            .iter()
+            .filter_map(|(&message_id, &(pinned_by, pinned_at))| {
+                let row = tables.visible_message(tenant_id, message_id, now)?;
+                Some(bookmark::Pin {
+                    message_id,
+                    message_content: row.message_content.clone(),
+                    user_id: row.user_id,
+                    posted_at: row.posted_at,
+                    pinned_by,
+                    pinned_at,
+                })
+            })
+            .collect();
+        pins.sort_by(|a, b| b.pinned_at.cmp(&a.pinned_at));
+
+        Ok(pins)
+    }
+}
@@ -0,0 +1,49 @@
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::{
+    domain::{errors::DomainError, tenant},
+    infra::{memory::MemoryStore, repositories::tenants::TenantsRepositoryTrait},
+};
+
+#[derive(Clone)]
+pub struct MemoryTenantsRepository {
+    store: MemoryStore,
+}
+
+impl MemoryTenantsRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl TenantsRepositoryTrait for MemoryTenantsRepository {
+    async fn create_tenant(&self, name: String) -> Result<tenant::Tenant, DomainError> {
+        let mut tables = self.store.write().await;
+
+        tables.last_tenant_id += 1;
+        let created = tenant::Tenant {
+            tenant_id: tables.last_tenant_id,
+            name,
+            created_at: Utc::now(),
+            suspended_at: None,
+        };
+        tables.tenants.insert(created.tenant_id, created.clone());
+
+        Ok(created)
+    }
+
+    async fn get_tenant(&self, tenant_id: i32) -> Result<Option<tenant::Tenant>, DomainError> {
+        Ok(self.store.read().await.tenants.get(&tenant_id).cloned())
+    }
+
+    async fn suspend_tenant(&self, tenant_id: i32) -> Result<Option<tenant::Tenant>, DomainError> {
+        let mut tables = self.store.write().await;
+
+        Ok(tables.tenants.get_mut(&tenant_id).map(|row| {
+            row.suspended_at.get_or_insert_with(Utc::now);
+            row.clone()
+        }))
+    }
+}
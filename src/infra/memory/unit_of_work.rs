@@ -0,0 +1,186 @@
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::sync::OwnedRwLockWriteGuard;
+
+use crate::{
+    domain::{errors::DomainError, message},
+    infra::{
+        memory::{MemoryStore, MessageRow, Tables},
+        repositories::unit_of_work::{UnitOfWorkFactoryTrait, UnitOfWorkTrait},
+    },
+};
+
+/// Memory counterpart of [`crate::infra::repositories::UnitOfWorkFactory`]. Webhook events
+/// are not recorded, since there is no worker to deliver them.
+///
+/// A unit takes the write lock of the whole store, not only of its tenant, so units of all
+/// tenants run one at a time and block every read until they end. This is fine for the
+/// single process development backend, as long as units stay short.
+#[derive(Clone)]
+pub struct MemoryUnitOfWorkFactory {
+    store: MemoryStore,
+}
+
+impl MemoryUnitOfWorkFactory {
+    pub fn new(store: MemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl UnitOfWorkFactoryTrait for MemoryUnitOfWorkFactory {
+    async fn begin(&self, tenant_id: i32) -> Result<Box<dyn UnitOfWorkTrait>, DomainError> {
+        Ok(Box::new(MemoryUnitOfWork {
+            tables: Some(self.store.write_owned().await),
+            tenant_id,
+            saved: BTreeMap::new(),
+        }))
+    }
+}
+
+/// Holds the tables locked until commit or rollback and remembers every message it touches
+/// as it was before, so that rollback can put it back. Message ids are not reused after
+/// rollback, like sequences.
+pub struct MemoryUnitOfWork {
+    tables: Option<OwnedRwLockWriteGuard<Tables>>,
+    tenant_id: i32,
+    saved: BTreeMap<i64, SavedMessage>,
+}
+
+/// Message row with its pin and bookmarks before the unit changed them.
+struct SavedMessage {
+    row: Option<MessageRow>,
+    pin: Option<(i32, DateTime<Utc>)>,
+    bookmarks: Vec<((i32, i64), DateTime<Utc>)>,
+}
+
+impl MemoryUnitOfWork {
+    /// Saves the message before its first change and returns tables to change it in.
+    fn touch(&mut self, message_id: i64) -> Result<&mut Tables, DomainError> {
+        let tables = self.tables.as_deref_mut().ok_or_else(finished)?;
+
+        self.saved
+            .entry(message_id)
+            .or_insert_with(|| SavedMessage {
+                row: tables.messages.get(&message_id).cloned(),
+                pin: tables.pins.get(&message_id).copied(),
+                bookmarks: tables
+                    .bookmarks
+                    .iter()
+                    .filter(|((_, id), _)| *id == message_id)
+                    .map(|(&key, &bookmarked_at)| (key, bookmarked_at))
+                    .collect(),
+            });
+
+        Ok(tables)
+    }
+
+    fn restore(&mut self) {
+        let Some(mut tables) = self.tables.take() else {
+            return;
+        };
+
+        for (message_id, saved) in std::mem::take(&mut self.saved) {
+            match saved.row {
+                Some(row) => tables.messages.insert(message_id, row),
+                None => tables.messages.remove(&message_id),
+            };
+            match saved.pin {
+                Some(pin) => tables.pins.insert(message_id, pin),
+                None => tables.pins.remove(&message_id),
+            };
+            tables.bookmarks.retain(|(_, id), _| *id != message_id);
+            tables.bookmarks.extend(saved.bookmarks);
+        }
+    }
+}
+
+fn finished() -> DomainError {
+    DomainError::Internal(anyhow::anyhow!("unit of work is finished"))
+}
+
+impl Drop for MemoryUnitOfWork {
+    fn drop(&mut self) {
+        self.restore();
+    }
+}
+
+#[async_trait]
+impl UnitOfWorkTrait for MemoryUnitOfWork {
+    async fn create_message(&mut self, msg: message::PostMessage) -> Result<i64, DomainError> {
+        let tenant_id = self.tenant_id;
+        let last_message_id = self.tables.as_ref().ok_or_else(finished)?.last_message_id;
+        // nothing is saved for the next id yet, so rollback removes the message
+        Ok(self
+            .touch(last_message_id + 1)?
+            .insert_message(tenant_id, msg))
+    }
+
+    async fn update_message(
+        &mut self,
+        message_id: i64,
+        user_id: i32,
+        content: String,
+        edited_at: DateTime<Utc>,
+    ) -> Result<message::Message, DomainError> {
+        let tenant_id = self.tenant_id;
+        self.touch(message_id)?
+            .edit_message(tenant_id, message_id, user_id, content, edited_at)
+    }
+
+    async fn delete_message(
+        &mut self,
+        message_id: i64,
+        user_id: i32,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<message::Message, DomainError> {
+        let tenant_id = self.tenant_id;
+        self.touch(message_id)?
+            .mark_message_deleted(tenant_id, message_id, user_id, deleted_at)
+    }
+
+    async fn pin_message(&mut self, message_id: i64, pinned_by: i32) -> Result<bool, DomainError> {
+        let tenant_id = self.tenant_id;
+        Ok(self
+            .touch(message_id)?
+            .insert_pin(tenant_id, message_id, pinned_by))
+    }
+
+    async fn unpin_message(&mut self, message_id: i64) -> Result<bool, DomainError> {
+        let tenant_id = self.tenant_id;
+        Ok(self.touch(message_id)?.delete_pin(tenant_id, message_id))
+    }
+
+    async fn add_bookmark(&mut self, user_id: i32, message_id: i64) -> Result<bool, DomainError> {
+        let tenant_id = self.tenant_id;
+        Ok(self
+            .touch(message_id)?
+            .insert_bookmark(tenant_id, user_id, message_id))
+    }
+
+    async fn remove_bookmark(
+        &mut self,
+        user_id: i32,
+        message_id: i64,
+    ) -> Result<bool, DomainError> {
+        let tenant_id = self.tenant_id;
+        Ok(self
+            .touch(message_id)?
+            .delete_bookmark(tenant_id, user_id, message_id))
+    }
+
+    async fn commit(mut self: Box<Self>) -> Result<(), DomainError> {
+        self.saved.clear();
+        self.tables.take();
+
+        Ok(())
+    }
+
+    async fn rollback(mut self: Box<Self>) -> Result<(), DomainError> {
+        self.restore();
+
+        Ok(())
+    }
+}
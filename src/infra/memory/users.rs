@@ -0,0 +1,68 @@
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::{
+    domain::{errors::DomainError, user},
+    infra::{
+        memory::{MemoryStore, UserRow},
+        repositories::users::UsersRepositoryTrait,
+    },
+};
+
+#[derive(Clone)]
+pub struct MemoryUsersRepository {
+    store: MemoryStore,
+}
+
+impl MemoryUsersRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl UsersRepositoryTrait for MemoryUsersRepository {
+    async fn get_profile(
+        &self,
+        tenant_id: i32,
+        user_id: i32,
+    ) -> Result<Option<user::UserProfile>, DomainError> {
+        Ok(self
+            .store
+            .read()
+            .await
+            .users
+            .get(&(tenant_id, user_id))
+            .map(|user| user.profile.clone()))
+    }
+
+    async fn update_profile(
+        &self,
+        tenant_id: i32,
+        user_id: i32,
+        change: user::ProfileChange,
+    ) -> Result<user::UserProfile, DomainError> {
+        let mut tables = self.store.write().await;
+        let user = tables
+            .users
+            .entry((tenant_id, user_id))
+            .or_insert_with(|| UserRow {
+                profile: user::UserProfile::empty(user_id),
+                updated_at: Utc::now(),
+            });
+        user.updated_at = Utc::now();
+        let profile = &mut user.profile;
+
+        if let Some(display_name) = change.display_name {
+            profile.display_name = display_name;
+        }
+        if let Some(avatar_url) = change.avatar_url {
+            profile.avatar_url = avatar_url;
+        }
+        if let Some(bio) = change.bio {
+            profile.bio = bio;
+        }
+
+        Ok(profile.clone())
+    }
+}
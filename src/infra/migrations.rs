@@ -0,0 +1,285 @@
+//! Postgres migrations embedded into binaries.
+//!
+//! Applied versions are tracked in `schema_migrations` the same way `golang-migrate` does, so
+//! databases migrated by the `migrate/migrate` container keep working and vice versa.
+
+use anyhow::anyhow;
+use caslex_extra::storages::postgres_pool;
+use clap::Parser;
+use deadpool_postgres::Pool;
+
+use crate::infra::postgres;
+
+/// Migration from `migrations/`, applied inside its own transaction.
+pub struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    pub up: &'static str,
+}
+
+macro_rules! migration {
+    ($version:literal, $name:literal) => {
+        Migration {
+            version: $version,
+            name: $name,
+            up: include_str!(concat!("../../migrations/", $name, ".up.sql")),
+        }
+    };
+}
+
+pub const MIGRATIONS: &[Migration] = &[
+    migration!(1, "000001_init"),
+    migration!(2, "000002_message_import"),
+    migration!(3, "000003_webhooks"),
+    migration!(4, "000004_integrations"),
+    migration!(5, "000005_scheduled_messages"),
+    migration!(6, "000006_message_ttl"),
+    migration!(7, "000007_pins_bookmarks"),
+    migration!(8, "000008_users"),
+    migration!(9, "000009_harden_messages"),
+    migration!(10, "000010_tenants"),
+    migration!(11, "000011_tenant_users"),
+    migration!(12, "000012_tenant_pins_bookmarks"),
+    migration!(13, "000013_tenant_isolation"),
+];
+
+/// Define migration mode config. Other arguments are left to the configs of the binary.
+#[derive(Parser, Debug, Clone)]
+#[command(ignore_errors = true)]
+pub struct MigrateConfig {
+    /// Applies pending migrations and exits instead of starting the service.
+    #[arg(long)]
+    pub migrate: bool,
+}
+
+impl MigrateConfig {
+    pub fn parse() -> MigrateConfig {
+        MigrateConfig::try_parse().expect("Parsing configuration failed.")
+    }
+}
+
+/// Version recorded in `schema_migrations`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SchemaVersion {
+    pub version: Option<i64>,
+    pub dirty: bool,
+}
+
+impl SchemaVersion {
+    /// Rejects schema this binary was not built for.
+    pub fn verify(&self) -> anyhow::Result<()> {
+        let latest = latest_version();
+
+        match self.version {
+            _ if self.dirty => Err(anyhow!(
+                "schema version {} is dirty, a migration failed halfway and must be fixed manually",
+                self.version.unwrap_or_default()
+            )),
+            None => Err(anyhow!("schema is not migrated, run with --migrate")),
+            Some(version) if version > latest => Err(anyhow!(
+                "unknown schema version {version}, this build knows versions up to {latest}"
+            )),
+            Some(version) if version < latest => Err(anyhow!(
+                "schema version {version} is behind {latest}, run with --migrate"
+            )),
+            Some(_) => Ok(()),
+        }
+    }
+}
+
+pub fn latest_version() -> i64 {
+    MIGRATIONS.last().map_or(0, |migration| migration.version)
+}
+
+/// Migrates database configured by `POSTGRES_*` variables, the `--migrate` mode of binaries.
+pub async fn run() -> anyhow::Result<()> {
+    // configs know nothing about `--migrate`, so they are read from env only
+    let config = postgres_pool::Config::try_parse_from([env!("CARGO_PKG_NAME")])?;
+    let schema = postgres::SchemaConfig::try_parse_from([env!("CARGO_PKG_NAME")])?;
+    let pool = postgres::build_pool(config, &schema)
+        .await
+        .map_err(|err| anyhow!("failed to create pool: {:?}", err))?;
+
+    let applied = migrate(&pool, &schema.schema).await;
+    pool.close();
+
+    tracing::info!(
+        "Schema {} is at version {}, applied {} migrations",
+        schema.schema,
+        latest_version(),
+        applied?
+    );
+
+    Ok(())
+}
+
+/// Fails unless the schema is exactly at the latest embedded version.
+pub async fn check_version(pool: &Pool) -> anyhow::Result<()> {
+    let client = pool.get().await?;
+    let version = current_version(&client).await?;
+
+    version.verify()
+}
+
+/// Creates the schema of the pool when missing and applies pending migrations, returns the
+/// number of applied ones. Replicas started together migrate once, guarded by an advisory lock.
+pub async fn migrate(pool: &Pool, schema: &str) -> anyhow::Result<usize> {
+    let mut client = pool.get().await?;
+    let lock_key = format!("schema_migrations:{schema}");
+
+    client
+        .execute(
+            "SELECT pg_advisory_lock(hashtextextended($1, 0));",
+            &[&lock_key],
+        )
+        .await?;
+
+    let applied = apply_pending(&mut client, schema).await;
+
+    client
+        .execute(
+            "SELECT pg_advisory_unlock(hashtextextended($1, 0));",
+            &[&lock_key],
+        )
+        .await?;
+
+    applied
+}
+
+async fn apply_pending(
+    client: &mut deadpool_postgres::Client,
+    schema: &str,
+) -> anyhow::Result<usize> {
+    client
+        .batch_execute(&format!(
+            // language=postgresql
+            r#"
+            CREATE SCHEMA IF NOT EXISTS {schema};
+
+            CREATE TABLE IF NOT EXISTS schema_migrations
+            (
+                version bigint  NOT NULL PRIMARY KEY,
+                dirty   boolean NOT NULL
+            );
+            "#
+        ))
+        .await?;
+
+    let current = current_version(client).await?;
+    if current.dirty || current.version > Some(latest_version()) {
+        current.verify()?;
+    }
+
+    let mut applied = 0;
+    for migration in MIGRATIONS
+        .iter()
+        .filter(|migration| Some(migration.version) > current.version)
+    {
+        set_version(client, migration.version, true).await?;
+        client
+            .batch_execute(migration.up)
+            .await
+            .map_err(|err| anyhow!("migration {} failed: {}", migration.name, err))?;
+        set_version(client, migration.version, false).await?;
+
+        tracing::info!("Applied migration {}", migration.name);
+        applied += 1;
+    }
+
+    Ok(applied)
+}
+
+async fn current_version(client: &deadpool_postgres::Client) -> anyhow::Result<SchemaVersion> {
+    let row = client
+        .query_one(
+            // language=postgresql
+            r#"
+            SELECT to_regclass('schema_migrations') IS NOT NULL AS tracked;
+            "#,
+            &[],
+        )
+        .await?;
+    if !row.get::<_, bool>("tracked") {
+        return Ok(SchemaVersion {
+            version: None,
+            dirty: false,
+        });
+    }
+
+    let row = client
+        .query_opt(
+            // language=postgresql
+            r#"
+            SELECT version AS version,
+                   dirty   AS dirty
+            FROM schema_migrations
+            LIMIT 1;
+            "#,
+            &[],
+        )
+        .await?;
+
+    Ok(match row {
+        Some(row) => SchemaVersion {
+            version: Some(row.get("version")),
+            dirty: row.get("dirty"),
+        },
+        None => SchemaVersion {
+            version: None,
+            dirty: false,
+        },
+    })
+}
+
+/// Replaces the single row of `schema_migrations`, as `golang-migrate` does.
+async fn set_version(
+    client: &mut deadpool_postgres::Client,
+    version: i64,
+    dirty: bool,
+) -> anyhow::Result<()> {
+    let tx = client.transaction().await?;
+    tx.batch_execute("TRUNCATE schema_migrations;").await?;
+    tx.execute(
+        // language=postgresql
+        "INSERT INTO schema_migrations (version, dirty) VALUES ($1, $2);",
+        &[&version, &dirty],
+    )
+    .await?;
+    tx.commit().await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema(version: Option<i64>, dirty: bool) -> SchemaVersion {
+        SchemaVersion { version, dirty }
+    }
+
+    #[test]
+    fn test_migrations_are_ordered() {
+        for (index, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.version, index as i64 + 1);
+            assert!(
+                migration
+                    .name
+                    .starts_with(&format!("{:06}_", migration.version))
+            );
+        }
+    }
+
+    #[test]
+    fn test_verify_schema_version() {
+        let latest = latest_version();
+
+        assert!(schema(Some(latest), false).verify().is_ok());
+        assert!(schema(None, false).verify().is_err());
+        assert!(schema(Some(latest), true).verify().is_err());
+        assert!(schema(Some(latest - 1), false).verify().is_err());
+
+        let unknown = schema(Some(latest + 1), false).verify().unwrap_err();
+        assert!(unknown.to_string().contains("unknown schema version"));
+    }
+}
@@ -1 +1,11 @@
+pub mod cache;
+#[cfg(test)]
+mod conformance;
+pub mod health;
+pub mod memory;
+pub mod migrations;
+pub mod postgres;
 pub mod repositories;
+pub mod sqlite;
+pub mod storage;
+pub mod unsupported;
@@ -0,0 +1,434 @@
+//! Postgres pool whose connections work inside the configured schema.
+//!
+//! Statements and migrations use unqualified table names, `search_path` of every connection
+//! points them to the schema. Several deployments can share one database this way.
+//!
+//! Reads may go to a replica set up by [`ReplicaConfig`], see [`Pools`].
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use anyhow::anyhow;
+use caslex_extra::storages::postgres_pool;
+use clap::Parser;
+use deadpool_postgres::{Object, Pool, PoolError};
+
+/// Define schema config.
+#[derive(Parser, Debug, Clone)]
+pub struct SchemaConfig {
+    /// Schema holding chat tables, created by `--migrate` when missing. Env variable name:
+    /// `POSTGRES_SCHEMA`.
+    #[arg(long, env = "POSTGRES_SCHEMA", default_value = "rust_simple_chat", value_parser = parse_schema)]
+    pub schema: String,
+}
+
+impl SchemaConfig {
+    pub fn parse() -> SchemaConfig {
+        SchemaConfig::try_parse().expect("Parsing configuration failed.")
+    }
+}
+
+/// Define read replica config. Unset connection settings are taken from the primary.
+#[derive(Parser, Debug, Clone)]
+pub struct ReplicaConfig {
+    /// Replica host, reads stay on the primary when unset. Env variable name:
+    /// `POSTGRES_REPLICA_HOST`.
+    #[arg(long = "replica-host", env = "POSTGRES_REPLICA_HOST")]
+    pub host: Option<String>,
+    /// Env variable name: `POSTGRES_REPLICA_PORT`.
+    #[arg(long = "replica-port", env = "POSTGRES_REPLICA_PORT")]
+    pub port: Option<u16>,
+    /// Env variable name: `POSTGRES_REPLICA_USER`.
+    #[arg(long = "replica-user", env = "POSTGRES_REPLICA_USER")]
+    pub user: Option<String>,
+    /// Env variable name: `POSTGRES_REPLICA_PASSWORD`.
+    #[arg(long = "replica-password", env = "POSTGRES_REPLICA_PASSWORD")]
+    pub password: Option<String>,
+    /// Env variable name: `POSTGRES_REPLICA_DB`.
+    #[arg(long = "replica-db", env = "POSTGRES_REPLICA_DB")]
+    pub db: Option<String>,
+    /// Env variable name: `POSTGRES_REPLICA_CONNECT_TIMEOUT`.
+    #[arg(
+        long = "replica-connect-timeout",
+        env = "POSTGRES_REPLICA_CONNECT_TIMEOUT"
+    )]
+    pub connect_timeout: Option<humantime::Duration>,
+    /// Env variable name: `POSTGRES_REPLICA_MAX_CONNECTIONS`.
+    #[arg(
+        long = "replica-max-connections",
+        env = "POSTGRES_REPLICA_MAX_CONNECTIONS"
+    )]
+    pub max_connections: Option<usize>,
+    /// Env variable name: `POSTGRES_REPLICA_WAIT_TIMEOUT`.
+    #[arg(long = "replica-wait-timeout", env = "POSTGRES_REPLICA_WAIT_TIMEOUT")]
+    pub wait_timeout: Option<humantime::Duration>,
+    /// Reads of a tenant stay on the primary this long after its writes, covering replication
+    /// lag. Env variable name: `POSTGRES_REPLICA_MAX_LAG`.
+    #[arg(
+        long = "replica-max-lag",
+        env = "POSTGRES_REPLICA_MAX_LAG",
+        default_value = "5s"
+    )]
+    pub max_lag: humantime::Duration,
+    /// Pause of replica reads after it failed to give a connection. Env variable name:
+    /// `POSTGRES_REPLICA_RETRY_INTERVAL`.
+    #[arg(
+        long = "replica-retry-interval",
+        env = "POSTGRES_REPLICA_RETRY_INTERVAL",
+        default_value = "30s"
+    )]
+    pub retry_interval: humantime::Duration,
+}
+
+impl ReplicaConfig {
+    pub fn parse() -> ReplicaConfig {
+        ReplicaConfig::try_parse().expect("Parsing configuration failed.")
+    }
+
+    /// Returns pool config of the replica, or `None` when no replica is configured.
+    pub fn pool_config(&self, primary: &postgres_pool::Config) -> Option<postgres_pool::Config> {
+        let host = self.host.clone()?;
+
+        Some(postgres_pool::Config {
+            host,
+            port: self.port.unwrap_or(primary.port),
+            user: self.user.clone().unwrap_or_else(|| primary.user.clone()),
+            password: self
+                .password
+                .clone()
+                .unwrap_or_else(|| primary.password.clone()),
+            db: self.db.clone().unwrap_or_else(|| primary.db.clone()),
+            connect_timeout: self.connect_timeout.unwrap_or(primary.connect_timeout),
+            max_connections: self.max_connections.unwrap_or(primary.max_connections),
+            wait_timeout: self.wait_timeout.unwrap_or(primary.wait_timeout),
+            target_session_attrs: "any".to_string(),
+            ..primary.clone()
+        })
+    }
+}
+
+/// Accepts plain identifiers only, the name goes into connection options and DDL unquoted.
+fn parse_schema(schema: &str) -> Result<String, String> {
+    let mut chars = schema.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|ch| ch.is_ascii_lowercase() || ch == '_')
+        && chars.all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '_')
+        && schema.len() <= 63;
+
+    if valid {
+        Ok(schema.to_string())
+    } else {
+        Err("expected lowercase letters, digits and underscores".to_string())
+    }
+}
+
+/// Connection option making unqualified names resolve in the schema.
+pub fn search_path_option(schema: &str) -> String {
+    format!("-c search_path={schema}")
+}
+
+/// Builds pool the same way as [`postgres_pool::build_pool_from_config`], bound to the schema.
+pub async fn build_pool(
+    config: postgres_pool::Config,
+    schema: &SchemaConfig,
+) -> anyhow::Result<Pool> {
+    let pool = create_pool(&config, schema)?;
+
+    // ping db
+    let _ = pool.get().await.map_err(|err| {
+        anyhow!(
+            "Failed get postgres connection {} addr: {}:{} db:{} schema:{}",
+            err,
+            config.host,
+            config.port,
+            config.db,
+            schema.schema
+        )
+    })?;
+
+    Ok(pool)
+}
+
+/// Creates pool without connecting, e.g. for a replica allowed to be down at startup.
+pub fn create_pool(config: &postgres_pool::Config, schema: &SchemaConfig) -> anyhow::Result<Pool> {
+    let target_session_attrs = match config.target_session_attrs.as_str() {
+        "rw" => deadpool_postgres::TargetSessionAttrs::ReadWrite,
+        _ => deadpool_postgres::TargetSessionAttrs::Any,
+    };
+
+    let conn_opts = deadpool_postgres::Config {
+        application_name: Some(env!("CARGO_PKG_NAME").to_string()),
+        host: Some(config.host.clone()),
+        port: Some(config.port),
+        user: Some(config.user.clone()),
+        password: Some(config.password.clone()),
+        dbname: Some(config.db.clone()),
+        options: Some(search_path_option(&schema.schema)),
+        connect_timeout: Some(Duration::from(config.connect_timeout)),
+        keepalives: Some(config.keepalives),
+        keepalives_idle: Some(Duration::from(config.keepalives_idle)),
+        target_session_attrs: Some(target_session_attrs),
+        manager: Some(deadpool_postgres::ManagerConfig {
+            recycling_method: deadpool_postgres::RecyclingMethod::Fast,
+        }),
+        pool: Some(deadpool_postgres::PoolConfig {
+            max_size: config.max_connections,
+            timeouts: deadpool_postgres::Timeouts {
+                wait: Some(Duration::from(config.wait_timeout)),
+                create: Some(Duration::from(config.create_timeout)),
+                ..Default::default()
+            },
+            queue_mode: Default::default(),
+        }),
+        ..Default::default()
+    };
+
+    conn_opts
+        .create_pool(
+            Some(deadpool_postgres::Runtime::Tokio1),
+            tokio_postgres::NoTls,
+        )
+        .map_err(|err| anyhow!("Failed create postgres pool {}", err))
+}
+
+tokio::task_local! {
+    static PRIMARY_READS: bool;
+}
+
+/// Runs `fut` with every read sent to the primary, for callers that must see their own writes.
+pub async fn with_primary_reads<F: Future>(fut: F) -> F::Output {
+    PRIMARY_READS.scope(true, fut).await
+}
+
+/// Pool serving a read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Route {
+    Primary,
+    Replica,
+}
+
+/// Primary pool with an optional read replica.
+///
+/// Reads go to the replica unless the replica recently failed, the tenant wrote through this
+/// process within the replica lag, or the caller asked for primary reads.
+#[derive(Clone)]
+pub struct Pools {
+    primary: Pool,
+    replica: Option<Arc<Replica>>,
+}
+
+struct Replica {
+    pool: Pool,
+    max_lag: Duration,
+    retry_interval: Duration,
+    state: Mutex<ReplicaState>,
+}
+
+#[derive(Default)]
+struct ReplicaState {
+    unhealthy_until: Option<Instant>,
+    /// Tenants reading from the primary until the time.
+    recent_writes: HashMap<i32, Instant>,
+}
+
+impl Pools {
+    pub fn new(primary: Pool) -> Self {
+        Self {
+            primary,
+            replica: None,
+        }
+    }
+
+    pub fn with_replica(self, pool: Pool, config: &ReplicaConfig) -> Self {
+        Self {
+            replica: Some(Arc::new(Replica {
+                pool,
+                max_lag: config.max_lag.into(),
+                retry_interval: config.retry_interval.into(),
+                state: Mutex::default(),
+            })),
+            ..self
+        }
+    }
+
+    pub fn primary(&self) -> &Pool {
+        &self.primary
+    }
+
+    /// Connection for writes and reads of data just written.
+    pub async fn write(&self, tenant_id: i32) -> Result<Object, PoolError> {
+        self.record_write(tenant_id);
+        self.primary.get().await
+    }
+
+    fn record_write(&self, tenant_id: i32) {
+        if let Some(replica) = &self.replica {
+            let now = Instant::now();
+            let mut state = replica.state();
+            state.recent_writes.retain(|_, until| *until > now);
+            state.recent_writes.insert(tenant_id, now + replica.max_lag);
+        }
+    }
+
+    /// Connection for reads tolerating replication lag.
+    pub async fn read(&self, tenant_id: i32) -> Result<Object, PoolError> {
+        self.get(self.read_route(tenant_id)).await
+    }
+
+    /// Picks pool for reads of the tenant, a stream keeps it for all of its queries.
+    pub fn read_route(&self, tenant_id: i32) -> Route {
+        let Some(replica) = &self.replica else {
+            return Route::Primary;
+        };
+        if PRIMARY_READS.try_with(|primary| *primary).unwrap_or(false) {
+            return Route::Primary;
+        }
+
+        let now = Instant::now();
+        let state = replica.state();
+        let healthy = state.unhealthy_until.is_none_or(|until| until <= now);
+        let lagging = state
+            .recent_writes
+            .get(&tenant_id)
+            .is_some_and(|until| *until > now);
+
+        if healthy && !lagging {
+            Route::Replica
+        } else {
+            Route::Primary
+        }
+    }
+
+    /// Connection of the route, falls back to the primary when the replica fails.
+    pub async fn get(&self, route: Route) -> Result<Object, PoolError> {
+        if let (Route::Replica, Some(replica)) = (route, &self.replica) {
+            match replica.pool.get().await {
+                Ok(client) => return Ok(client),
+                Err(err) => {
+                    tracing::warn!("replica unavailable, reading from primary: {}", err);
+                    replica.state().unhealthy_until = Some(Instant::now() + replica.retry_interval);
+                }
+            }
+        }
+        self.primary.get().await
+    }
+}
+
+impl Replica {
+    fn state(&self) -> std::sync::MutexGuard<'_, ReplicaState> {
+        // state holds hints only, a panicked holder can't corrupt reads
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replica_config(host: Option<&str>) -> ReplicaConfig {
+        ReplicaConfig::try_parse_from(
+            ["test", "--replica-port", "1"]
+                .into_iter()
+                .chain(host.iter().flat_map(|host| ["--replica-host", host])),
+        )
+        .unwrap()
+    }
+
+    fn unconnected_pool(port: u16) -> Pool {
+        deadpool_postgres::Config {
+            host: Some("127.0.0.1".to_string()),
+            port: Some(port),
+            user: Some("user".to_string()),
+            dbname: Some("chat".to_string()),
+            ..Default::default()
+        }
+        .create_pool(
+            Some(deadpool_postgres::Runtime::Tokio1),
+            tokio_postgres::NoTls,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_replica_pool_config() {
+        let primary = postgres_pool::Config::try_parse_from([
+            "test",
+            "--host",
+            "primary",
+            "--user",
+            "user",
+            "--password",
+            "password",
+            "--db",
+            "chat",
+        ])
+        .unwrap();
+
+        assert!(replica_config(None).pool_config(&primary).is_none());
+
+        let replica = replica_config(Some("replica"))
+            .pool_config(&primary)
+            .unwrap();
+        assert_eq!(replica.host, "replica");
+        assert_eq!(replica.port, 1);
+        assert_eq!(replica.user, "user");
+        assert_eq!(replica.db, "chat");
+    }
+
+    #[tokio::test]
+    async fn test_pools_read_route() {
+        let primary_only = Pools::new(unconnected_pool(1));
+        assert_eq!(primary_only.read_route(1), Route::Primary);
+
+        let pools = Pools::new(unconnected_pool(1))
+            .with_replica(unconnected_pool(2), &replica_config(Some("replica")));
+        assert_eq!(pools.read_route(1), Route::Replica);
+
+        pools.record_write(1);
+        assert_eq!(pools.read_route(1), Route::Primary);
+        assert_eq!(pools.read_route(2), Route::Replica);
+
+        let route = with_primary_reads(async { pools.read_route(2) }).await;
+        assert_eq!(route, Route::Primary);
+    }
+
+    #[tokio::test]
+    async fn test_pools_fall_back_to_primary() {
+        let Ok(url) = std::env::var("TEST_POSTGRES_URL") else {
+            return;
+        };
+        let primary = deadpool_postgres::Config {
+            url: Some(url),
+            ..Default::default()
+        }
+        .create_pool(
+            Some(deadpool_postgres::Runtime::Tokio1),
+            tokio_postgres::NoTls,
+        )
+        .unwrap();
+        // nothing listens on port 1, connecting fails right away
+        let pools =
+            Pools::new(primary).with_replica(unconnected_pool(1), &replica_config(Some("replica")));
+
+        let client = pools.read(1).await.unwrap();
+        client.simple_query("SELECT 1").await.unwrap();
+        assert_eq!(pools.read_route(1), Route::Primary);
+    }
+
+    #[test]
+    fn test_parse_schema() {
+        assert!(parse_schema("rust_simple_chat").is_ok());
+        assert!(parse_schema("_tenant42").is_ok());
+
+        assert!(parse_schema("").is_err());
+        assert!(parse_schema("42tenant").is_err());
+        assert!(parse_schema("Chat").is_err());
+        assert!(parse_schema("chat; DROP TABLE messages").is_err());
+        assert!(parse_schema(&"a".repeat(64)).is_err());
+    }
+}
@@ -0,0 +1,168 @@
+use async_trait::async_trait;
+use deadpool_postgres::{GenericClient, Pool};
+use mockall::*;
+
+use crate::{
+    domain::{bookmark, errors::DomainError},
+    infra::{postgres::Pools, repositories::tenants::tenant_transaction},
+};
+
+#[async_trait]
+#[automock]
+pub trait BookmarksRepositoryTrait: Send + Sync {
+    /// Saves visible message for the user, saving twice is a no-op. Returns `false` when the
+    /// message does not exist.
+    async fn add_bookmark(
+        &self,
+        tenant_id: i32,
+        user_id: i32,
+        message_id: i64,
+    ) -> Result<bool, DomainError>;
+    async fn remove_bookmark(
+        &self,
+        tenant_id: i32,
+        user_id: i32,
+        message_id: i64,
+    ) -> Result<bool, DomainError>;
+    async fn list_bookmarks(
+        &self,
+        tenant_id: i32,
+        user_id: i32,
+    ) -> Result<Vec<bookmark::Bookmark>, DomainError>;
+}
+
+#[derive(Clone)]
+pub struct BookmarksRepository {
+    pools: Pools,
+}
+
+impl BookmarksRepository {
+    pub fn new(pool: Pool) -> Self {
+        Self::with_pools(Pools::new(pool))
+    }
+
+    /// Creates repository reading from the replica of `pools` when there is one.
+    pub fn with_pools(pools: Pools) -> Self {
+        Self { pools }
+    }
+}
+
+#[async_trait]
+impl BookmarksRepositoryTrait for BookmarksRepository {
+    async fn add_bookmark(
+        &self,
+        tenant_id: i32,
+        user_id: i32,
+        message_id: i64,
+    ) -> Result<bool, DomainError> {
+        let mut client = self.pools.write(tenant_id).await?;
+        let tx = tenant_transaction(&mut client, tenant_id).await?;
+        let added = insert_bookmark(&tx, tenant_id, user_id, message_id).await?;
+        tx.commit().await?;
+
+        Ok(added)
+    }
+
+    async fn remove_bookmark(
+        &self,
+        tenant_id: i32,
+        user_id: i32,
+        message_id: i64,
+    ) -> Result<bool, DomainError> {
+        let mut client = self.pools.write(tenant_id).await?;
+        let tx = tenant_transaction(&mut client, tenant_id).await?;
+        let deleted = delete_bookmark(&tx, tenant_id, user_id, message_id).await?;
+        tx.commit().await?;
+
+        Ok(deleted)
+    }
+
+    async fn list_bookmarks(
+        &self,
+        tenant_id: i32,
+        user_id: i32,
+    ) -> Result<Vec<bookmark::Bookmark>, DomainError> {
+        let mut client = self.pools.read(tenant_id).await?;
+        let tx = tenant_transaction(&mut client, tenant_id).await?;
+        let stmt = tx
+            .prepare_cached(
+                // language=postgresql
+                r#"
+                SELECT m.message_id      AS message_id,
+                       m.message_content AS message_content,
+                       m.user_id         AS user_id,
+                       m.posted_at       AS posted_at,
+                       b.bookmarked_at   AS bookmarked_at
+                FROM bookmarks b
+                         JOIN messages m ON m.message_id = b.message_id
+                WHERE m.tenant_id = $1
+                  AND b.user_id = $2
+                  AND m.deleted_at IS NULL
+                  AND (m.expires_at IS NULL OR m.expires_at > now())
+                ORDER BY b.bookmarked_at DESC;
+                "#,
+            )
+            .await?;
+
+        let rows = tx.query(&stmt, &[&tenant_id, &user_id]).await?;
+        tx.commit().await?;
+
+        Ok(rows.iter().map(bookmark::Bookmark::from).collect())
+    }
+}
+
+/// Bookmarks a visible message, returns false when there is no such message.
+pub(crate) async fn insert_bookmark(
+    client: &impl GenericClient,
+    tenant_id: i32,
+    user_id: i32,
+    message_id: i64,
+) -> Result<bool, DomainError> {
+    let stmt = client
+        .prepare_cached(
+            // language=postgresql
+            r#"
+            INSERT INTO bookmarks (tenant_id, user_id, message_id)
+            SELECT tenant_id, $2, message_id
+            FROM messages
+            WHERE tenant_id = $1
+              AND message_id = $3
+              AND deleted_at IS NULL
+              AND (expires_at IS NULL OR expires_at > now())
+            ON CONFLICT (user_id, message_id) DO UPDATE SET message_id = excluded.message_id;
+            "#,
+        )
+        .await?;
+
+    let added = client
+        .execute(&stmt, &[&tenant_id, &user_id, &message_id])
+        .await?;
+
+    Ok(added > 0)
+}
+
+/// Removes a bookmark, returns false when there was none.
+pub(crate) async fn delete_bookmark(
+    client: &impl GenericClient,
+    tenant_id: i32,
+    user_id: i32,
+    message_id: i64,
+) -> Result<bool, DomainError> {
+    let stmt = client
+        .prepare_cached(
+            // language=postgresql
+            r#"
+            DELETE FROM bookmarks
+            WHERE tenant_id = $1
+              AND user_id = $2
+              AND message_id = $3;
+            "#,
+        )
+        .await?;
+
+    let deleted = client
+        .execute(&stmt, &[&tenant_id, &user_id, &message_id])
+        .await?;
+
+    Ok(deleted > 0)
+}
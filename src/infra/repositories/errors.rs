@@ -0,0 +1,54 @@
+use deadpool_postgres::PoolError;
+use tokio_postgres::error::SqlState;
+
+use crate::domain::errors::DomainError;
+
+impl From<tokio_postgres::Error> for DomainError {
+    fn from(err: tokio_postgres::Error) -> Self {
+        let Some(code) = err.code() else {
+            // no server code means the connection itself failed
+            return if err.is_closed() || source_is_io(&err) {
+                DomainError::Unavailable(err.to_string())
+            } else {
+                DomainError::Internal(err.into())
+            };
+        };
+
+        if *code == SqlState::UNIQUE_VIOLATION
+            || *code == SqlState::EXCLUSION_VIOLATION
+            || *code == SqlState::FOREIGN_KEY_VIOLATION
+            || *code == SqlState::T_R_SERIALIZATION_FAILURE
+            || *code == SqlState::T_R_DEADLOCK_DETECTED
+        {
+            let constraint = err
+                .as_db_error()
+                .and_then(|db| db.constraint())
+                .unwrap_or(code.code());
+            return DomainError::Conflict(format!("{constraint} violated"));
+        }
+
+        if code.code().starts_with("08")
+            || *code == SqlState::ADMIN_SHUTDOWN
+            || *code == SqlState::CRASH_SHUTDOWN
+            || *code == SqlState::CANNOT_CONNECT_NOW
+            || *code == SqlState::TOO_MANY_CONNECTIONS
+        {
+            return DomainError::Unavailable(err.to_string());
+        }
+
+        DomainError::Internal(err.into())
+    }
+}
+
+impl From<PoolError> for DomainError {
+    fn from(err: PoolError) -> Self {
+        match err {
+            PoolError::Backend(err) => err.into(),
+            err => DomainError::Unavailable(err.to_string()),
+        }
+    }
+}
+
+fn source_is_io(err: &tokio_postgres::Error) -> bool {
+    std::error::Error::source(err).is_some_and(|source| source.is::<std::io::Error>())
+}
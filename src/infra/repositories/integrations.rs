@@ -0,0 +1,202 @@
+use async_trait::async_trait;
+use deadpool_postgres::Pool;
+use mockall::*;
+
+use crate::{
+    domain::{errors::DomainError, integration},
+    infra::repositories::tenants::{all_tenants_transaction, tenant_transaction},
+};
+
+#[async_trait]
+#[automock]
+pub trait IntegrationsRepositoryTrait: Send + Sync {
+    async fn create_integration(
+        &self,
+        integration: integration::NewIntegration,
+    ) -> Result<integration::Integration, DomainError>;
+    async fn list_integrations(
+        &self,
+        tenant_id: i32,
+    ) -> Result<Vec<integration::Integration>, DomainError>;
+    /// Returns integration owning the token unless it was revoked.
+    async fn find_active_integration(
+        &self,
+        token_hash: String,
+    ) -> Result<Option<integration::Integration>, DomainError>;
+    async fn rotate_integration_token(
+        &self,
+        tenant_id: i32,
+        integration_id: i64,
+        token_hash: String,
+    ) -> Result<bool, DomainError>;
+    async fn revoke_integration(
+        &self,
+        tenant_id: i32,
+        integration_id: i64,
+    ) -> Result<bool, DomainError>;
+}
+
+#[derive(Clone)]
+pub struct IntegrationsRepository {
+    pool: Pool,
+}
+
+impl IntegrationsRepository {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl IntegrationsRepositoryTrait for IntegrationsRepository {
+    async fn create_integration(
+        &self,
+        integration: integration::NewIntegration,
+    ) -> Result<integration::Integration, DomainError> {
+        let mut client = self.pool.get().await?;
+        let tx = tenant_transaction(&mut client, integration.tenant_id).await?;
+        let stmt = tx
+            .prepare_cached(
+                // language=postgresql
+                r#"
+                INSERT INTO integrations (tenant_id, name, token_hash, created_by)
+                VALUES ($1, $2, $3, $4)
+                RETURNING integration_id AS integration_id,
+                          tenant_id      AS tenant_id,
+                          name           AS name,
+                          created_by     AS created_by,
+                          created_at     AS created_at,
+                          revoked_at     AS revoked_at;
+                "#,
+            )
+            .await?;
+
+        let row = tx
+            .query_one(
+                &stmt,
+                &[
+                    &integration.tenant_id,
+                    &integration.name,
+                    &integration.token_hash,
+                    &integration.created_by,
+                ],
+            )
+            .await?;
+        tx.commit().await?;
+
+        Ok(integration::Integration::from(&row))
+    }
+
+    async fn list_integrations(
+        &self,
+        tenant_id: i32,
+    ) -> Result<Vec<integration::Integration>, DomainError> {
+        let mut client = self.pool.get().await?;
+        let tx = tenant_transaction(&mut client, tenant_id).await?;
+        let stmt = tx
+            .prepare_cached(
+                // language=postgresql
+                r#"
+                SELECT integration_id AS integration_id,
+                       tenant_id      AS tenant_id,
+                       name           AS name,
+                       created_by     AS created_by,
+                       created_at     AS created_at,
+                       revoked_at     AS revoked_at
+                FROM integrations
+                WHERE tenant_id = $1
+                ORDER BY integration_id;
+                "#,
+            )
+            .await?;
+
+        let rows = tx.query(&stmt, &[&tenant_id]).await?;
+        tx.commit().await?;
+
+        Ok(rows.iter().map(integration::Integration::from).collect())
+    }
+
+    async fn find_active_integration(
+        &self,
+        token_hash: String,
+    ) -> Result<Option<integration::Integration>, DomainError> {
+        let mut client = self.pool.get().await?;
+        let tx = all_tenants_transaction(&mut client).await?;
+        let stmt = tx
+            .prepare_cached(
+                // language=postgresql
+                r#"
+                SELECT integration_id AS integration_id,
+                       tenant_id      AS tenant_id,
+                       name           AS name,
+                       created_by     AS created_by,
+                       created_at     AS created_at,
+                       revoked_at     AS revoked_at
+                FROM integrations
+                WHERE token_hash = $1
+                  AND revoked_at IS NULL;
+                "#,
+            )
+            .await?;
+
+        let row = tx.query_opt(&stmt, &[&token_hash]).await?;
+        tx.commit().await?;
+
+        Ok(row.as_ref().map(integration::Integration::from))
+    }
+
+    async fn rotate_integration_token(
+        &self,
+        tenant_id: i32,
+        integration_id: i64,
+        token_hash: String,
+    ) -> Result<bool, DomainError> {
+        let mut client = self.pool.get().await?;
+        let tx = tenant_transaction(&mut client, tenant_id).await?;
+        let stmt = tx
+            .prepare_cached(
+                // language=postgresql
+                r#"
+                UPDATE integrations
+                SET token_hash = $3
+                WHERE tenant_id = $1
+                  AND integration_id = $2
+                  AND revoked_at IS NULL;
+                "#,
+            )
+            .await?;
+
+        let updated = tx
+            .execute(&stmt, &[&tenant_id, &integration_id, &token_hash])
+            .await?;
+        tx.commit().await?;
+
+        Ok(updated > 0)
+    }
+
+    async fn revoke_integration(
+        &self,
+        tenant_id: i32,
+        integration_id: i64,
+    ) -> Result<bool, DomainError> {
+        let mut client = self.pool.get().await?;
+        let tx = tenant_transaction(&mut client, tenant_id).await?;
+        let stmt = tx
+            .prepare_cached(
+                // language=postgresql
+                r#"
+                UPDATE integrations
+                SET revoked_at = now()
+                WHERE tenant_id = $1
+                  AND integration_id = $2
+                  AND revoked_at IS NULL;
+                "#,
+            )
+            .await?;
+
+        let updated = tx.execute(&stmt, &[&tenant_id, &integration_id]).await?;
+        tx.commit().await?;
+
+        Ok(updated > 0)
+    }
+}
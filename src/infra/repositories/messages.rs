@@ -1,29 +1,128 @@
 use async_trait::async_trait;
-use deadpool_postgres::Pool;
+use chrono::{DateTime, Utc};
+use deadpool_postgres::{GenericClient, Pool};
+use futures_util::{StreamExt, TryStreamExt, stream, stream::BoxStream};
 use mockall::*;
 
-use crate::domain::message;
+use crate::{
+    domain::{errors::DomainError, message, webhook},
+    infra::{
+        postgres::{Pools, Route},
+        repositories::{
+            sql::{Conditions, escape_like},
+            tenants::{all_tenants_transaction, tenant_transaction},
+            webhooks,
+        },
+    },
+};
+
+/// Messages read by a single query of an export.
+const EXPORT_BATCH_SIZE: i64 = 1000;
 
 #[async_trait]
 #[automock]
 pub trait MessagesRepositoryTrait: Send + Sync {
-    async fn create_message(&self, msg: message::PostMessage)
-    -> anyhow::Result<i64, anyhow::Error>;
+    async fn create_message(
+        &self,
+        tenant_id: i32,
+        msg: message::PostMessage,
+    ) -> Result<i64, DomainError>;
+    /// Fails with `NotFound` for missing messages and `Forbidden` for messages of others.
+    async fn update_message(
+        &self,
+        tenant_id: i32,
+        message_id: i64,
+        user_id: i32,
+        content: String,
+        edited_at: DateTime<Utc>,
+    ) -> Result<message::Message, DomainError>;
+    async fn delete_message(
+        &self,
+        tenant_id: i32,
+        message_id: i64,
+        user_id: i32,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<message::Message, DomainError>;
+    async fn import_messages(
+        &self,
+        tenant_id: i32,
+        msgs: Vec<message::ImportMessage>,
+    ) -> Result<u64, DomainError>;
     async fn list_messages(
         &self,
+        tenant_id: i32,
+        filter: message::MessageFilter,
         offset: i64,
         limit: i64,
-    ) -> anyhow::Result<Vec<message::Message>, anyhow::Error>;
+    ) -> Result<Vec<message::AuthoredMessage>, DomainError>;
+    /// Returns up to `limit` messages adjacent to `cursor` in listing order, or the first
+    /// page without cursor.
+    async fn list_messages_page(
+        &self,
+        tenant_id: i32,
+        filter: message::MessageFilter,
+        cursor: Option<message::Cursor>,
+        limit: i64,
+    ) -> Result<Vec<message::AuthoredMessage>, DomainError>;
+    async fn count_messages(
+        &self,
+        tenant_id: i32,
+        filter: message::MessageFilter,
+    ) -> Result<i64, DomainError>;
+    /// Returns fingerprint of messages matching the filter without reading them.
+    async fn messages_version(
+        &self,
+        tenant_id: i32,
+        filter: message::MessageFilter,
+    ) -> Result<message::ListingVersion, DomainError>;
+    /// Fails with `NotFound` when message is missing, deleted or expired.
+    async fn get_message(
+        &self,
+        tenant_id: i32,
+        message_id: i64,
+    ) -> Result<message::AuthoredMessage, DomainError>;
+    /// Returns visible messages among `message_ids`, missing ids are skipped.
+    async fn get_messages(
+        &self,
+        tenant_id: i32,
+        message_ids: Vec<i64>,
+    ) -> Result<Vec<message::AuthoredMessage>, DomainError>;
+    fn stream_messages(
+        &self,
+        tenant_id: i32,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> BoxStream<'static, Result<message::Message, DomainError>>;
+    /// Counts messages of all tenants affected by the retention policy.
+    async fn count_retention_candidates(
+        &self,
+        before: DateTime<Utc>,
+        action: message::RetentionAction,
+    ) -> Result<i64, DomainError>;
+    /// Applies retention policy to messages of all tenants.
+    async fn apply_retention(
+        &self,
+        before: DateTime<Utc>,
+        action: message::RetentionAction,
+        limit: i64,
+    ) -> Result<u64, DomainError>;
+    /// Physically removes up to `limit` messages of any tenant whose TTL has passed.
+    async fn delete_expired_messages(&self, limit: i64) -> Result<u64, DomainError>;
 }
 
 #[derive(Clone)]
 pub struct MessagesRepository {
-    pool: Pool,
+    pools: Pools,
 }
 
 impl MessagesRepository {
     pub fn new(pool: Pool) -> Self {
-        Self { pool }
+        Self::with_pools(Pools::new(pool))
+    }
+
+    /// Creates repository reading from the replica of `pools` when there is one.
+    pub fn with_pools(pools: Pools) -> Self {
+        Self { pools }
     }
 }
 
@@ -31,51 +130,727 @@ impl MessagesRepository {
 impl MessagesRepositoryTrait for MessagesRepository {
     async fn create_message(
         &self,
+        tenant_id: i32,
         msg: message::PostMessage,
-    ) -> anyhow::Result<i64, anyhow::Error> {
-        let client = self.pool.get().await?;
-        let stmt = client
-            .prepare(
+    ) -> Result<i64, DomainError> {
+        let mut client = self.pools.write(tenant_id).await?;
+        let tx = tenant_transaction(&mut client, tenant_id).await?;
+        let created = insert_message(&tx, tenant_id, msg).await?;
+        tx.commit().await?;
+
+        Ok(created.message_id)
+    }
+
+    async fn update_message(
+        &self,
+        tenant_id: i32,
+        message_id: i64,
+        user_id: i32,
+        content: String,
+        edited_at: DateTime<Utc>,
+    ) -> Result<message::Message, DomainError> {
+        let mut client = self.pools.write(tenant_id).await?;
+        let tx = tenant_transaction(&mut client, tenant_id).await?;
+        let updated = edit_message(&tx, tenant_id, message_id, user_id, content, edited_at).await?;
+        tx.commit().await?;
+
+        Ok(updated)
+    }
+
+    async fn delete_message(
+        &self,
+        tenant_id: i32,
+        message_id: i64,
+        user_id: i32,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<message::Message, DomainError> {
+        let mut client = self.pools.write(tenant_id).await?;
+        let tx = tenant_transaction(&mut client, tenant_id).await?;
+        let deleted = mark_message_deleted(&tx, tenant_id, message_id, user_id, deleted_at).await?;
+        tx.commit().await?;
+
+        Ok(deleted)
+    }
+
+    async fn import_messages(
+        &self,
+        tenant_id: i32,
+        msgs: Vec<message::ImportMessage>,
+    ) -> Result<u64, DomainError> {
+        let mut client = self.pools.write(tenant_id).await?;
+        let tx = tenant_transaction(&mut client, tenant_id).await?;
+        let stmt = tx
+            .prepare_cached(
                 // language=postgresql
                 r#"
-                INSERT INTO rust_simple_chat.messages (message_content, user_id, posted_at)
-                VALUES ($1, $2, $3)
-                RETURNING message_id AS message_id;"#,
+                INSERT INTO messages (tenant_id, external_id, message_content, user_id, posted_at)
+                SELECT $1, *
+                FROM unnest($2::varchar[], $3::varchar[], $4::integer[], $5::timestamptz[])
+                ON CONFLICT (tenant_id, external_id) DO NOTHING;
+                "#,
             )
             .await?;
 
-        let row = client
-            .query_one(&stmt, &[&msg.content, &msg.user_id, &msg.posted_at])
-            .await?;
+        let mut external_ids = Vec::with_capacity(msgs.len());
+        let mut contents = Vec::with_capacity(msgs.len());
+        let mut user_ids = Vec::with_capacity(msgs.len());
+        let mut posted_ats = Vec::with_capacity(msgs.len());
+        for msg in msgs {
+            external_ids.push(msg.external_id);
+            contents.push(msg.content);
+            user_ids.push(msg.user_id);
+            posted_ats.push(msg.posted_at);
+        }
 
-        let message_id: i64 = row.get("message_id");
+        let inserted = tx
+            .execute(
+                &stmt,
+                &[&tenant_id, &external_ids, &contents, &user_ids, &posted_ats],
+            )
+            .await?;
+        tx.commit().await?;
 
-        Ok(message_id)
+        Ok(inserted)
     }
 
     async fn list_messages(
         &self,
+        tenant_id: i32,
+        filter: message::MessageFilter,
         offset: i64,
         limit: i64,
-    ) -> anyhow::Result<Vec<message::Message>, anyhow::Error> {
-        let client = self.pool.get().await?;
-        let stmt = client
+    ) -> Result<Vec<message::AuthoredMessage>, DomainError> {
+        let mut conditions = filter_conditions(tenant_id, &filter);
+        let order = sort_order(filter.order);
+        let offset = conditions.bind(offset);
+        let limit = conditions.bind(limit);
+
+        let query = authored_messages_query(
+            &conditions,
+            &format!(
+                "ORDER BY m.posted_at {order}, m.message_id {order} OFFSET {offset} LIMIT {limit}"
+            ),
+        );
+
+        let mut client = self.pools.read(tenant_id).await?;
+        let tx = tenant_transaction(&mut client, tenant_id).await?;
+        // every filter combination yields its own statement, a handful in total
+        let stmt = tx.prepare_cached(&query).await?;
+        let rows = tx.query(&stmt, &conditions.params()).await?;
+        tx.commit().await?;
+
+        Ok(rows.iter().map(message::AuthoredMessage::from).collect())
+    }
+
+    async fn list_messages_page(
+        &self,
+        tenant_id: i32,
+        filter: message::MessageFilter,
+        cursor: Option<message::Cursor>,
+        limit: i64,
+    ) -> Result<Vec<message::AuthoredMessage>, DomainError> {
+        let mut conditions = filter_conditions(tenant_id, &filter);
+
+        // previous page is read backwards from the cursor and flipped afterwards
+        let backward = matches!(
+            cursor,
+            Some(message::Cursor {
+                direction: message::Direction::Prev,
+                ..
+            })
+        );
+        let ascending = (filter.order == message::SortOrder::Asc) != backward;
+
+        if let Some(cursor) = cursor {
+            let posted_at = conditions.bind(cursor.posted_at);
+            let message_id = conditions.bind(cursor.message_id);
+            let op = if ascending { ">" } else { "<" };
+            conditions.and(format!(
+                "(m.posted_at, m.message_id) {op} ({posted_at}, {message_id})"
+            ));
+        }
+
+        let order = if ascending { "ASC" } else { "DESC" };
+        let limit = conditions.bind(limit);
+
+        let query = authored_messages_query(
+            &conditions,
+            &format!("ORDER BY m.posted_at {order}, m.message_id {order} LIMIT {limit}"),
+        );
+
+        let mut client = self.pools.read(tenant_id).await?;
+        let tx = tenant_transaction(&mut client, tenant_id).await?;
+        let stmt = tx.prepare_cached(&query).await?;
+        let rows = tx.query(&stmt, &conditions.params()).await?;
+        tx.commit().await?;
+
+        let mut messages: Vec<_> = rows.iter().map(message::AuthoredMessage::from).collect();
+        if backward {
+            messages.reverse();
+        }
+
+        Ok(messages)
+    }
+
+    async fn count_messages(
+        &self,
+        tenant_id: i32,
+        filter: message::MessageFilter,
+    ) -> Result<i64, DomainError> {
+        let conditions = filter_conditions(tenant_id, &filter);
+
+        let query = format!(
+            // language=postgresql
+            r#"
+            SELECT count(*) AS total
+            FROM messages m
+            {where_clause};
+            "#,
+            where_clause = conditions.where_clause(),
+        );
+
+        let mut client = self.pools.read(tenant_id).await?;
+        let tx = tenant_transaction(&mut client, tenant_id).await?;
+        let stmt = tx.prepare_cached(&query).await?;
+        let row = tx.query_one(&stmt, &conditions.params()).await?;
+        tx.commit().await?;
+
+        Ok(row.get("total"))
+    }
+
+    async fn messages_version(
+        &self,
+        tenant_id: i32,
+        filter: message::MessageFilter,
+    ) -> Result<message::ListingVersion, DomainError> {
+        // deleted rows stay in the scan so that deletions move the last modification time, and
+        // authors are joined since listings show their profiles
+        let conditions = match_conditions(&filter, tenant_conditions(tenant_id));
+
+        let query = format!(
+            // language=postgresql
+            r#"
+            SELECT max(m.message_id) AS max_message_id,
+                   greatest(max(m.posted_at), max(m.edited_at), max(m.deleted_at),
+                            max(u.updated_at))
+                                     AS last_modified,
+                   count(*) FILTER (WHERE m.deleted_at IS NULL
+                       AND (m.expires_at IS NULL OR m.expires_at > now()))
+                                     AS visible
+            FROM messages m
+                     LEFT JOIN users u ON u.tenant_id = m.tenant_id AND u.user_id = m.user_id
+            {where_clause};
+            "#,
+            where_clause = conditions.where_clause(),
+        );
+
+        let mut client = self.pools.read(tenant_id).await?;
+        let tx = tenant_transaction(&mut client, tenant_id).await?;
+        let stmt = tx.prepare_cached(&query).await?;
+        let row = tx.query_one(&stmt, &conditions.params()).await?;
+        tx.commit().await?;
+
+        Ok(message::ListingVersion::from(&row))
+    }
+
+    async fn get_message(
+        &self,
+        tenant_id: i32,
+        message_id: i64,
+    ) -> Result<message::AuthoredMessage, DomainError> {
+        let mut client = self.pools.read(tenant_id).await?;
+        let tx = tenant_transaction(&mut client, tenant_id).await?;
+        let stmt = tx
+            .prepare_cached(
+                // language=postgresql
+                r#"
+                SELECT m.message_id                     AS message_id,
+                       m.message_content                AS message_content,
+                       m.user_id                        AS user_id,
+                       m.posted_at                      AS posted_at,
+                       m.expires_at                     AS expires_at,
+                       coalesce(u.display_name, i.name) AS display_name,
+                       u.avatar_url                     AS avatar_url
+                FROM messages m
+                         LEFT JOIN users u ON u.tenant_id = m.tenant_id AND u.user_id = m.user_id
+                         LEFT JOIN integrations i ON i.tenant_id = m.tenant_id AND -i.integration_id = m.user_id
+                WHERE m.tenant_id = $1
+                  AND m.message_id = $2
+                  AND m.deleted_at IS NULL
+                  AND (m.expires_at IS NULL OR m.expires_at > now());
+                "#,
+            )
+            .await?;
+
+        let row = tx.query_opt(&stmt, &[&tenant_id, &message_id]).await?;
+        tx.commit().await?;
+
+        match row {
+            Some(row) => Ok(message::AuthoredMessage::from(&row)),
+            None => Err(DomainError::NotFound("message")),
+        }
+    }
+
+    async fn get_messages(
+        &self,
+        tenant_id: i32,
+        message_ids: Vec<i64>,
+    ) -> Result<Vec<message::AuthoredMessage>, DomainError> {
+        let mut client = self.pools.read(tenant_id).await?;
+        let tx = tenant_transaction(&mut client, tenant_id).await?;
+        let stmt = tx
+            .prepare_cached(
+                // language=postgresql
+                r#"
+                SELECT m.message_id                     AS message_id,
+                       m.message_content                AS message_content,
+                       m.user_id                        AS user_id,
+                       m.posted_at                      AS posted_at,
+                       m.expires_at                     AS expires_at,
+                       coalesce(u.display_name, i.name) AS display_name,
+                       u.avatar_url                     AS avatar_url
+                FROM messages m
+                         LEFT JOIN users u ON u.tenant_id = m.tenant_id AND u.user_id = m.user_id
+                         LEFT JOIN integrations i ON i.tenant_id = m.tenant_id AND -i.integration_id = m.user_id
+                WHERE m.tenant_id = $1
+                  AND m.message_id = ANY ($2)
+                  AND m.deleted_at IS NULL
+                  AND (m.expires_at IS NULL OR m.expires_at > now());
+                "#,
+            )
+            .await?;
+
+        let rows = tx.query(&stmt, &[&tenant_id, &message_ids]).await?;
+        tx.commit().await?;
+
+        Ok(rows.iter().map(message::AuthoredMessage::from).collect())
+    }
+
+    fn stream_messages(
+        &self,
+        tenant_id: i32,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> BoxStream<'static, Result<message::Message, DomainError>> {
+        let pools = self.pools.clone();
+        let route = pools.read_route(tenant_id);
+
+        // tenant scope lives as long as a transaction, so rows are read in short batches
+        // continuing after the last exported message
+        stream::try_unfold(Some(None), move |after| {
+            let pools = pools.clone();
+            async move {
+                let Some(after) = after else {
+                    return Ok::<_, DomainError>(None);
+                };
+
+                let batch = export_batch(&pools, route, tenant_id, since, until, after).await?;
+                let next = match batch.last() {
+                    Some(last) if batch.len() as i64 == EXPORT_BATCH_SIZE => {
+                        Some(Some((last.posted_at, last.message_id)))
+                    }
+                    _ => None,
+                };
+
+                Ok(Some((stream::iter(batch.into_iter().map(Ok)), next)))
+            }
+        })
+        .try_flatten()
+        .boxed()
+    }
+
+    async fn count_retention_candidates(
+        &self,
+        before: DateTime<Utc>,
+        action: message::RetentionAction,
+    ) -> Result<i64, DomainError> {
+        let mut client = self.pools.primary().get().await?;
+        let tx = all_tenants_transaction(&mut client).await?;
+        let stmt = tx
+            .prepare_cached(
+                // language=postgresql
+                r#"
+                SELECT count(*) AS total
+                FROM messages
+                WHERE posted_at < $1
+                  AND ($2 OR user_id <> $3);
+                "#,
+            )
+            .await?;
+
+        let delete = action == message::RetentionAction::Delete;
+        let row = tx
+            .query_one(&stmt, &[&before, &delete, &message::ANONYMOUS_USER_ID])
+            .await?;
+        tx.commit().await?;
+
+        Ok(row.get("total"))
+    }
+
+    async fn apply_retention(
+        &self,
+        before: DateTime<Utc>,
+        action: message::RetentionAction,
+        limit: i64,
+    ) -> Result<u64, DomainError> {
+        let mut client = self.pools.primary().get().await?;
+        let tx = all_tenants_transaction(&mut client).await?;
+        let affected = match action {
+            message::RetentionAction::Delete => {
+                let stmt = tx
+                    .prepare_cached(
+                        // language=postgresql
+                        r#"
+                        DELETE FROM messages
+                        WHERE ctid IN (SELECT ctid
+                                       FROM messages
+                                       WHERE posted_at < $1
+                                       LIMIT $2);
+                        "#,
+                    )
+                    .await?;
+
+                tx.execute(&stmt, &[&before, &limit]).await?
+            }
+            message::RetentionAction::Anonymize => {
+                let stmt = tx
+                    .prepare_cached(
+                        // language=postgresql
+                        r#"
+                        UPDATE messages
+                        SET user_id   = $3,
+                            -- moves listing versions, the author is part of the response
+                            edited_at = now()
+                        WHERE ctid IN (SELECT ctid
+                                       FROM messages
+                                       WHERE posted_at < $1
+                                         AND user_id <> $3
+                                       LIMIT $2);
+                        "#,
+                    )
+                    .await?;
+
+                tx.execute(&stmt, &[&before, &limit, &message::ANONYMOUS_USER_ID])
+                    .await?
+            }
+        };
+        tx.commit().await?;
+
+        Ok(affected)
+    }
+
+    async fn delete_expired_messages(&self, limit: i64) -> Result<u64, DomainError> {
+        let mut client = self.pools.primary().get().await?;
+        let tx = all_tenants_transaction(&mut client).await?;
+        let stmt = tx
             .prepare_cached(
                 // language=postgresql
                 r#"
-                SELECT message_id      AS message_id,
-                       message_content AS message_content,
-                       user_id         AS user_id,
-                       posted_at       AS posted_at
-                FROM rust_simple_chat.messages
-                ORDER BY posted_at DESC
-                OFFSET $1 LIMIT $2;
+                DELETE FROM messages
+                WHERE ctid IN (SELECT ctid
+                               FROM messages
+                               WHERE expires_at <= now()
+                               LIMIT $1);
                 "#,
             )
             .await?;
 
-        let rows = client.query(&stmt, &[&offset, &limit]).await?;
+        let deleted = tx.execute(&stmt, &[&limit]).await?;
+        tx.commit().await?;
+
+        Ok(deleted)
+    }
+}
+
+/// Inserts message and enqueues its webhook event.
+pub(crate) async fn insert_message(
+    client: &impl GenericClient,
+    tenant_id: i32,
+    msg: message::PostMessage,
+) -> Result<message::Message, DomainError> {
+    let stmt = client
+        .prepare(
+            // language=postgresql
+            r#"
+            INSERT INTO messages (tenant_id, message_content, user_id, posted_at, expires_at)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING message_id AS message_id;"#,
+        )
+        .await?;
+
+    let row = client
+        .query_one(
+            &stmt,
+            &[
+                &tenant_id,
+                &msg.content,
+                &msg.user_id,
+                &msg.posted_at,
+                &msg.expires_at,
+            ],
+        )
+        .await?;
+
+    let created = message::Message {
+        message_id: row.get("message_id"),
+        message_content: msg.content,
+        user_id: msg.user_id,
+        posted_at: msg.posted_at,
+        expires_at: msg.expires_at,
+    };
+    let event_type = webhook::EventType::MessageCreated;
+    webhooks::enqueue_event(
+        client,
+        tenant_id,
+        event_type,
+        &webhook::message_event(event_type, tenant_id, &created),
+    )
+    .await?;
 
-        Ok(rows.iter().map(message::Message::from).collect())
+    Ok(created)
+}
+
+/// Changes content of a message written by `user_id` and enqueues its webhook event.
+pub(crate) async fn edit_message(
+    client: &impl GenericClient,
+    tenant_id: i32,
+    message_id: i64,
+    user_id: i32,
+    content: String,
+    edited_at: DateTime<Utc>,
+) -> Result<message::Message, DomainError> {
+    match lock_message_author(client, tenant_id, message_id).await? {
+        None => return Err(DomainError::NotFound("message")),
+        Some(author_id) if author_id != user_id => return Err(DomainError::Forbidden),
+        Some(_) => {}
+    }
+
+    let stmt = client
+        .prepare_cached(
+            // language=postgresql
+            r#"
+            UPDATE messages
+            SET message_content = $2,
+                edited_at       = $3
+            WHERE message_id = $1
+            RETURNING message_id      AS message_id,
+                      message_content AS message_content,
+                      user_id         AS user_id,
+                      posted_at       AS posted_at,
+                      expires_at      AS expires_at;
+            "#,
+        )
+        .await?;
+
+    let row = client
+        .query_one(&stmt, &[&message_id, &content, &edited_at])
+        .await?;
+    let updated = message::Message::from(&row);
+
+    let event_type = webhook::EventType::MessageEdited;
+    webhooks::enqueue_event(
+        client,
+        tenant_id,
+        event_type,
+        &webhook::message_event(event_type, tenant_id, &updated),
+    )
+    .await?;
+
+    Ok(updated)
+}
+
+/// Soft deletes a message written by `user_id` and enqueues its webhook event.
+pub(crate) async fn mark_message_deleted(
+    client: &impl GenericClient,
+    tenant_id: i32,
+    message_id: i64,
+    user_id: i32,
+    deleted_at: DateTime<Utc>,
+) -> Result<message::Message, DomainError> {
+    match lock_message_author(client, tenant_id, message_id).await? {
+        None => return Err(DomainError::NotFound("message")),
+        Some(author_id) if author_id != user_id => return Err(DomainError::Forbidden),
+        Some(_) => {}
     }
+
+    let stmt = client
+        .prepare_cached(
+            // language=postgresql
+            r#"
+            UPDATE messages
+            SET deleted_at = $2
+            WHERE message_id = $1
+            RETURNING message_id      AS message_id,
+                      message_content AS message_content,
+                      user_id         AS user_id,
+                      posted_at       AS posted_at,
+                      expires_at      AS expires_at;
+            "#,
+        )
+        .await?;
+
+    let row = client.query_one(&stmt, &[&message_id, &deleted_at]).await?;
+    let deleted = message::Message::from(&row);
+
+    let event_type = webhook::EventType::MessageDeleted;
+    webhooks::enqueue_event(
+        client,
+        tenant_id,
+        event_type,
+        &webhook::message_event(event_type, tenant_id, &deleted),
+    )
+    .await?;
+
+    Ok(deleted)
+}
+
+/// Reads next batch of exported messages following `after` position.
+async fn export_batch(
+    pools: &Pools,
+    route: Route,
+    tenant_id: i32,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    after: Option<(DateTime<Utc>, i64)>,
+) -> Result<Vec<message::Message>, DomainError> {
+    let mut client = pools.get(route).await?;
+    let tx = tenant_transaction(&mut client, tenant_id).await?;
+    let stmt = tx
+        .prepare_cached(
+            // language=postgresql
+            r#"
+            SELECT message_id      AS message_id,
+                   message_content AS message_content,
+                   user_id         AS user_id,
+                   posted_at       AS posted_at,
+                   expires_at      AS expires_at
+            FROM messages
+            WHERE tenant_id = $1
+              AND deleted_at IS NULL
+              AND (expires_at IS NULL OR expires_at > now())
+              AND ($2::timestamptz IS NULL OR posted_at >= $2)
+              AND ($3::timestamptz IS NULL OR posted_at < $3)
+              AND ($4::timestamptz IS NULL OR (posted_at, message_id) > ($4, $5::bigint))
+            ORDER BY posted_at, message_id
+            LIMIT $6;
+            "#,
+        )
+        .await?;
+
+    let (after_posted_at, after_message_id) = after.unzip();
+    let rows = tx
+        .query(
+            &stmt,
+            &[
+                &tenant_id,
+                &since,
+                &until,
+                &after_posted_at,
+                &after_message_id,
+                &EXPORT_BATCH_SIZE,
+            ],
+        )
+        .await?;
+    tx.commit().await?;
+
+    Ok(rows.iter().map(message::Message::from).collect())
+}
+
+/// Locks message row for update and returns its author.
+async fn lock_message_author(
+    client: &impl GenericClient,
+    tenant_id: i32,
+    message_id: i64,
+) -> Result<Option<i32>, DomainError> {
+    let stmt = client
+        .prepare_cached(
+            // language=postgresql
+            r#"
+            SELECT user_id AS user_id
+            FROM messages
+            WHERE tenant_id = $1
+              AND message_id = $2
+              AND deleted_at IS NULL
+              AND (expires_at IS NULL OR expires_at > now())
+            FOR UPDATE;
+            "#,
+        )
+        .await?;
+
+    let row = client.query_opt(&stmt, &[&tenant_id, &message_id]).await?;
+
+    Ok(row.map(|row| row.get("user_id")))
+}
+
+/// Conditions of visible messages of the tenant matching the filter.
+fn filter_conditions(tenant_id: i32, filter: &message::MessageFilter) -> Conditions {
+    let mut conditions = tenant_conditions(tenant_id);
+    conditions.and("m.deleted_at IS NULL");
+    conditions.and("(m.expires_at IS NULL OR m.expires_at > now())");
+
+    match_conditions(filter, conditions)
+}
+
+/// Conditions limiting rows to the tenant, row-level security only backs them up.
+fn tenant_conditions(tenant_id: i32) -> Conditions {
+    let mut conditions = Conditions::new();
+    let param = conditions.bind(tenant_id);
+    conditions.and(format!("m.tenant_id = {param}"));
+
+    conditions
+}
+
+/// Adds conditions of the filter regardless of message visibility.
+fn match_conditions(filter: &message::MessageFilter, mut conditions: Conditions) -> Conditions {
+    if let Some(user_id) = filter.user_id {
+        let param = conditions.bind(user_id);
+        conditions.and(format!("m.user_id = {param}"));
+    }
+    if let Some(since) = filter.since {
+        let param = conditions.bind(since);
+        conditions.and(format!("m.posted_at >= {param}"));
+    }
+    if let Some(until) = filter.until {
+        let param = conditions.bind(until);
+        conditions.and(format!("m.posted_at < {param}"));
+    }
+    if let Some(text) = &filter.text {
+        let pattern = match text {
+            message::TextMatch::Contains(text) => format!("%{}%", escape_like(text)),
+            message::TextMatch::Prefix(text) => format!("{}%", escape_like(text)),
+        };
+        let param = conditions.bind(pattern);
+        conditions.and(format!("m.message_content ILIKE {param}"));
+    }
+
+    conditions
+}
+
+fn sort_order(order: message::SortOrder) -> &'static str {
+    match order {
+        message::SortOrder::Asc => "ASC",
+        message::SortOrder::Desc => "DESC",
+    }
+}
+
+/// Selects messages with author profiles, `tail` holds ordering and paging clauses.
+fn authored_messages_query(conditions: &Conditions, tail: &str) -> String {
+    format!(
+        // language=postgresql
+        r#"
+        SELECT m.message_id                     AS message_id,
+               m.message_content                AS message_content,
+               m.user_id                        AS user_id,
+               m.posted_at                      AS posted_at,
+               m.expires_at                     AS expires_at,
+               coalesce(u.display_name, i.name) AS display_name,
+               u.avatar_url                     AS avatar_url
+        FROM messages m
+                 LEFT JOIN users u ON u.tenant_id = m.tenant_id AND u.user_id = m.user_id
+                 LEFT JOIN integrations i ON i.tenant_id = m.tenant_id AND -i.integration_id = m.user_id
+        {where_clause}
+        {tail};
+        "#,
+        where_clause = conditions.where_clause(),
+    )
 }
@@ -1,3 +1,21 @@
+pub mod bookmarks;
+mod errors;
+pub mod integrations;
 pub mod messages;
+pub mod pins;
+pub mod scheduled_messages;
+pub(crate) mod sql;
+pub mod tenants;
+pub mod unit_of_work;
+pub mod users;
+pub mod webhooks;
 
+pub use bookmarks::BookmarksRepository;
+pub use integrations::IntegrationsRepository;
 pub use messages::MessagesRepository;
+pub use pins::PinsRepository;
+pub use scheduled_messages::ScheduledMessagesRepository;
+pub use tenants::TenantsRepository;
+pub use unit_of_work::UnitOfWorkFactory;
+pub use users::UsersRepository;
+pub use webhooks::WebhooksRepository;
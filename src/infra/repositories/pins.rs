@@ -0,0 +1,146 @@
+use async_trait::async_trait;
+use deadpool_postgres::{GenericClient, Pool};
+use mockall::*;
+
+use crate::{
+    domain::{bookmark, errors::DomainError},
+    infra::{postgres::Pools, repositories::tenants::tenant_transaction},
+};
+
+#[async_trait]
+#[automock]
+pub trait PinsRepositoryTrait: Send + Sync {
+    /// Pins visible message, pinning twice keeps the first pin. Returns `false` when the
+    /// message does not exist.
+    async fn pin_message(
+        &self,
+        tenant_id: i32,
+        message_id: i64,
+        pinned_by: i32,
+    ) -> Result<bool, DomainError>;
+    async fn unpin_message(&self, tenant_id: i32, message_id: i64) -> Result<bool, DomainError>;
+    async fn list_pins(&self, tenant_id: i32) -> Result<Vec<bookmark::Pin>, DomainError>;
+}
+
+#[derive(Clone)]
+pub struct PinsRepository {
+    pools: Pools,
+}
+
+impl PinsRepository {
+    pub fn new(pool: Pool) -> Self {
+        Self::with_pools(Pools::new(pool))
+    }
+
+    /// Creates repository reading from the replica of `pools` when there is one.
+    pub fn with_pools(pools: Pools) -> Self {
+        Self { pools }
+    }
+}
+
+#[async_trait]
+impl PinsRepositoryTrait for PinsRepository {
+    async fn pin_message(
+        &self,
+        tenant_id: i32,
+        message_id: i64,
+        pinned_by: i32,
+    ) -> Result<bool, DomainError> {
+        let mut client = self.pools.write(tenant_id).await?;
+        let tx = tenant_transaction(&mut client, tenant_id).await?;
+        let pinned = insert_pin(&tx, tenant_id, message_id, pinned_by).await?;
+        tx.commit().await?;
+
+        Ok(pinned)
+    }
+
+    async fn unpin_message(&self, tenant_id: i32, message_id: i64) -> Result<bool, DomainError> {
+        let mut client = self.pools.write(tenant_id).await?;
+        let tx = tenant_transaction(&mut client, tenant_id).await?;
+        let deleted = delete_pin(&tx, tenant_id, message_id).await?;
+        tx.commit().await?;
+
+        Ok(deleted)
+    }
+
+    async fn list_pins(&self, tenant_id: i32) -> Result<Vec<bookmark::Pin>, DomainError> {
+        let mut client = self.pools.read(tenant_id).await?;
+        let tx = tenant_transaction(&mut client, tenant_id).await?;
+        let stmt = tx
+            .prepare_cached(
+                // language=postgresql
+                r#"
+                SELECT m.message_id      AS message_id,
+                       m.message_content AS message_content,
+                       m.user_id         AS user_id,
+                       m.posted_at       AS posted_at,
+                       p.pinned_by       AS pinned_by,
+                       p.pinned_at       AS pinned_at
+                FROM pins p
+                         JOIN messages m ON m.message_id = p.message_id
+                WHERE m.tenant_id = $1
+                  AND m.deleted_at IS NULL
+                  AND (m.expires_at IS NULL OR m.expires_at > now())
+                ORDER BY p.pinned_at DESC;
+                "#,
+            )
+            .await?;
+
+        let rows = tx.query(&stmt, &[&tenant_id]).await?;
+        tx.commit().await?;
+
+        Ok(rows.iter().map(bookmark::Pin::from).collect())
+    }
+}
+
+/// Pins a visible message, returns false when there is no such message.
+pub(crate) async fn insert_pin(
+    client: &impl GenericClient,
+    tenant_id: i32,
+    message_id: i64,
+    pinned_by: i32,
+) -> Result<bool, DomainError> {
+    let stmt = client
+        .prepare_cached(
+            // language=postgresql
+            r#"
+            INSERT INTO pins (tenant_id, message_id, pinned_by)
+            SELECT tenant_id, message_id, $3
+            FROM messages
+            WHERE tenant_id = $1
+              AND message_id = $2
+              AND deleted_at IS NULL
+              AND (expires_at IS NULL OR expires_at > now())
+            ON CONFLICT (message_id) DO UPDATE SET message_id = excluded.message_id;
+            "#,
+        )
+        .await?;
+
+    let pinned = client
+        .execute(&stmt, &[&tenant_id, &message_id, &pinned_by])
+        .await?;
+
+    Ok(pinned > 0)
+}
+
+/// Unpins a message, returns false when it was not pinned.
+pub(crate) async fn delete_pin(
+    client: &impl GenericClient,
+    tenant_id: i32,
+    message_id: i64,
+) -> Result<bool, DomainError> {
+    let stmt = client
+        .prepare_cached(
+            // language=postgresql
+            r#"
+            DELETE FROM pins
+            WHERE tenant_id = $1
+              AND message_id = $2;
+            "#,
+        )
+        .await?;
+
+    let deleted = client.execute(&stmt, &[&tenant_id, &message_id]).await?;
+
+    Ok(deleted > 0)
+}
@@ -0,0 +1,242 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use deadpool_postgres::Pool;
+use mockall::*;
+
+use crate::{
+    domain::{errors::DomainError, message, scheduled, webhook},
+    infra::repositories::{
+        tenants::{all_tenants_transaction, tenant_transaction},
+        webhooks,
+    },
+};
+
+#[async_trait]
+#[automock]
+pub trait ScheduledMessagesRepositoryTrait: Send + Sync {
+    async fn schedule_message(&self, msg: scheduled::ScheduleMessage) -> Result<i64, DomainError>;
+    /// Returns pending messages of the user ordered by send time.
+    async fn list_scheduled_messages(
+        &self,
+        tenant_id: i32,
+        user_id: i32,
+    ) -> Result<Vec<scheduled::ScheduledMessage>, DomainError>;
+    /// Changes pending message of the user, `None` fields are kept. Returns `None` when the
+    /// message does not exist, belongs to another user or was already published.
+    async fn update_scheduled_message(
+        &self,
+        tenant_id: i32,
+        scheduled_id: i64,
+        user_id: i32,
+        content: Option<String>,
+        send_at: Option<DateTime<Utc>>,
+    ) -> Result<Option<scheduled::ScheduledMessage>, DomainError>;
+    async fn cancel_scheduled_message(
+        &self,
+        tenant_id: i32,
+        scheduled_id: i64,
+        user_id: i32,
+    ) -> Result<bool, DomainError>;
+    /// Moves due messages of all active tenants into the chat, messages of suspended tenants wait.
+    /// Rows are locked so concurrent workers never publish the same message twice.
+    async fn publish_due_messages(&self, limit: i64) -> Result<u64, DomainError>;
+}
+
+#[derive(Clone)]
+pub struct ScheduledMessagesRepository {
+    pool: Pool,
+}
+
+impl ScheduledMessagesRepository {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ScheduledMessagesRepositoryTrait for ScheduledMessagesRepository {
+    async fn schedule_message(&self, msg: scheduled::ScheduleMessage) -> Result<i64, DomainError> {
+        let mut client = self.pool.get().await?;
+        let tx = tenant_transaction(&mut client, msg.tenant_id).await?;
+        let stmt = tx
+            .prepare_cached(
+                // language=postgresql
+                r#"
+                INSERT INTO scheduled_messages (tenant_id, message_content, user_id, send_at, expires_in)
+                VALUES ($1, $2, $3, $4, $5)
+                RETURNING scheduled_id AS scheduled_id;
+                "#,
+            )
+            .await?;
+
+        let row = tx
+            .query_one(
+                &stmt,
+                &[
+                    &msg.tenant_id,
+                    &msg.content,
+                    &msg.user_id,
+                    &msg.send_at,
+                    &msg.expires_in,
+                ],
+            )
+            .await?;
+        tx.commit().await?;
+
+        Ok(row.get("scheduled_id"))
+    }
+
+    async fn list_scheduled_messages(
+        &self,
+        tenant_id: i32,
+        user_id: i32,
+    ) -> Result<Vec<scheduled::ScheduledMessage>, DomainError> {
+        let mut client = self.pool.get().await?;
+        let tx = tenant_transaction(&mut client, tenant_id).await?;
+        let stmt = tx
+            .prepare_cached(
+                // language=postgresql
+                r#"
+                SELECT scheduled_id    AS scheduled_id,
+                       message_content AS message_content,
+                       user_id         AS user_id,
+                       send_at         AS send_at,
+                       expires_in      AS expires_in,
+                       created_at      AS created_at
+                FROM scheduled_messages
+                WHERE tenant_id = $1
+                  AND user_id = $2
+                ORDER BY send_at, scheduled_id;
+                "#,
+            )
+            .await?;
+
+        let rows = tx.query(&stmt, &[&tenant_id, &user_id]).await?;
+        tx.commit().await?;
+
+        Ok(rows.iter().map(scheduled::ScheduledMessage::from).collect())
+    }
+
+    async fn update_scheduled_message(
+        &self,
+        tenant_id: i32,
+        scheduled_id: i64,
+        user_id: i32,
+        content: Option<String>,
+        send_at: Option<DateTime<Utc>>,
+    ) -> Result<Option<scheduled::ScheduledMessage>, DomainError> {
+        let mut client = self.pool.get().await?;
+        let tx = tenant_transaction(&mut client, tenant_id).await?;
+        let stmt = tx
+            .prepare_cached(
+                // language=postgresql
+                r#"
+                UPDATE scheduled_messages
+                SET message_content = coalesce($4, message_content),
+                    send_at         = coalesce($5, send_at)
+                WHERE tenant_id = $1
+                  AND scheduled_id = $2
+                  AND user_id = $3
+                RETURNING scheduled_id    AS scheduled_id,
+                          message_content AS message_content,
+                          user_id         AS user_id,
+                          send_at         AS send_at,
+                          expires_in      AS expires_in,
+                          created_at      AS created_at;
+                "#,
+            )
+            .await?;
+
+        let row = tx
+            .query_opt(
+                &stmt,
+                &[&tenant_id, &scheduled_id, &user_id, &content, &send_at],
+            )
+            .await?;
+        tx.commit().await?;
+
+        Ok(row.as_ref().map(scheduled::ScheduledMessage::from))
+    }
+
+    async fn cancel_scheduled_message(
+        &self,
+        tenant_id: i32,
+        scheduled_id: i64,
+        user_id: i32,
+    ) -> Result<bool, DomainError> {
+        let mut client = self.pool.get().await?;
+        let tx = tenant_transaction(&mut client, tenant_id).await?;
+        let stmt = tx
+            .prepare_cached(
+                // language=postgresql
+                r#"
+                DELETE FROM scheduled_messages
+                WHERE tenant_id = $1
+                  AND scheduled_id = $2
+                  AND user_id = $3;
+                "#,
+            )
+            .await?;
+
+        let deleted = tx
+            .execute(&stmt, &[&tenant_id, &scheduled_id, &user_id])
+            .await?;
+        tx.commit().await?;
+
+        Ok(deleted > 0)
+    }
+
+    async fn publish_due_messages(&self, limit: i64) -> Result<u64, DomainError> {
+        let mut client = self.pool.get().await?;
+        let tx = all_tenants_transaction(&mut client).await?;
+        let stmt = tx
+            .prepare_cached(
+                // language=postgresql
+                r#"
+                WITH due AS (DELETE FROM scheduled_messages
+                             WHERE scheduled_id IN (SELECT scheduled_id
+                                                    FROM scheduled_messages
+                                                    WHERE send_at <= now()
+                                                      AND tenant_id IN (SELECT tenant_id
+                                                                        FROM tenants
+                                                                        WHERE suspended_at IS NULL)
+                                                    ORDER BY send_at
+                                                    LIMIT $1 FOR UPDATE SKIP LOCKED)
+                             RETURNING tenant_id, message_content, user_id, send_at, expires_in)
+                INSERT INTO messages (tenant_id, message_content, user_id, posted_at, expires_at)
+                SELECT tenant_id,
+                       message_content,
+                       user_id,
+                       send_at,
+                       send_at + make_interval(secs => expires_in::double precision)
+                FROM due
+                RETURNING tenant_id       AS tenant_id,
+                          message_id      AS message_id,
+                          message_content AS message_content,
+                          user_id         AS user_id,
+                          posted_at       AS posted_at,
+                          expires_at      AS expires_at;
+                "#,
+            )
+            .await?;
+
+        let rows = tx.query(&stmt, &[&limit]).await?;
+
+        let event_type = webhook::EventType::MessageCreated;
+        for row in &rows {
+            let tenant_id: i32 = row.get("tenant_id");
+            let published = message::Message::from(row);
+            webhooks::enqueue_event(
+                &tx,
+                tenant_id,
+                event_type,
+                &webhook::message_event(event_type, tenant_id, &published),
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(rows.len() as u64)
+    }
+}
@@ -0,0 +1,85 @@
+use tokio_postgres::types::ToSql;
+
+/// `WHERE` clause assembled from optional conditions. Values are always bound as positional
+/// parameters, so request input never ends up in the SQL text.
+#[derive(Default)]
+pub(crate) struct Conditions {
+    conditions: Vec<String>,
+    params: Vec<Box<dyn ToSql + Sync + Send>>,
+}
+
+impl Conditions {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a parameter and returns its placeholder, e.g. `$3`.
+    pub(crate) fn bind<T>(&mut self, value: T) -> String
+    where
+        T: ToSql + Sync + Send + 'static,
+    {
+        self.params.push(Box::new(value));
+        format!("${}", self.params.len())
+    }
+
+    pub(crate) fn and(&mut self, condition: impl Into<String>) {
+        self.conditions.push(condition.into());
+    }
+
+    pub(crate) fn where_clause(&self) -> String {
+        if self.conditions.is_empty() {
+            return String::new();
+        }
+        format!("WHERE {}", self.conditions.join("\n  AND "))
+    }
+
+    pub(crate) fn params(&self) -> Vec<&(dyn ToSql + Sync)> {
+        self.params
+            .iter()
+            .map(|param| param.as_ref() as &(dyn ToSql + Sync))
+            .collect()
+    }
+}
+
+/// Escapes `LIKE` wildcards so the text is matched literally.
+pub(crate) fn escape_like(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        if matches!(ch, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conditions_bind_in_order() {
+        let mut conditions = Conditions::new();
+
+        let user_id = conditions.bind(7);
+        conditions.and(format!("user_id = {user_id}"));
+        let text = conditions.bind("hi".to_string());
+        conditions.and(format!("content ILIKE {text}"));
+
+        assert_eq!(
+            conditions.where_clause(),
+            "WHERE user_id = $1\n  AND content ILIKE $2"
+        );
+        assert_eq!(conditions.params().len(), 2);
+    }
+
+    #[test]
+    fn test_conditions_empty() {
+        assert_eq!(Conditions::new().where_clause(), "");
+    }
+
+    #[test]
+    fn test_escape_like() {
+        assert_eq!(escape_like(r"50%_off\now"), r"50\%\_off\\now");
+    }
+}
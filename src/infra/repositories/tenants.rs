@@ -0,0 +1,121 @@
+use async_trait::async_trait;
+use deadpool_postgres::{Client, Pool, Transaction};
+use mockall::*;
+
+use crate::domain::{errors::DomainError, tenant};
+
+#[async_trait]
+#[automock]
+pub trait TenantsRepositoryTrait: Send + Sync {
+    async fn create_tenant(&self, name: String) -> Result<tenant::Tenant, DomainError>;
+    async fn get_tenant(&self, tenant_id: i32) -> Result<Option<tenant::Tenant>, DomainError>;
+    /// Suspends tenant, suspending twice keeps the first suspension time. Returns `None` when
+    /// the tenant does not exist.
+    async fn suspend_tenant(&self, tenant_id: i32) -> Result<Option<tenant::Tenant>, DomainError>;
+}
+
+#[derive(Clone)]
+pub struct TenantsRepository {
+    pool: Pool,
+}
+
+impl TenantsRepository {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TenantsRepositoryTrait for TenantsRepository {
+    async fn create_tenant(&self, name: String) -> Result<tenant::Tenant, DomainError> {
+        let client = self.pool.get().await?;
+        let stmt = client
+            .prepare_cached(
+                // language=postgresql
+                r#"
+                INSERT INTO tenants (name)
+                VALUES ($1)
+                RETURNING tenant_id    AS tenant_id,
+                          name         AS name,
+                          created_at   AS created_at,
+                          suspended_at AS suspended_at;
+                "#,
+            )
+            .await?;
+
+        let row = client.query_one(&stmt, &[&name]).await?;
+
+        Ok(tenant::Tenant::from(&row))
+    }
+
+    async fn get_tenant(&self, tenant_id: i32) -> Result<Option<tenant::Tenant>, DomainError> {
+        let client = self.pool.get().await?;
+        let stmt = client
+            .prepare_cached(
+                // language=postgresql
+                r#"
+                SELECT tenant_id    AS tenant_id,
+                       name         AS name,
+                       created_at   AS created_at,
+                       suspended_at AS suspended_at
+                FROM tenants
+                WHERE tenant_id = $1;
+                "#,
+            )
+            .await?;
+
+        let row = client.query_opt(&stmt, &[&tenant_id]).await?;
+
+        Ok(row.as_ref().map(tenant::Tenant::from))
+    }
+
+    async fn suspend_tenant(&self, tenant_id: i32) -> Result<Option<tenant::Tenant>, DomainError> {
+        let client = self.pool.get().await?;
+        let stmt = client
+            .prepare_cached(
+                // language=postgresql
+                r#"
+                UPDATE tenants
+                SET suspended_at = coalesce(suspended_at, now())
+                WHERE tenant_id = $1
+                RETURNING tenant_id    AS tenant_id,
+                          name         AS name,
+                          created_at   AS created_at,
+                          suspended_at AS suspended_at;
+                "#,
+            )
+            .await?;
+
+        let row = client.query_opt(&stmt, &[&tenant_id]).await?;
+
+        Ok(row.as_ref().map(tenant::Tenant::from))
+    }
+}
+
+/// Starts transaction seeing rows of the tenant only. Row-level security of every tenant owned
+/// table relies on it, statements outside of such transactions see no rows of them at all.
+pub(crate) async fn tenant_transaction(
+    client: &mut Client,
+    tenant_id: i32,
+) -> Result<Transaction<'_>, DomainError> {
+    let tx = client.transaction().await?;
+    tx.execute(
+        "SELECT set_config('app.tenant_id', $1, true);",
+        &[&tenant_id.to_string()],
+    )
+    .await?;
+
+    Ok(tx)
+}
+
+/// Starts transaction seeing rows of every tenant, for maintenance jobs and for lookups that
+/// find out the tenant, like integration tokens.
+pub(crate) async fn all_tenants_transaction(
+    client: &mut Client,
+) -> Result<Transaction<'_>, DomainError> {
+    let tx = client.transaction().await?;
+    tx.execute("SELECT set_config('app.all_tenants', 'on', true);", &[])
+        .await?;
+
+    Ok(tx)
+}
@@ -0,0 +1,320 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use deadpool_postgres::{GenericClient, Object, Pool};
+use mockall::*;
+
+use crate::{
+    domain::{errors::DomainError, message},
+    infra::{
+        postgres::Pools,
+        repositories::{bookmarks, messages, pins},
+    },
+};
+
+/// Writes of one tenant applied together on commit. Dropping the unit without commit rolls
+/// it back. Message changes queue their webhook events within the unit, like the messages
+/// repository does.
+#[async_trait]
+#[automock]
+pub trait UnitOfWorkTrait: Send {
+    async fn create_message(&mut self, msg: message::PostMessage) -> Result<i64, DomainError>;
+    /// Fails with `NotFound` for missing messages and `Forbidden` for messages of others.
+    async fn update_message(
+        &mut self,
+        message_id: i64,
+        user_id: i32,
+        content: String,
+        edited_at: DateTime<Utc>,
+    ) -> Result<message::Message, DomainError>;
+    async fn delete_message(
+        &mut self,
+        message_id: i64,
+        user_id: i32,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<message::Message, DomainError>;
+    async fn pin_message(&mut self, message_id: i64, pinned_by: i32) -> Result<bool, DomainError>;
+    async fn unpin_message(&mut self, message_id: i64) -> Result<bool, DomainError>;
+    async fn add_bookmark(&mut self, user_id: i32, message_id: i64) -> Result<bool, DomainError>;
+    async fn remove_bookmark(&mut self, user_id: i32, message_id: i64)
+    -> Result<bool, DomainError>;
+    async fn commit(self: Box<Self>) -> Result<(), DomainError>;
+    async fn rollback(self: Box<Self>) -> Result<(), DomainError>;
+}
+
+#[async_trait]
+#[automock]
+pub trait UnitOfWorkFactoryTrait: Send + Sync {
+    /// Starts unit of work seeing rows of the tenant only.
+    async fn begin(&self, tenant_id: i32) -> Result<Box<dyn UnitOfWorkTrait>, DomainError>;
+}
+
+#[cfg(test)]
+impl MockUnitOfWorkFactoryTrait {
+    /// Factory of one unit of the tenant committing a message matching `matches` as
+    /// `message_id`.
+    pub fn posting<F>(tenant_id: i32, matches: F, message_id: i64) -> Self
+    where
+        F: Fn(&message::PostMessage) -> bool + Send + 'static,
+    {
+        let mut unit = MockUnitOfWorkTrait::default();
+        unit.expect_create_message()
+            .withf(matches)
+            .once()
+            .returning(move |_| Box::pin(async move { Ok(message_id) }));
+        unit.expect_commit()
+            .once()
+            .returning(|| Box::pin(async { Ok(()) }));
+
+        let mut unit = Some(unit);
+        let mut factory = Self::default();
+        factory
+            .expect_begin()
+            .with(predicate::eq(tenant_id))
+            .once()
+            .returning(move |_| {
+                let unit = unit.take().unwrap();
+                Box::pin(async move { Ok(Box::new(unit) as Box<dyn UnitOfWorkTrait>) })
+            });
+
+        factory
+    }
+}
+
+#[derive(Clone)]
+pub struct UnitOfWorkFactory {
+    pools: Pools,
+}
+
+impl UnitOfWorkFactory {
+    pub fn new(pool: Pool) -> Self {
+        Self::with_pools(Pools::new(pool))
+    }
+
+    pub fn with_pools(pools: Pools) -> Self {
+        Self { pools }
+    }
+}
+
+#[async_trait]
+impl UnitOfWorkFactoryTrait for UnitOfWorkFactory {
+    async fn begin(&self, tenant_id: i32) -> Result<Box<dyn UnitOfWorkTrait>, DomainError> {
+        let client = self.pools.write(tenant_id).await?;
+        // the transaction outlives this call, so it is driven by statements instead of
+        // a borrowed `Transaction`
+        let unit = UnitOfWork {
+            client: Some(client),
+            tenant_id,
+        };
+        let client = unit.client()?;
+        client.batch_execute("BEGIN;").await?;
+        client
+            .execute(
+                "SELECT set_config('app.tenant_id', $1, true);",
+                &[&tenant_id.to_string()],
+            )
+            .await?;
+
+        Ok(Box::new(unit))
+    }
+}
+
+/// Transaction on a connection taken from the pool until commit or rollback.
+pub struct UnitOfWork {
+    client: Option<Object>,
+    tenant_id: i32,
+}
+
+impl UnitOfWork {
+    fn client(&self) -> Result<&Object, DomainError> {
+        self.client
+            .as_ref()
+            .ok_or_else(|| DomainError::Internal(anyhow::anyhow!("unit of work is finished")))
+    }
+
+    async fn finish(mut self: Box<Self>, statement: &str) -> Result<(), DomainError> {
+        self.client()?.batch_execute(statement).await?;
+        // back to the pool only once the transaction is over
+        self.client.take();
+
+        Ok(())
+    }
+}
+
+impl Drop for UnitOfWork {
+    fn drop(&mut self) {
+        // the transaction may still be open, closing the connection rolls it back
+        if let Some(client) = self.client.take() {
+            drop(Object::take(client));
+        }
+    }
+}
+
+#[async_trait]
+impl UnitOfWorkTrait for UnitOfWork {
+    async fn create_message(&mut self, msg: message::PostMessage) -> Result<i64, DomainError> {
+        let tenant_id = self.tenant_id;
+        let created = messages::insert_message(self.client()?, tenant_id, msg).await?;
+
+        Ok(created.message_id)
+    }
+
+    async fn update_message(
+        &mut self,
+        message_id: i64,
+        user_id: i32,
+        content: String,
+        edited_at: DateTime<Utc>,
+    ) -> Result<message::Message, DomainError> {
+        let tenant_id = self.tenant_id;
+        messages::edit_message(
+            self.client()?,
+            tenant_id,
+            message_id,
+            user_id,
+            content,
+            edited_at,
+        )
+        .await
+    }
+
+    async fn delete_message(
+        &mut self,
+        message_id: i64,
+        user_id: i32,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<message::Message, DomainError> {
+        let tenant_id = self.tenant_id;
+        messages::mark_message_deleted(self.client()?, tenant_id, message_id, user_id, deleted_at)
+            .await
+    }
+
+    async fn pin_message(&mut self, message_id: i64, pinned_by: i32) -> Result<bool, DomainError> {
+        let tenant_id = self.tenant_id;
+        pins::insert_pin(self.client()?, tenant_id, message_id, pinned_by).await
+    }
+
+    async fn unpin_message(&mut self, message_id: i64) -> Result<bool, DomainError> {
+        let tenant_id = self.tenant_id;
+        pins::delete_pin(self.client()?, tenant_id, message_id).await
+    }
+
+    async fn add_bookmark(&mut self, user_id: i32, message_id: i64) -> Result<bool, DomainError> {
+        let tenant_id = self.tenant_id;
+        bookmarks::insert_bookmark(self.client()?, tenant_id, user_id, message_id).await
+    }
+
+    async fn remove_bookmark(
+        &mut self,
+        user_id: i32,
+        message_id: i64,
+    ) -> Result<bool, DomainError> {
+        let tenant_id = self.tenant_id;
+        bookmarks::delete_bookmark(self.client()?, tenant_id, user_id, message_id).await
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), DomainError> {
+        self.finish("COMMIT;").await
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), DomainError> {
+        self.finish("ROLLBACK;").await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::crypto::random_token,
+        infra::{migrations, postgres, repositories::messages::MessagesRepositoryTrait},
+    };
+
+    fn post(content: &str) -> message::PostMessage {
+        message::PostMessage {
+            content: content.to_string(),
+            user_id: 123,
+            posted_at: Utc::now(),
+            expires_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unit_of_work_is_mockable() {
+        let mut factory = MockUnitOfWorkFactoryTrait::default();
+        factory.expect_begin().once().returning(|_| {
+            let mut unit = MockUnitOfWorkTrait::default();
+            unit.expect_create_message()
+                .once()
+                .returning(|_| Box::pin(async { Ok(1) }));
+            unit.expect_commit()
+                .once()
+                .returning(|| Box::pin(async { Ok(()) }));
+            Box::pin(async move { Ok(Box::new(unit) as Box<dyn UnitOfWorkTrait>) })
+        });
+
+        let mut unit = factory.begin(1).await.unwrap();
+        assert_eq!(unit.create_message(post("a")).await.unwrap(), 1);
+        unit.commit().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_postgres_unit_of_work() {
+        let Ok(url) = std::env::var("TEST_POSTGRES_URL") else {
+            return;
+        };
+        let schema = format!("unit_of_work_{}", &random_token()[..12]);
+        let pool = deadpool_postgres::Config {
+            url: Some(url),
+            options: Some(postgres::search_path_option(&schema)),
+            ..Default::default()
+        }
+        .create_pool(
+            Some(deadpool_postgres::Runtime::Tokio1),
+            tokio_postgres::NoTls,
+        )
+        .unwrap();
+        migrations::migrate(&pool, &schema).await.unwrap();
+
+        let factory = UnitOfWorkFactory::new(pool.clone());
+        let messages = crate::infra::repositories::MessagesRepository::new(pool.clone());
+        let visible = || async {
+            messages
+                .count_messages(1, Default::default())
+                .await
+                .unwrap()
+        };
+
+        let mut unit = factory.begin(1).await.unwrap();
+        let message_id = unit.create_message(post("kept")).await.unwrap();
+        assert!(unit.pin_message(message_id, 123).await.unwrap());
+        assert_eq!(visible().await, 0);
+        unit.commit().await.unwrap();
+        assert_eq!(visible().await, 1);
+
+        let mut unit = factory.begin(1).await.unwrap();
+        unit.create_message(post("rolled back")).await.unwrap();
+        unit.rollback().await.unwrap();
+        assert_eq!(visible().await, 1);
+
+        let mut unit = factory.begin(1).await.unwrap();
+        unit.create_message(post("dropped")).await.unwrap();
+        drop(unit);
+        assert_eq!(visible().await, 1);
+
+        // failed statements leave nothing behind either
+        let mut unit = factory.begin(1).await.unwrap();
+        assert!(matches!(
+            unit.update_message(message_id, 7, "x".to_string(), Utc::now())
+                .await,
+            Err(DomainError::Forbidden)
+        ));
+        unit.rollback().await.unwrap();
+
+        pool.get()
+            .await
+            .unwrap()
+            .batch_execute(&format!("DROP SCHEMA {schema} CASCADE;"))
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,119 @@
+use async_trait::async_trait;
+use deadpool_postgres::Pool;
+use mockall::*;
+
+use crate::{
+    domain::{errors::DomainError, user},
+    infra::{postgres::Pools, repositories::tenants::tenant_transaction},
+};
+
+#[async_trait]
+#[automock]
+pub trait UsersRepositoryTrait: Send + Sync {
+    async fn get_profile(
+        &self,
+        tenant_id: i32,
+        user_id: i32,
+    ) -> Result<Option<user::UserProfile>, DomainError>;
+    /// Creates profile on first change.
+    async fn update_profile(
+        &self,
+        tenant_id: i32,
+        user_id: i32,
+        change: user::ProfileChange,
+    ) -> Result<user::UserProfile, DomainError>;
+}
+
+#[derive(Clone)]
+pub struct UsersRepository {
+    pools: Pools,
+}
+
+impl UsersRepository {
+    pub fn new(pool: Pool) -> Self {
+        Self::with_pools(Pools::new(pool))
+    }
+
+    /// Creates repository reading from the replica of `pools` when there is one.
+    pub fn with_pools(pools: Pools) -> Self {
+        Self { pools }
+    }
+}
+
+#[async_trait]
+impl UsersRepositoryTrait for UsersRepository {
+    async fn get_profile(
+        &self,
+        tenant_id: i32,
+        user_id: i32,
+    ) -> Result<Option<user::UserProfile>, DomainError> {
+        let mut client = self.pools.read(tenant_id).await?;
+        let tx = tenant_transaction(&mut client, tenant_id).await?;
+        let stmt = tx
+            .prepare_cached(
+                // language=postgresql
+                r#"
+                SELECT user_id      AS user_id,
+                       display_name AS display_name,
+                       avatar_url   AS avatar_url,
+                       bio          AS bio
+                FROM users
+                WHERE tenant_id = $1
+                  AND user_id = $2;
+                "#,
+            )
+            .await?;
+
+        let row = tx.query_opt(&stmt, &[&tenant_id, &user_id]).await?;
+        tx.commit().await?;
+
+        Ok(row.as_ref().map(user::UserProfile::from))
+    }
+
+    async fn update_profile(
+        &self,
+        tenant_id: i32,
+        user_id: i32,
+        change: user::ProfileChange,
+    ) -> Result<user::UserProfile, DomainError> {
+        let mut client = self.pools.write(tenant_id).await?;
+        let tx = tenant_transaction(&mut client, tenant_id).await?;
+        let stmt = tx
+            .prepare_cached(
+                // language=postgresql
+                r#"
+                INSERT INTO users AS u (tenant_id, user_id, display_name, avatar_url, bio)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (tenant_id, user_id) DO UPDATE
+                    SET display_name = CASE WHEN $6 THEN excluded.display_name ELSE u.display_name END,
+                        avatar_url   = CASE WHEN $7 THEN excluded.avatar_url ELSE u.avatar_url END,
+                        bio          = CASE WHEN $8 THEN excluded.bio ELSE u.bio END,
+                        updated_at   = now()
+                RETURNING user_id      AS user_id,
+                          display_name AS display_name,
+                          avatar_url   AS avatar_url,
+                          bio          AS bio;
+                "#,
+            )
+            .await?;
+
+        let row = tx
+            .query_one(
+                &stmt,
+                &[
+                    &tenant_id,
+                    &user_id,
+                    &change.display_name.clone().flatten(),
+                    &change.avatar_url.clone().flatten(),
+                    &change.bio.clone().flatten(),
+                    &change.display_name.is_some(),
+                    &change.avatar_url.is_some(),
+                    &change.bio.is_some(),
+                ],
+            )
+            .await?;
+        tx.commit().await?;
+
+        Ok(user::UserProfile::from(&row))
+    }
+}
@@ -0,0 +1,273 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use deadpool_postgres::{GenericClient, Pool};
+use mockall::*;
+use tokio_postgres::Row;
+
+use crate::{
+    domain::{errors::DomainError, webhook},
+    infra::repositories::tenants::{all_tenants_transaction, tenant_transaction},
+};
+
+#[async_trait]
+#[automock]
+pub trait WebhooksRepositoryTrait: Send + Sync {
+    async fn create_webhook(
+        &self,
+        webhook: webhook::NewWebhook,
+    ) -> Result<webhook::Webhook, DomainError>;
+    async fn list_webhooks(&self, tenant_id: i32) -> Result<Vec<webhook::Webhook>, DomainError>;
+    async fn delete_webhook(&self, tenant_id: i32, webhook_id: i64) -> Result<bool, DomainError>;
+    /// Leases due deliveries of active tenants so concurrent workers never pick the same delivery.
+    /// Deliveries of suspended tenants wait.
+    async fn claim_deliveries(
+        &self,
+        limit: i64,
+        lease: Duration,
+    ) -> Result<Vec<webhook::Delivery>, DomainError>;
+    async fn complete_delivery(&self, delivery_id: i64) -> Result<(), DomainError>;
+    /// Schedules next attempt at `retry_at` or moves delivery to dead letters when `None`.
+    async fn fail_delivery(
+        &self,
+        delivery_id: i64,
+        error: String,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<(), DomainError>;
+}
+
+#[derive(Clone)]
+pub struct WebhooksRepository {
+    pool: Pool,
+}
+
+impl WebhooksRepository {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl WebhooksRepositoryTrait for WebhooksRepository {
+    async fn create_webhook(
+        &self,
+        webhook: webhook::NewWebhook,
+    ) -> Result<webhook::Webhook, DomainError> {
+        let mut client = self.pool.get().await?;
+        let tx = tenant_transaction(&mut client, webhook.tenant_id).await?;
+        let stmt = tx
+            .prepare_cached(
+                // language=postgresql
+                r#"
+                INSERT INTO webhooks (tenant_id, url, secret, events, created_by)
+                VALUES ($1, $2, $3, $4, $5)
+                RETURNING webhook_id AS webhook_id,
+                          url        AS url,
+                          events     AS events,
+                          created_by AS created_by,
+                          created_at AS created_at;
+                "#,
+            )
+            .await?;
+
+        let events: Vec<&str> = webhook.events.iter().map(|event| event.as_str()).collect();
+        let row = tx
+            .query_one(
+                &stmt,
+                &[
+                    &webhook.tenant_id,
+                    &webhook.url,
+                    &webhook.secret,
+                    &events,
+                    &webhook.created_by,
+                ],
+            )
+            .await?;
+        tx.commit().await?;
+
+        webhook_from_row(&row)
+    }
+
+    async fn list_webhooks(&self, tenant_id: i32) -> Result<Vec<webhook::Webhook>, DomainError> {
+        let mut client = self.pool.get().await?;
+        let tx = tenant_transaction(&mut client, tenant_id).await?;
+        let stmt = tx
+            .prepare_cached(
+                // language=postgresql
+                r#"
+                SELECT webhook_id AS webhook_id,
+                       url        AS url,
+                       events     AS events,
+                       created_by AS created_by,
+                       created_at AS created_at
+                FROM webhooks
+                WHERE tenant_id = $1
+                ORDER BY webhook_id;
+                "#,
+            )
+            .await?;
+
+        let rows = tx.query(&stmt, &[&tenant_id]).await?;
+        tx.commit().await?;
+
+        rows.iter().map(webhook_from_row).collect()
+    }
+
+    async fn delete_webhook(&self, tenant_id: i32, webhook_id: i64) -> Result<bool, DomainError> {
+        let mut client = self.pool.get().await?;
+        let tx = tenant_transaction(&mut client, tenant_id).await?;
+        let stmt = tx
+            .prepare_cached(
+                // language=postgresql
+                r#"
+                DELETE FROM webhooks
+                WHERE tenant_id = $1
+                  AND webhook_id = $2;
+                "#,
+            )
+            .await?;
+
+        let deleted = tx.execute(&stmt, &[&tenant_id, &webhook_id]).await?;
+        tx.commit().await?;
+
+        Ok(deleted > 0)
+    }
+
+    async fn claim_deliveries(
+        &self,
+        limit: i64,
+        lease: Duration,
+    ) -> Result<Vec<webhook::Delivery>, DomainError> {
+        let mut client = self.pool.get().await?;
+        let tx = all_tenants_transaction(&mut client).await?;
+        let stmt = tx
+            .prepare_cached(
+                // language=postgresql
+                r#"
+                WITH due AS (SELECT delivery_id
+                             FROM webhook_outbox
+                             WHERE status = 'pending'
+                               AND next_attempt_at <= now()
+                               AND tenant_id IN (SELECT tenant_id
+                                                 FROM tenants
+                                                 WHERE suspended_at IS NULL)
+                             ORDER BY next_attempt_at
+                             LIMIT $1 FOR UPDATE SKIP LOCKED)
+                UPDATE webhook_outbox o
+                SET next_attempt_at = now() + make_interval(secs => $2),
+                    attempts        = o.attempts + 1
+                FROM due,
+                     webhooks w
+                WHERE o.delivery_id = due.delivery_id
+                  AND w.webhook_id = o.webhook_id
+                RETURNING o.delivery_id AS delivery_id,
+                          o.webhook_id  AS webhook_id,
+                          w.url         AS url,
+                          w.secret      AS secret,
+                          o.event_type  AS event_type,
+                          o.payload     AS payload,
+                          o.attempts    AS attempts;
+                "#,
+            )
+            .await?;
+
+        let rows = tx.query(&stmt, &[&limit, &lease.as_secs_f64()]).await?;
+        tx.commit().await?;
+
+        Ok(rows.iter().map(webhook::Delivery::from).collect())
+    }
+
+    async fn complete_delivery(&self, delivery_id: i64) -> Result<(), DomainError> {
+        let mut client = self.pool.get().await?;
+        let tx = all_tenants_transaction(&mut client).await?;
+        let stmt = tx
+            .prepare_cached(
+                // language=postgresql
+                r#"
+                UPDATE webhook_outbox
+                SET status       = 'delivered',
+                    delivered_at = now(),
+                    last_error   = NULL
+                WHERE delivery_id = $1;
+                "#,
+            )
+            .await?;
+
+        tx.execute(&stmt, &[&delivery_id]).await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn fail_delivery(
+        &self,
+        delivery_id: i64,
+        error: String,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<(), DomainError> {
+        let mut client = self.pool.get().await?;
+        let tx = all_tenants_transaction(&mut client).await?;
+        let stmt = tx
+            .prepare_cached(
+                // language=postgresql
+                r#"
+                UPDATE webhook_outbox
+                SET status          = CASE WHEN $3::timestamptz IS NULL THEN 'dead' ELSE 'pending' END,
+                    next_attempt_at = coalesce($3, next_attempt_at),
+                    last_error      = $2
+                WHERE delivery_id = $1;
+                "#,
+            )
+            .await?;
+
+        tx.execute(&stmt, &[&delivery_id, &error, &retry_at])
+            .await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
+
+/// Writes event to the outbox for every subscribed webhook of the tenant within caller's
+/// transaction.
+pub(crate) async fn enqueue_event(
+    client: &impl GenericClient,
+    tenant_id: i32,
+    event_type: webhook::EventType,
+    payload: &serde_json::Value,
+) -> Result<(), DomainError> {
+    let stmt = client
+        .prepare_cached(
+            // language=postgresql
+            r#"
+            INSERT INTO webhook_outbox (tenant_id, webhook_id, event_type, payload)
+            SELECT tenant_id, webhook_id, $2, $3
+            FROM webhooks
+            WHERE tenant_id = $1
+              AND $2::varchar = ANY (events);
+            "#,
+        )
+        .await?;
+
+    client
+        .execute(&stmt, &[&tenant_id, &event_type.as_str(), payload])
+        .await?;
+
+    Ok(())
+}
+
+fn webhook_from_row(row: &Row) -> Result<webhook::Webhook, DomainError> {
+    let events: Vec<String> = row.get("events");
+
+    Ok(webhook::Webhook {
+        webhook_id: row.get("webhook_id"),
+        url: row.get("url"),
+        events: events
+            .iter()
+            .map(|event| event.parse())
+            .collect::<Result<_, _>>()?,
+        created_by: row.get("created_by"),
+        created_at: row.get("created_at"),
+    })
+}
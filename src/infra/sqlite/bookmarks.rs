@@ -0,0 +1,138 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use rusqlite::{Connection, params};
+
+use crate::{
+    domain::{bookmark, errors::DomainError},
+    infra::{
+        repositories::bookmarks::BookmarksRepositoryTrait,
+        sqlite::{SqliteStore, from_micros, is_visible_message, to_micros},
+    },
+};
+
+#[derive(Clone)]
+pub struct SqliteBookmarksRepository {
+    store: SqliteStore,
+}
+
+impl SqliteBookmarksRepository {
+    pub fn new(store: SqliteStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl BookmarksRepositoryTrait for SqliteBookmarksRepository {
+    async fn add_bookmark(
+        &self,
+        tenant_id: i32,
+        user_id: i32,
+        message_id: i64,
+    ) -> Result<bool, DomainError> {
+        self.store
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+                let bookmarked = insert_bookmark(&tx, tenant_id, user_id, message_id)?;
+                tx.commit()?;
+
+                Ok(bookmarked)
+            })
+            .await
+    }
+
+    async fn remove_bookmark(
+        &self,
+        tenant_id: i32,
+        user_id: i32,
+        message_id: i64,
+    ) -> Result<bool, DomainError> {
+        self.store
+            .call(move |conn| delete_bookmark(conn, tenant_id, user_id, message_id))
+            .await
+    }
+
+    async fn list_bookmarks(
+        &self,
+        tenant_id: i32,
+        user_id: i32,
+    ) -> Result<Vec<bookmark::Bookmark>, DomainError> {
+        let now = to_micros(Utc::now());
+
+        self.store
+            .call(move |conn| {
+                let mut stmt = conn.prepare_cached(
+                    // language=sqlite
+                    r#"
+                    SELECT b.message_id, m.message_content, m.user_id, m.posted_at, b.bookmarked_at
+                    FROM bookmarks AS b
+                             JOIN messages AS m ON m.message_id = b.message_id
+                    WHERE m.tenant_id = ?1
+                      AND b.user_id = ?2
+                      AND m.deleted_at IS NULL
+                      AND (m.expires_at IS NULL OR m.expires_at > ?3)
+                    ORDER BY b.bookmarked_at DESC;
+                    "#,
+                )?;
+                let bookmarks = stmt
+                    .query_map(params![tenant_id, user_id, now], |row| {
+                        Ok(bookmark::Bookmark {
+                            message_id: row.get(0)?,
+                            message_content: row.get(1)?,
+                            user_id: row.get(2)?,
+                            posted_at: from_micros(row.get(3)?),
+                            bookmarked_at: from_micros(row.get(4)?),
+                        })
+                    })?
+                    .collect::<Result<_, _>>()?;
+
+                Ok(bookmarks)
+            })
+            .await
+    }
+}
+
+/// Bookmarks visible message of the tenant, run within a transaction.
+pub(super) fn insert_bookmark(
+    conn: &Connection,
+    tenant_id: i32,
+    user_id: i32,
+    message_id: i64,
+) -> Result<bool, DomainError> {
+    let now = to_micros(Utc::now());
+    if !is_visible_message(conn, tenant_id, message_id, now)? {
+        return Ok(false);
+    }
+
+    conn.execute(
+        // language=sqlite
+        r#"
+        INSERT INTO bookmarks (user_id, message_id, bookmarked_at)
+        VALUES (?1, ?2, ?3)
+        ON CONFLICT (user_id, message_id) DO NOTHING;
+        "#,
+        params![user_id, message_id, now],
+    )?;
+
+    Ok(true)
+}
+
+pub(super) fn delete_bookmark(
+    conn: &Connection,
+    tenant_id: i32,
+    user_id: i32,
+    message_id: i64,
+) -> Result<bool, DomainError> {
+    let deleted = conn.execute(
+        // language=sqlite
+        r#"
+        DELETE
+        FROM bookmarks
+        WHERE user_id = ?2
+          AND message_id = ?3
+          AND message_id IN (SELECT message_id FROM messages WHERE tenant_id = ?1);
+        "#,
+        params![tenant_id, user_id, message_id],
+    )?;
+
+    Ok(deleted > 0)
+}
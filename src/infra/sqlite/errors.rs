@@ -0,0 +1,19 @@
+use rusqlite::ErrorCode;
+
+use crate::domain::errors::DomainError;
+
+impl From<rusqlite::Error> for DomainError {
+    fn from(err: rusqlite::Error) -> Self {
+        match err.sqlite_error_code() {
+            Some(ErrorCode::ConstraintViolation) => DomainError::Conflict(err.to_string()),
+            Some(
+                ErrorCode::DatabaseBusy
+                | ErrorCode::DatabaseLocked
+                | ErrorCode::CannotOpen
+                | ErrorCode::DiskFull
+                | ErrorCode::SystemIoFailure,
+            ) => DomainError::Unavailable(err.to_string()),
+            _ => DomainError::Internal(err.into()),
+        }
+    }
+}
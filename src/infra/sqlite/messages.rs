@@ -0,0 +1,660 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures_util::{StreamExt, TryStreamExt, stream, stream::BoxStream};
+use rusqlite::{Connection, OptionalExtension, Row, params, params_from_iter, types::Value};
+
+use crate::{
+    domain::{errors::DomainError, message},
+    infra::{
+        repositories::{messages::MessagesRepositoryTrait, sql::escape_like},
+        sqlite::{SqliteStore, from_micros, sql::Conditions, to_micros},
+    },
+};
+
+/// SQLite counterpart of [`crate::infra::repositories::MessagesRepository`]. Webhook events
+/// are not recorded, since there is no worker to deliver them.
+#[derive(Clone)]
+pub struct SqliteMessagesRepository {
+    store: SqliteStore,
+}
+
+impl SqliteMessagesRepository {
+    pub fn new(store: SqliteStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl MessagesRepositoryTrait for SqliteMessagesRepository {
+    async fn create_message(
+        &self,
+        tenant_id: i32,
+        msg: message::PostMessage,
+    ) -> Result<i64, DomainError> {
+        self.store
+            .call(move |conn| insert_message(conn, tenant_id, msg))
+            .await
+    }
+
+    async fn update_message(
+        &self,
+        tenant_id: i32,
+        message_id: i64,
+        user_id: i32,
+        content: String,
+        edited_at: DateTime<Utc>,
+    ) -> Result<message::Message, DomainError> {
+        self.store
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+                let edited = edit_message(&tx, tenant_id, message_id, user_id, content, edited_at)?;
+                tx.commit()?;
+
+                Ok(edited)
+            })
+            .await
+    }
+
+    async fn delete_message(
+        &self,
+        tenant_id: i32,
+        message_id: i64,
+        user_id: i32,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<message::Message, DomainError> {
+        self.store
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+                let deleted =
+                    mark_message_deleted(&tx, tenant_id, message_id, user_id, deleted_at)?;
+                tx.commit()?;
+
+                Ok(deleted)
+            })
+            .await
+    }
+
+    async fn import_messages(
+        &self,
+        tenant_id: i32,
+        msgs: Vec<message::ImportMessage>,
+    ) -> Result<u64, DomainError> {
+        self.store
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+                let mut inserted = 0;
+                {
+                    let mut stmt = tx.prepare_cached(
+                        // language=sqlite
+                        r#"
+                        INSERT INTO messages (tenant_id, external_id, message_content, user_id, posted_at)
+                        VALUES (?1, ?2, ?3, ?4, ?5)
+                        ON CONFLICT (tenant_id, external_id) DO NOTHING;
+                        "#,
+                    )?;
+                    for msg in msgs {
+                        inserted += stmt.execute(params![
+                            tenant_id,
+                            msg.external_id,
+                            msg.content,
+                            msg.user_id,
+                            to_micros(msg.posted_at),
+                        ])? as u64;
+                    }
+                }
+                tx.commit()?;
+
+                Ok(inserted)
+            })
+            .await
+    }
+
+    async fn list_messages(
+        &self,
+        tenant_id: i32,
+        filter: message::MessageFilter,
+        offset: i64,
+        limit: i64,
+    ) -> Result<Vec<message::AuthoredMessage>, DomainError> {
+        self.store
+            .call(move |conn| {
+                let mut conditions = filter_conditions(tenant_id, &filter);
+                let tail = format!(
+                    "ORDER BY m.posted_at {order}, m.message_id {order} LIMIT {limit} OFFSET {offset}",
+                    order = sort_order(filter.order),
+                    limit = conditions.bind(limit.max(0)),
+                    offset = conditions.bind(offset.max(0)),
+                );
+
+                let mut stmt = conn.prepare(&authored_messages_query(&conditions, &tail))?;
+                let messages = stmt
+                    .query_map(conditions.params(), authored_from_row)?
+                    .collect::<Result<_, _>>()?;
+
+                Ok(messages)
+            })
+            .await
+    }
+
+    async fn list_messages_page(
+        &self,
+        tenant_id: i32,
+        filter: message::MessageFilter,
+        cursor: Option<message::Cursor>,
+        limit: i64,
+    ) -> Result<Vec<message::AuthoredMessage>, DomainError> {
+        self.store
+            .call(move |conn| {
+                let mut conditions = filter_conditions(tenant_id, &filter);
+
+                // previous page is read backwards from the cursor and reversed afterwards
+                let backwards = cursor
+                    .as_ref()
+                    .is_some_and(|cursor| cursor.direction == message::Direction::Prev);
+                let order = match (filter.order, backwards) {
+                    (message::SortOrder::Asc, false) | (message::SortOrder::Desc, true) => {
+                        message::SortOrder::Asc
+                    }
+                    _ => message::SortOrder::Desc,
+                };
+
+                if let Some(cursor) = &cursor {
+                    let posted_at = conditions.bind(to_micros(cursor.posted_at));
+                    let message_id = conditions.bind(cursor.message_id);
+                    let comparison = match order {
+                        message::SortOrder::Asc => ">",
+                        message::SortOrder::Desc => "<",
+                    };
+                    conditions.and(format!(
+                        "(m.posted_at, m.message_id) {comparison} ({posted_at}, {message_id})"
+                    ));
+                }
+
+                let tail = format!(
+                    "ORDER BY m.posted_at {order}, m.message_id {order} LIMIT {limit}",
+                    order = sort_order(order),
+                    limit = conditions.bind(limit.max(0)),
+                );
+
+                let mut stmt = conn.prepare(&authored_messages_query(&conditions, &tail))?;
+                let mut messages: Vec<_> = stmt
+                    .query_map(conditions.params(), authored_from_row)?
+                    .collect::<Result<_, _>>()?;
+                if backwards {
+                    messages.reverse();
+                }
+
+                Ok(messages)
+            })
+            .await
+    }
+
+    async fn count_messages(
+        &self,
+        tenant_id: i32,
+        filter: message::MessageFilter,
+    ) -> Result<i64, DomainError> {
+        self.store
+            .call(move |conn| {
+                let conditions = filter_conditions(tenant_id, &filter);
+                let count = conn.query_row(
+                    &format!(
+                        // language=sqlite
+                        r#"
+                        SELECT count(*) AS total
+                        FROM messages AS m
+                        {where_clause};
+                        "#,
+                        where_clause = conditions.where_clause(),
+                    ),
+                    conditions.params(),
+                    |row| row.get(0),
+                )?;
+
+                Ok(count)
+            })
+            .await
+    }
+
+    async fn messages_version(
+        &self,
+        tenant_id: i32,
+        filter: message::MessageFilter,
+    ) -> Result<message::ListingVersion, DomainError> {
+        self.store
+            .call(move |conn| {
+                let mut conditions = tenant_conditions(tenant_id);
+                let now = conditions.bind(to_micros(Utc::now()));
+                let conditions = match_conditions(&filter, conditions);
+
+                let version = conn.query_row(
+                    &format!(
+                        // language=sqlite
+                        r#"
+                        SELECT max(m.message_id) AS max_message_id,
+                               max(max(m.posted_at),
+                                   coalesce(max(m.edited_at), 0),
+                                   coalesce(max(m.deleted_at), 0),
+                                   coalesce(max(u.updated_at), 0)) AS last_modified,
+                               coalesce(sum(m.deleted_at IS NULL
+                                   AND (m.expires_at IS NULL OR m.expires_at > {now})), 0) AS visible
+                        FROM messages AS m
+                                 LEFT JOIN users AS u
+                                           ON u.tenant_id = m.tenant_id AND u.user_id = m.user_id
+                        {where_clause};
+                        "#,
+                        where_clause = conditions.where_clause(),
+                    ),
+                    conditions.params(),
+                    |row| {
+                        Ok(message::ListingVersion {
+                            max_message_id: row.get(0)?,
+                            last_modified: row.get::<_, Option<i64>>(1)?.map(from_micros),
+                            visible: row.get(2)?,
+                        })
+                    },
+                )?;
+
+                Ok(version)
+            })
+            .await
+    }
+
+    async fn get_message(
+        &self,
+        tenant_id: i32,
+        message_id: i64,
+    ) -> Result<message::AuthoredMessage, DomainError> {
+        self.store
+            .call(move |conn| {
+                let mut conditions = filter_conditions(tenant_id, &Default::default());
+                let param = conditions.bind(message_id);
+                conditions.and(format!("m.message_id = {param}"));
+
+                conn.query_row(
+                    &authored_messages_query(&conditions, ""),
+                    conditions.params(),
+                    authored_from_row,
+                )
+                .optional()?
+                .ok_or(DomainError::NotFound("message"))
+            })
+            .await
+    }
+
+    async fn get_messages(
+        &self,
+        tenant_id: i32,
+        message_ids: Vec<i64>,
+    ) -> Result<Vec<message::AuthoredMessage>, DomainError> {
+        if message_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.store
+            .call(move |conn| {
+                let mut conditions = filter_conditions(tenant_id, &Default::default());
+                let params: Vec<_> = message_ids
+                    .into_iter()
+                    .map(|message_id| conditions.bind(message_id))
+                    .collect();
+                conditions.and(format!("m.message_id IN ({})", params.join(", ")));
+
+                let mut stmt = conn.prepare(&authored_messages_query(&conditions, ""))?;
+                let messages = stmt
+                    .query_map(conditions.params(), authored_from_row)?
+                    .collect::<Result<_, _>>()?;
+
+                Ok(messages)
+            })
+            .await
+    }
+
+    fn stream_messages(
+        &self,
+        tenant_id: i32,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> BoxStream<'static, Result<message::Message, DomainError>> {
+        let filter = message::MessageFilter {
+            since,
+            until,
+            order: message::SortOrder::Asc,
+            ..Default::default()
+        };
+
+        // the connection can't be held across polls, so the export is read at once
+        let store = self.store.clone();
+        let messages = async move {
+            store
+                .call(move |conn| {
+                    let conditions = filter_conditions(tenant_id, &filter);
+                    let mut stmt = conn.prepare(&format!(
+                        // language=sqlite
+                        r#"
+                SELECT m.message_id, m.message_content, m.user_id, m.posted_at, m.expires_at
+                FROM messages AS m
+                {where_clause}
+                ORDER BY m.posted_at, m.message_id;
+                "#,
+                        where_clause = conditions.where_clause(),
+                    ))?;
+                    let messages: Vec<_> = stmt
+                        .query_map(conditions.params(), message_from_row)?
+                        .collect::<Result<_, _>>()?;
+
+                    Ok(messages)
+                })
+                .await
+        };
+
+        stream::once(messages)
+            .map_ok(|messages| stream::iter(messages.into_iter().map(Ok)))
+            .try_flatten()
+            .boxed()
+    }
+
+    async fn count_retention_candidates(
+        &self,
+        before: DateTime<Utc>,
+        action: message::RetentionAction,
+    ) -> Result<i64, DomainError> {
+        self.store
+            .call(move |conn| {
+                let count = conn.query_row(
+                    // language=sqlite
+                    r#"
+                    SELECT count(*) AS candidates
+                    FROM messages
+                    WHERE posted_at < ?1
+                      AND (?2 OR user_id <> ?3);
+                    "#,
+                    params![
+                        to_micros(before),
+                        action == message::RetentionAction::Delete,
+                        message::ANONYMOUS_USER_ID,
+                    ],
+                    |row| row.get(0),
+                )?;
+
+                Ok(count)
+            })
+            .await
+    }
+
+    async fn apply_retention(
+        &self,
+        before: DateTime<Utc>,
+        action: message::RetentionAction,
+        limit: i64,
+    ) -> Result<u64, DomainError> {
+        let before = to_micros(before);
+        let limit = limit.max(0);
+        let now = to_micros(Utc::now());
+
+        self.store
+            .call(move |conn| {
+                let changed = match action {
+                    message::RetentionAction::Delete => conn.execute(
+                        // language=sqlite
+                        r#"
+                        DELETE
+                        FROM messages
+                        WHERE message_id IN (SELECT message_id
+                                             FROM messages
+                                             WHERE posted_at < ?1
+                                             LIMIT ?2);
+                        "#,
+                        params![before, limit],
+                    )?,
+                    message::RetentionAction::Anonymize => conn.execute(
+                        // language=sqlite
+                        r#"
+                        UPDATE messages
+                        SET user_id   = ?2,
+                            -- moves listing versions, the author is part of the response
+                            edited_at = ?4
+                        WHERE message_id IN (SELECT message_id
+                                             FROM messages
+                                             WHERE posted_at < ?1
+                                               AND user_id <> ?2
+                                             LIMIT ?3);
+                        "#,
+                        params![before, message::ANONYMOUS_USER_ID, limit, now],
+                    )?,
+                };
+
+                Ok(changed as u64)
+            })
+            .await
+    }
+
+    async fn delete_expired_messages(&self, limit: i64) -> Result<u64, DomainError> {
+        let now = to_micros(Utc::now());
+
+        self.store
+            .call(move |conn| {
+                let deleted = conn.execute(
+                    // language=sqlite
+                    r#"
+                    DELETE
+                    FROM messages
+                    WHERE message_id IN (SELECT message_id
+                                         FROM messages
+                                         WHERE expires_at <= ?1
+                                         LIMIT ?2);
+                    "#,
+                    params![now, limit.max(0)],
+                )?;
+
+                Ok(deleted as u64)
+            })
+            .await
+    }
+}
+
+pub(super) fn insert_message(
+    conn: &Connection,
+    tenant_id: i32,
+    msg: message::PostMessage,
+) -> Result<i64, DomainError> {
+    let message_id = conn.query_row(
+        // language=sqlite
+        r#"
+        INSERT INTO messages (tenant_id, message_content, user_id, posted_at, expires_at)
+        VALUES (?1, ?2, ?3, ?4, ?5)
+        RETURNING message_id;
+        "#,
+        params![
+            tenant_id,
+            msg.content,
+            msg.user_id,
+            to_micros(msg.posted_at),
+            msg.expires_at.map(to_micros),
+        ],
+        |row| row.get(0),
+    )?;
+
+    Ok(message_id)
+}
+
+/// Changes content of a message written by `user_id`, run within a transaction.
+pub(super) fn edit_message(
+    conn: &Connection,
+    tenant_id: i32,
+    message_id: i64,
+    user_id: i32,
+    content: String,
+    edited_at: DateTime<Utc>,
+) -> Result<message::Message, DomainError> {
+    change_message(
+        conn,
+        tenant_id,
+        message_id,
+        user_id,
+        "message_content = ?2, edited_at = ?3",
+        vec![content.into(), to_micros(edited_at).into()],
+    )
+}
+
+/// Soft deletes a message written by `user_id`, run within a transaction.
+pub(super) fn mark_message_deleted(
+    conn: &Connection,
+    tenant_id: i32,
+    message_id: i64,
+    user_id: i32,
+    deleted_at: DateTime<Utc>,
+) -> Result<message::Message, DomainError> {
+    change_message(
+        conn,
+        tenant_id,
+        message_id,
+        user_id,
+        "deleted_at = ?2",
+        vec![to_micros(deleted_at).into()],
+    )
+}
+
+/// Updates own visible message of the user and returns it.
+fn change_message(
+    conn: &Connection,
+    tenant_id: i32,
+    message_id: i64,
+    user_id: i32,
+    assignments: &'static str,
+    values: Vec<Value>,
+) -> Result<message::Message, DomainError> {
+    let now = to_micros(Utc::now());
+
+    let author_id: Option<i32> = conn
+        .query_row(
+            // language=sqlite
+            r#"
+            SELECT user_id AS user_id
+            FROM messages
+            WHERE tenant_id = ?1
+              AND message_id = ?2
+              AND deleted_at IS NULL
+              AND (expires_at IS NULL OR expires_at > ?3);
+            "#,
+            params![tenant_id, message_id, now],
+            |row| row.get(0),
+        )
+        .optional()?;
+    match author_id {
+        None => return Err(DomainError::NotFound("message")),
+        Some(author_id) if author_id != user_id => return Err(DomainError::Forbidden),
+        Some(_) => {}
+    }
+
+    let changed = conn.query_row(
+        &format!(
+            // language=sqlite
+            r#"
+            UPDATE messages
+            SET {assignments}
+            WHERE message_id = ?1
+            RETURNING message_id, message_content, user_id, posted_at, expires_at;
+            "#
+        ),
+        params_from_iter(std::iter::once(Value::from(message_id)).chain(values)),
+        message_from_row,
+    )?;
+
+    Ok(changed)
+}
+
+/// Conditions of visible messages of the tenant matching the filter.
+fn filter_conditions(tenant_id: i32, filter: &message::MessageFilter) -> Conditions {
+    let mut conditions = tenant_conditions(tenant_id);
+    let now = conditions.bind(to_micros(Utc::now()));
+    conditions.and("m.deleted_at IS NULL");
+    conditions.and(format!("(m.expires_at IS NULL OR m.expires_at > {now})"));
+
+    match_conditions(filter, conditions)
+}
+
+fn tenant_conditions(tenant_id: i32) -> Conditions {
+    let mut conditions = Conditions::new();
+    let param = conditions.bind(tenant_id);
+    conditions.and(format!("m.tenant_id = {param}"));
+
+    conditions
+}
+
+/// Adds conditions of the filter regardless of message visibility. `LIKE` of SQLite ignores
+/// case of ASCII letters only, unlike `ILIKE` of postgres.
+fn match_conditions(filter: &message::MessageFilter, mut conditions: Conditions) -> Conditions {
+    if let Some(user_id) = filter.user_id {
+        let param = conditions.bind(user_id);
+        conditions.and(format!("m.user_id = {param}"));
+    }
+    if let Some(since) = filter.since {
+        let param = conditions.bind(to_micros(since));
+        conditions.and(format!("m.posted_at >= {param}"));
+    }
+    if let Some(until) = filter.until {
+        let param = conditions.bind(to_micros(until));
+        conditions.and(format!("m.posted_at < {param}"));
+    }
+    if let Some(text) = &filter.text {
+        let pattern = match text {
+            message::TextMatch::Contains(text) => format!("%{}%", escape_like(text)),
+            message::TextMatch::Prefix(text) => format!("{}%", escape_like(text)),
+        };
+        let param = conditions.bind(pattern);
+        conditions.and(format!("m.message_content LIKE {param} ESCAPE '\\'"));
+    }
+
+    conditions
+}
+
+fn sort_order(order: message::SortOrder) -> &'static str {
+    match order {
+        message::SortOrder::Asc => "ASC",
+        message::SortOrder::Desc => "DESC",
+    }
+}
+
+/// Selects messages with author profiles, `tail` holds ordering and paging clauses.
+fn authored_messages_query(conditions: &Conditions, tail: &str) -> String {
+    format!(
+        // language=sqlite
+        r#"
+        SELECT m.message_id      AS message_id,
+               m.message_content AS message_content,
+               m.user_id         AS user_id,
+               m.posted_at       AS posted_at,
+               m.expires_at      AS expires_at,
+               u.display_name    AS display_name,
+               u.avatar_url      AS avatar_url
+        FROM messages AS m
+                 LEFT JOIN users AS u ON u.tenant_id = m.tenant_id AND u.user_id = m.user_id
+        {where_clause}
+        {tail};
+        "#,
+        where_clause = conditions.where_clause(),
+    )
+}
+
+fn message_from_row(row: &Row<'_>) -> rusqlite::Result<message::Message> {
+    Ok(message::Message {
+        message_id: row.get(0)?,
+        message_content: row.get(1)?,
+        user_id: row.get(2)?,
+        posted_at: from_micros(row.get(3)?),
+        expires_at: row.get::<_, Option<i64>>(4)?.map(from_micros),
+    })
+}
+
+fn authored_from_row(row: &Row<'_>) -> rusqlite::Result<message::AuthoredMessage> {
+    Ok(message::AuthoredMessage {
+        message_id: row.get(0)?,
+        message_content: row.get(1)?,
+        user_id: row.get(2)?,
+        posted_at: from_micros(row.get(3)?),
+        expires_at: row.get::<_, Option<i64>>(4)?.map(from_micros),
+        display_name: row.get(5)?,
+        avatar_url: row.get(6)?,
+    })
+}
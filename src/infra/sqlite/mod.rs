@@ -0,0 +1,183 @@
+//! Repositories backed by an SQLite file, for small installs without postgres.
+
+use std::{path::Path, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, OpenFlags};
+use tokio::sync::{Mutex, OwnedMutexGuard};
+
+use crate::{domain::errors::DomainError, infra::health::DependencyCheckTrait};
+
+pub mod bookmarks;
+mod errors;
+pub mod messages;
+pub mod pins;
+mod sql;
+pub mod tenants;
+pub mod unit_of_work;
+pub mod users;
+
+pub use bookmarks::SqliteBookmarksRepository;
+pub use messages::SqliteMessagesRepository;
+pub use pins::SqlitePinsRepository;
+pub use tenants::SqliteTenantsRepository;
+pub use unit_of_work::SqliteUnitOfWorkFactory;
+pub use users::SqliteUsersRepository;
+
+/// Migrations of the SQLite schema, the position in the list is the schema version.
+const MIGRATIONS: &[&str] = &[
+    include_str!("../../../migrations/sqlite/000001_init.up.sql"),
+    include_str!("../../../migrations/sqlite/000002_tenants.up.sql"),
+    include_str!("../../../migrations/sqlite/000003_tenant_users.up.sql"),
+];
+
+/// Connection shared by SQLite repositories. SQLite serializes writers anyway, so a single
+/// connection used from the blocking pool is enough.
+#[derive(Clone)]
+pub struct SqliteStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl SqliteStore {
+    /// Opens database file, creating it when missing, and applies pending migrations.
+    pub fn open(path: &Path) -> anyhow::Result<Self> {
+        let conn = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_WRITE | OpenFlags::SQLITE_OPEN_CREATE,
+        )?;
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+
+        Self::setup(conn)
+    }
+
+    /// Opens private database living in memory, used by tests.
+    pub fn open_in_memory() -> anyhow::Result<Self> {
+        Self::setup(Connection::open_in_memory()?)
+    }
+
+    fn setup(conn: Connection) -> anyhow::Result<Self> {
+        conn.pragma_update(None, "foreign_keys", true)?;
+        conn.busy_timeout(std::time::Duration::from_secs(5))?;
+        migrate(&conn)?;
+
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+        })
+    }
+
+    /// Runs blocking statements on the connection outside of the async runtime.
+    async fn call<T, F>(&self, f: F) -> Result<T, DomainError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Connection) -> Result<T, DomainError> + Send + 'static,
+    {
+        let (_, result) = run(self.lock().await, |conn| {
+            roll_back_abandoned(conn)?;
+            f(conn)
+        })
+        .await?;
+
+        result
+    }
+
+    /// Takes the connection for exclusive use until the guard is dropped.
+    async fn lock(&self) -> OwnedMutexGuard<Connection> {
+        self.conn.clone().lock_owned().await
+    }
+}
+
+/// Runs blocking statements on the locked connection and hands the connection back.
+async fn run<T, F>(
+    mut conn: OwnedMutexGuard<Connection>,
+    f: F,
+) -> Result<(OwnedMutexGuard<Connection>, Result<T, DomainError>), DomainError>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut Connection) -> Result<T, DomainError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || {
+        let result = f(&mut conn);
+        (conn, result)
+    })
+    .await
+    .map_err(|err| DomainError::Internal(err.into()))
+}
+
+/// Rolls back transaction left open by a unit of work dropped without commit.
+fn roll_back_abandoned(conn: &Connection) -> Result<(), DomainError> {
+    if !conn.is_autocommit() {
+        conn.execute_batch("ROLLBACK;")?;
+    }
+
+    Ok(())
+}
+
+#[async_trait]
+impl DependencyCheckTrait for SqliteStore {
+    async fn check(&self) -> Result<(), DomainError> {
+        self.call(|conn| {
+            conn.query_row("SELECT 1;", [], |_| Ok(()))?;
+            Ok(())
+        })
+        .await
+    }
+}
+
+fn migrate(conn: &Connection) -> anyhow::Result<()> {
+    let version: usize = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+        conn.execute_batch(migration)?;
+        conn.pragma_update(None, "user_version", index + 1)?;
+        tracing::info!("Applied sqlite migration {}", index + 1);
+    }
+
+    Ok(())
+}
+
+fn to_micros(at: DateTime<Utc>) -> i64 {
+    at.timestamp_micros()
+}
+
+fn from_micros(micros: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp_micros(micros).unwrap_or_default()
+}
+
+fn is_visible_message(
+    conn: &Connection,
+    tenant_id: i32,
+    message_id: i64,
+    now: i64,
+) -> Result<bool, rusqlite::Error> {
+    conn.query_row(
+        // language=sqlite
+        r#"
+        SELECT EXISTS (SELECT 1
+                       FROM messages
+                       WHERE tenant_id = ?1
+                         AND message_id = ?2
+                         AND deleted_at IS NULL
+                         AND (expires_at IS NULL OR expires_at > ?3));
+        "#,
+        rusqlite::params![tenant_id, message_id, now],
+        |row| row.get(0),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_is_repeatable() {
+        let conn = Connection::open_in_memory().unwrap();
+        migrate(&conn).unwrap();
+        migrate(&conn).unwrap();
+
+        let version: usize = conn
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .unwrap();
+        assert_eq!(version, MIGRATIONS.len());
+    }
+}
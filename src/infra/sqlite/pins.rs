@@ -0,0 +1,127 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use rusqlite::{Connection, params};
+
+use crate::{
+    domain::{bookmark, errors::DomainError},
+    infra::{
+        repositories::pins::PinsRepositoryTrait,
+        sqlite::{SqliteStore, from_micros, is_visible_message, to_micros},
+    },
+};
+
+#[derive(Clone)]
+pub struct SqlitePinsRepository {
+    store: SqliteStore,
+}
+
+impl SqlitePinsRepository {
+    pub fn new(store: SqliteStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl PinsRepositoryTrait for SqlitePinsRepository {
+    async fn pin_message(
+        &self,
+        tenant_id: i32,
+        message_id: i64,
+        pinned_by: i32,
+    ) -> Result<bool, DomainError> {
+        self.store
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+                let pinned = insert_pin(&tx, tenant_id, message_id, pinned_by)?;
+                tx.commit()?;
+
+                Ok(pinned)
+            })
+            .await
+    }
+
+    async fn unpin_message(&self, tenant_id: i32, message_id: i64) -> Result<bool, DomainError> {
+        self.store
+            .call(move |conn| delete_pin(conn, tenant_id, message_id))
+            .await
+    }
+
+    async fn list_pins(&self, tenant_id: i32) -> Result<Vec<bookmark::Pin>, DomainError> {
+        let now = to_micros(Utc::now());
+
+        self.store
+            .call(move |conn| {
+                let mut stmt = conn.prepare_cached(
+                    // language=sqlite
+                    r#"
+                    SELECT p.message_id, m.message_content, m.user_id, m.posted_at, p.pinned_by, p.pinned_at
+                    FROM pins AS p
+                             JOIN messages AS m ON m.message_id = p.message_id
+                    WHERE m.tenant_id = ?1
+                      AND m.deleted_at IS NULL
+                      AND (m.expires_at IS NULL OR m.expires_at > ?2)
+                    ORDER BY p.pinned_at DESC;
+                    "#,
+                )?;
+                let pins = stmt
+                    .query_map(params![tenant_id, now], |row| {
+                        Ok(bookmark::Pin {
+                            message_id: row.get(0)?,
+                            message_content: row.get(1)?,
+                            user_id: row.get(2)?,
+                            posted_at: from_micros(row.get(3)?),
+                            pinned_by: row.get(4)?,
+                            pinned_at: from_micros(row.get(5)?),
+                        })
+                    })?
+                    .collect::<Result<_, _>>()?;
+
+                Ok(pins)
+            })
+            .await
+    }
+}
+
+/// Pins visible message of the tenant, run within a transaction.
+pub(super) fn insert_pin(
+    conn: &Connection,
+    tenant_id: i32,
+    message_id: i64,
+    pinned_by: i32,
+) -> Result<bool, DomainError> {
+    let now = to_micros(Utc::now());
+    if !is_visible_message(conn, tenant_id, message_id, now)? {
+        return Ok(false);
+    }
+
+    conn.execute(
+        // language=sqlite
+        r#"
+        INSERT INTO pins (message_id, pinned_by, pinned_at)
+        VALUES (?1, ?2, ?3)
+        ON CONFLICT (message_id) DO NOTHING;
+        "#,
+        params![message_id, pinned_by, now],
+    )?;
+
+    Ok(true)
+}
+
+pub(super) fn delete_pin(
+    conn: &Connection,
+    tenant_id: i32,
+    message_id: i64,
+) -> Result<bool, DomainError> {
+    let deleted = conn.execute(
+        // language=sqlite
+        r#"
+        DELETE
+        FROM pins
+        WHERE message_id = ?2
+          AND message_id IN (SELECT message_id FROM messages WHERE tenant_id = ?1);
+        "#,
+        params![tenant_id, message_id],
+    )?;
+
+    Ok(deleted > 0)
+}
@@ -0,0 +1,36 @@
+use rusqlite::types::Value;
+
+/// `WHERE` clause assembled from optional conditions, SQLite flavour of
+/// [`crate::infra::repositories`] conditions. Values are always bound as numbered parameters.
+#[derive(Default)]
+pub(crate) struct Conditions {
+    conditions: Vec<String>,
+    params: Vec<Value>,
+}
+
+impl Conditions {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a parameter and returns its placeholder, e.g. `?3`.
+    pub(crate) fn bind(&mut self, value: impl Into<Value>) -> String {
+        self.params.push(value.into());
+        format!("?{}", self.params.len())
+    }
+
+    pub(crate) fn and(&mut self, condition: impl Into<String>) {
+        self.conditions.push(condition.into());
+    }
+
+    pub(crate) fn where_clause(&self) -> String {
+        if self.conditions.is_empty() {
+            return String::new();
+        }
+        format!("WHERE {}", self.conditions.join("\n  AND "))
+    }
+
+    pub(crate) fn params(&self) -> rusqlite::ParamsFromIter<std::slice::Iter<'_, Value>> {
+        rusqlite::params_from_iter(self.params.iter())
+    }
+}
@@ -0,0 +1,100 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use rusqlite::{OptionalExtension, Row, params};
+
+use crate::{
+    domain::{errors::DomainError, tenant},
+    infra::{
+        repositories::tenants::TenantsRepositoryTrait,
+        sqlite::{SqliteStore, from_micros, to_micros},
+    },
+};
+
+#[derive(Clone)]
+pub struct SqliteTenantsRepository {
+    store: SqliteStore,
+}
+
+impl SqliteTenantsRepository {
+    pub fn new(store: SqliteStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl TenantsRepositoryTrait for SqliteTenantsRepository {
+    async fn create_tenant(&self, name: String) -> Result<tenant::Tenant, DomainError> {
+        let now = to_micros(Utc::now());
+
+        self.store
+            .call(move |conn| {
+                let created = conn.query_row(
+                    // language=sqlite
+                    r#"
+                    INSERT INTO tenants (name, created_at)
+                    VALUES (?1, ?2)
+                    RETURNING tenant_id, name, created_at, suspended_at;
+                    "#,
+                    params![name, now],
+                    tenant_from_row,
+                )?;
+
+                Ok(created)
+            })
+            .await
+    }
+
+    async fn get_tenant(&self, tenant_id: i32) -> Result<Option<tenant::Tenant>, DomainError> {
+        self.store
+            .call(move |conn| {
+                let found = conn
+                    .query_row(
+                        // language=sqlite
+                        r#"
+                        SELECT tenant_id, name, created_at, suspended_at
+                        FROM tenants
+                        WHERE tenant_id = ?1;
+                        "#,
+                        params![tenant_id],
+                        tenant_from_row,
+                    )
+                    .optional()?;
+
+                Ok(found)
+            })
+            .await
+    }
+
+    async fn suspend_tenant(&self, tenant_id: i32) -> Result<Option<tenant::Tenant>, DomainError> {
+        let now = to_micros(Utc::now());
+
+        self.store
+            .call(move |conn| {
+                let suspended = conn
+                    .query_row(
+                        // language=sqlite
+                        r#"
+                        UPDATE tenants
+                        SET suspended_at = coalesce(suspended_at, ?2)
+                        WHERE tenant_id = ?1
+                        RETURNING tenant_id, name, created_at, suspended_at;
+                        "#,
+                        params![tenant_id, now],
+                        tenant_from_row,
+                    )
+                    .optional()?;
+
+                Ok(suspended)
+            })
+            .await
+    }
+}
+
+fn tenant_from_row(row: &Row<'_>) -> rusqlite::Result<tenant::Tenant> {
+    Ok(tenant::Tenant {
+        tenant_id: row.get(0)?,
+        name: row.get(1)?,
+        created_at: from_micros(row.get(2)?),
+        suspended_at: row.get::<_, Option<i64>>(3)?.map(from_micros),
+    })
+}
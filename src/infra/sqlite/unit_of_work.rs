@@ -0,0 +1,148 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+use tokio::sync::OwnedMutexGuard;
+
+use crate::{
+    domain::{errors::DomainError, message},
+    infra::{
+        repositories::unit_of_work::{UnitOfWorkFactoryTrait, UnitOfWorkTrait},
+        sqlite::{SqliteStore, bookmarks, messages, pins, roll_back_abandoned, run},
+    },
+};
+
+/// SQLite counterpart of [`crate::infra::repositories::UnitOfWorkFactory`]. Webhook events
+/// are not recorded, since there is no worker to deliver them.
+#[derive(Clone)]
+pub struct SqliteUnitOfWorkFactory {
+    store: SqliteStore,
+}
+
+impl SqliteUnitOfWorkFactory {
+    pub fn new(store: SqliteStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl UnitOfWorkFactoryTrait for SqliteUnitOfWorkFactory {
+    async fn begin(&self, tenant_id: i32) -> Result<Box<dyn UnitOfWorkTrait>, DomainError> {
+        let (conn, result) = run(self.store.lock().await, |conn| {
+            roll_back_abandoned(conn)?;
+            conn.execute_batch("BEGIN IMMEDIATE;")?;
+            Ok(())
+        })
+        .await?;
+        result?;
+
+        Ok(Box::new(SqliteUnitOfWork {
+            conn: Some(conn),
+            tenant_id,
+        }))
+    }
+}
+
+/// Transaction on the connection, which stays locked until commit or rollback. A unit dropped
+/// without either is rolled back by the next user of the connection.
+pub struct SqliteUnitOfWork {
+    conn: Option<OwnedMutexGuard<Connection>>,
+    tenant_id: i32,
+}
+
+impl SqliteUnitOfWork {
+    async fn call<T, F>(&mut self, f: F) -> Result<T, DomainError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Connection) -> Result<T, DomainError> + Send + 'static,
+    {
+        let conn = self
+            .conn
+            .take()
+            .ok_or_else(|| DomainError::Internal(anyhow::anyhow!("unit of work is finished")))?;
+        let (conn, result) = run(conn, f).await?;
+        self.conn = Some(conn);
+
+        result
+    }
+
+    async fn finish(mut self: Box<Self>, statement: &'static str) -> Result<(), DomainError> {
+        self.call(move |conn| Ok(conn.execute_batch(statement)?))
+            .await?;
+        // unlocked only once the transaction is over
+        self.conn.take();
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl UnitOfWorkTrait for SqliteUnitOfWork {
+    async fn create_message(&mut self, msg: message::PostMessage) -> Result<i64, DomainError> {
+        let tenant_id = self.tenant_id;
+        self.call(move |conn| messages::insert_message(conn, tenant_id, msg))
+            .await
+    }
+
+    async fn update_message(
+        &mut self,
+        message_id: i64,
+        user_id: i32,
+        content: String,
+        edited_at: DateTime<Utc>,
+    ) -> Result<message::Message, DomainError> {
+        let tenant_id = self.tenant_id;
+        self.call(move |conn| {
+            messages::edit_message(conn, tenant_id, message_id, user_id, content, edited_at)
+        })
+        .await
+    }
+
+    async fn delete_message(
+        &mut self,
+        message_id: i64,
+        user_id: i32,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<message::Message, DomainError> {
+        let tenant_id = self.tenant_id;
+        self.call(move |conn| {
+            messages::mark_message_deleted(conn, tenant_id, message_id, user_id, deleted_at)
+        })
+        .await
+    }
+
+    async fn pin_message(&mut self, message_id: i64, pinned_by: i32) -> Result<bool, DomainError> {
+        let tenant_id = self.tenant_id;
+        self.call(move |conn| pins::insert_pin(conn, tenant_id, message_id, pinned_by))
+            .await
+    }
+
+    async fn unpin_message(&mut self, message_id: i64) -> Result<bool, DomainError> {
+        let tenant_id = self.tenant_id;
+        self.call(move |conn| pins::delete_pin(conn, tenant_id, message_id))
+            .await
+    }
+
+    async fn add_bookmark(&mut self, user_id: i32, message_id: i64) -> Result<bool, DomainError> {
+        let tenant_id = self.tenant_id;
+        self.call(move |conn| bookmarks::insert_bookmark(conn, tenant_id, user_id, message_id))
+            .await
+    }
+
+    async fn remove_bookmark(
+        &mut self,
+        user_id: i32,
+        message_id: i64,
+    ) -> Result<bool, DomainError> {
+        let tenant_id = self.tenant_id;
+        self.call(move |conn| bookmarks::delete_bookmark(conn, tenant_id, user_id, message_id))
+            .await
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), DomainError> {
+        self.finish("COMMIT;").await
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), DomainError> {
+        self.finish("ROLLBACK;").await
+    }
+}
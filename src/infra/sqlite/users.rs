@@ -0,0 +1,104 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use rusqlite::{OptionalExtension, Row, params};
+
+use crate::{
+    domain::{errors::DomainError, user},
+    infra::{
+        repositories::users::UsersRepositoryTrait,
+        sqlite::{SqliteStore, to_micros},
+    },
+};
+
+#[derive(Clone)]
+pub struct SqliteUsersRepository {
+    store: SqliteStore,
+}
+
+impl SqliteUsersRepository {
+    pub fn new(store: SqliteStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl UsersRepositoryTrait for SqliteUsersRepository {
+    async fn get_profile(
+        &self,
+        tenant_id: i32,
+        user_id: i32,
+    ) -> Result<Option<user::UserProfile>, DomainError> {
+        self.store
+            .call(move |conn| {
+                let profile = conn
+                    .query_row(
+                        // language=sqlite
+                        r#"
+                        SELECT user_id, display_name, avatar_url, bio
+                        FROM users
+                        WHERE tenant_id = ?1
+                          AND user_id = ?2;
+                        "#,
+                        params![tenant_id, user_id],
+                        profile_from_row,
+                    )
+                    .optional()?;
+
+                Ok(profile)
+            })
+            .await
+    }
+
+    async fn update_profile(
+        &self,
+        tenant_id: i32,
+        user_id: i32,
+        change: user::ProfileChange,
+    ) -> Result<user::UserProfile, DomainError> {
+        let now = to_micros(Utc::now());
+
+        self.store
+            .call(move |conn| {
+                let profile = conn.query_row(
+                    // language=sqlite
+                    r#"
+                    INSERT INTO users (tenant_id, user_id, display_name, avatar_url, bio,
+                                       updated_at)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                    ON CONFLICT (tenant_id, user_id) DO UPDATE
+                        SET display_name = CASE WHEN ?7 THEN excluded.display_name
+                                                ELSE users.display_name END,
+                            avatar_url   = CASE WHEN ?8 THEN excluded.avatar_url
+                                                ELSE users.avatar_url END,
+                            bio          = CASE WHEN ?9 THEN excluded.bio ELSE users.bio END,
+                            updated_at   = excluded.updated_at
+                    RETURNING user_id, display_name, avatar_url, bio;
+                    "#,
+                    params![
+                        tenant_id,
+                        user_id,
+                        change.display_name.clone().flatten(),
+                        change.avatar_url.clone().flatten(),
+                        change.bio.clone().flatten(),
+                        now,
+                        change.display_name.is_some(),
+                        change.avatar_url.is_some(),
+                        change.bio.is_some()
+                    ],
+                    profile_from_row,
+                )?;
+
+                Ok(profile)
+            })
+            .await
+    }
+}
+
+fn profile_from_row(row: &Row<'_>) -> rusqlite::Result<user::UserProfile> {
+    Ok(user::UserProfile {
+        user_id: row.get(0)?,
+        display_name: row.get(1)?,
+        avatar_url: row.get(2)?,
+        bio: row.get(3)?,
+    })
+}
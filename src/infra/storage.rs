@@ -0,0 +1,32 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+
+/// Where repositories keep their data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum StorageBackend {
+    Postgres,
+    /// Process memory, lost on restart. Webhooks, integrations and scheduled messages are
+    /// unavailable.
+    Memory,
+    /// Single SQLite file. Webhooks, integrations and scheduled messages are unavailable.
+    Sqlite,
+}
+
+/// Define storage config.
+#[derive(Parser, Debug, Clone)]
+pub struct StorageConfig {
+    /// Storage of the chat. Env variable name: `STORAGE_BACKEND`.
+    #[arg(long, env = "STORAGE_BACKEND", value_enum, default_value = "postgres")]
+    pub backend: StorageBackend,
+
+    /// Database file of sqlite storage, created when missing. Env variable name: `SQLITE_PATH`.
+    #[arg(long, env = "SQLITE_PATH", default_value = "chat.sqlite3")]
+    pub sqlite_path: PathBuf,
+}
+
+impl StorageConfig {
+    pub fn parse() -> StorageConfig {
+        StorageConfig::try_parse().expect("Parsing configuration failed.")
+    }
+}
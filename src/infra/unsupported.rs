@@ -0,0 +1,143 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::{
+    domain::{errors::DomainError, integration, scheduled, webhook},
+    infra::repositories::{
+        integrations::IntegrationsRepositoryTrait,
+        scheduled_messages::ScheduledMessagesRepositoryTrait, webhooks::WebhooksRepositoryTrait,
+    },
+};
+
+/// Stands in for repositories of features that need the worker and therefore postgres:
+/// webhooks, integrations and scheduled messages. Every call fails with `Unavailable`, used by
+/// embedded storage backends.
+#[derive(Clone, Copy, Default)]
+pub struct Unsupported;
+
+fn unsupported<T>() -> Result<T, DomainError> {
+    Err(DomainError::Unavailable(
+        "feature requires postgres storage".to_owned(),
+    ))
+}
+
+#[async_trait]
+impl WebhooksRepositoryTrait for Unsupported {
+    async fn create_webhook(
+        &self,
+        _webhook: webhook::NewWebhook,
+    ) -> Result<webhook::Webhook, DomainError> {
+        unsupported()
+    }
+
+    async fn list_webhooks(&self, _tenant_id: i32) -> Result<Vec<webhook::Webhook>, DomainError> {
+        unsupported()
+    }
+
+    async fn delete_webhook(&self, _tenant_id: i32, _webhook_id: i64) -> Result<bool, DomainError> {
+        unsupported()
+    }
+
+    async fn claim_deliveries(
+        &self,
+        _limit: i64,
+        _lease: Duration,
+    ) -> Result<Vec<webhook::Delivery>, DomainError> {
+        unsupported()
+    }
+
+    async fn complete_delivery(&self, _delivery_id: i64) -> Result<(), DomainError> {
+        unsupported()
+    }
+
+    async fn fail_delivery(
+        &self,
+        _delivery_id: i64,
+        _error: String,
+        _retry_at: Option<DateTime<Utc>>,
+    ) -> Result<(), DomainError> {
+        unsupported()
+    }
+}
+
+#[async_trait]
+impl IntegrationsRepositoryTrait for Unsupported {
+    async fn create_integration(
+        &self,
+        _integration: integration::NewIntegration,
+    ) -> Result<integration::Integration, DomainError> {
+        unsupported()
+    }
+
+    async fn list_integrations(
+        &self,
+        _tenant_id: i32,
+    ) -> Result<Vec<integration::Integration>, DomainError> {
+        unsupported()
+    }
+
+    async fn find_active_integration(
+        &self,
+        _token_hash: String,
+    ) -> Result<Option<integration::Integration>, DomainError> {
+        unsupported()
+    }
+
+    async fn rotate_integration_token(
+        &self,
+        _tenant_id: i32,
+        _integration_id: i64,
+        _token_hash: String,
+    ) -> Result<bool, DomainError> {
+        unsupported()
+    }
+
+    async fn revoke_integration(
+        &self,
+        _tenant_id: i32,
+        _integration_id: i64,
+    ) -> Result<bool, DomainError> {
+        unsupported()
+    }
+}
+
+#[async_trait]
+impl ScheduledMessagesRepositoryTrait for Unsupported {
+    async fn schedule_message(&self, _msg: scheduled::ScheduleMessage) -> Result<i64, DomainError> {
+        unsupported()
+    }
+
+    async fn list_scheduled_messages(
+        &self,
+        _tenant_id: i32,
+        _user_id: i32,
+    ) -> Result<Vec<scheduled::ScheduledMessage>, DomainError> {
+        unsupported()
+    }
+
+    async fn update_scheduled_message(
+        &self,
+        _tenant_id: i32,
+        _scheduled_id: i64,
+        _user_id: i32,
+        _content: Option<String>,
+        _send_at: Option<DateTime<Utc>>,
+    ) -> Result<Option<scheduled::ScheduledMessage>, DomainError> {
+        unsupported()
+    }
+
+    async fn cancel_scheduled_message(
+        &self,
+        _tenant_id: i32,
+        _scheduled_id: i64,
+        _user_id: i32,
+    ) -> Result<bool, DomainError> {
+        unsupported()
+    }
+
+    async fn publish_due_messages(&self, _limit: i64) -> Result<u64, DomainError> {
+        unsupported()
+    }
+}
@@ -1,5 +1,7 @@
 pub mod api;
+pub mod commands;
 pub mod cronjob;
 pub mod domain;
 pub mod entities;
+pub mod importer;
 pub mod infra;